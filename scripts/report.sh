@@ -2,5 +2,5 @@
 
 Deps=cargo-tarpaulin 
 cargo install ${Deps}
-cargo tarpaulin --out Html
+cargo tarpaulin --workspace --out Html
 
@@ -0,0 +1,552 @@
+//! Headless runner, assembles a file and runs it with the syscalls on
+//! stdin/stdout.
+//!
+//! Exit codes: 0 on success, 1 on invalid arguments or IO errors, 2 on
+//! assembly errors, 3 on runtime faults and 4 when a run limit is reached.
+
+use std::{
+    io::{BufRead, Write},
+    process::ExitCode,
+    sync::{Arc, Condvar, Mutex},
+};
+
+use moras_core::{
+    interface::{
+        assembler::Assembler,
+        parser::Parser,
+        simulator::{Simulator, SimulatorEventSink},
+    },
+    modules::riscv::basic::{
+        assembler::assembler::RiscVAssembler,
+        interface::parser::{RISCVExtension, RISCVParser, RV32IRegister},
+    },
+    simulator::simulator::RISCVSimulator,
+    types::middleware_types::{AssemblerConfig, MemoryReturnRange, Optional, Register, Xlen},
+    utility::ptr::Ptr,
+};
+use serde::Serialize;
+use strum::VariantArray;
+
+const USAGE: &str = "usage: moras-cli [options] <file.s>
+
+options:
+    --config <file>         AssemblerConfig as JSON
+    --max-steps <n>         stop after n instructions
+    --time-limit <ms>       stop after running for ms milliseconds
+    --snapshot <file>       start from a snapshot of the program
+    --save-snapshot <file>  write a snapshot of the final state
+    --dump-code             write the .text and .data dumps next to the file
+    --dump <file>           write the final registers and memory, - for stdout
+    --dump-format <fmt>     text (default) or json
+    --dump-memory <a>:<n>   add n bytes at address a to the dump, repeatable";
+
+const EXIT_USAGE: u8 = 1;
+const EXIT_ASSEMBLY: u8 = 2;
+const EXIT_FAULT: u8 = 3;
+const EXIT_LIMIT: u8 = 4;
+
+#[derive(PartialEq, Eq)]
+enum DumpFormat {
+    Text,
+    Json,
+}
+
+struct Options {
+    file: String,
+    config: Option<String>,
+    max_steps: Option<u64>,
+    time_limit: Option<u64>,
+    snapshot: Option<String>,
+    save_snapshot: Option<String>,
+    dump_code: bool,
+    dump: Option<String>,
+    dump_format: DumpFormat,
+    dump_memory: Vec<MemoryReturnRange>,
+}
+
+/// Wires the syscalls to stdin/stdout and wakes `main` on every update.
+struct CliSink {
+    sim_ptr: Ptr<RISCVSimulator>,
+    // result of the last update and whether it paused
+    res: Mutex<Option<(Optional, bool)>>,
+    cv: Condvar,
+}
+
+impl CliSink {
+    fn notify(&self, res: Optional, paused: bool) {
+        *self.res.lock().unwrap() = Some((res, paused));
+        self.cv.notify_one();
+    }
+}
+
+impl SimulatorEventSink for CliSink {
+    fn update(&self, simulator: &dyn Simulator, res: Optional, paused: bool) {
+        // the input resumes the simulator
+        if !simulator.is_waiting_input() {
+            self.notify(res, paused);
+        }
+    }
+
+    fn output(&self, _filepath: &str, output: &str) -> Result<(), String> {
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(output.as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(|e| e.to_string())
+    }
+
+    fn request_input(&self, _filepath: &str) -> Result<(), String> {
+        let sink_ptr = Ptr::new(self);
+        // the simulator thread has to return before the input resumes it
+        std::thread::spawn(move || {
+            let sink = sink_ptr.as_ref();
+            let mut line = String::new();
+            let res = std::io::stdin()
+                .lock()
+                .read_line(&mut line)
+                .map_err(|e| e.to_string())
+                .and_then(|_| {
+                    let line = line.trim_end_matches(['\n', '\r']);
+                    sink.sim_ptr.as_mut().syscall_input(line)
+                });
+            if let Err(e) = res {
+                sink.notify(
+                    Optional {
+                        success: false,
+                        message: e,
+                    },
+                    false,
+                );
+            }
+        });
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct Dump {
+    pc: Option<u32>,
+    registers: Vec<Register>,
+    memory: Vec<MemoryDump>,
+}
+
+#[derive(Serialize)]
+struct MemoryDump {
+    address: u64,
+    words: Vec<u32>,
+}
+
+fn main() -> ExitCode {
+    let opts = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(opts)) => opts,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    match run(&opts) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err((code, e)) => {
+            eprintln!("{}", e);
+            ExitCode::from(code)
+        }
+    }
+}
+
+/// `None` if the usage is asked for.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut opts = Options {
+        file: String::new(),
+        config: None,
+        max_steps: None,
+        time_limit: None,
+        snapshot: None,
+        save_snapshot: None,
+        dump_code: false,
+        dump: None,
+        dump_format: DumpFormat::Text,
+        dump_memory: Vec::new(),
+    };
+    let mut file = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--config" => opts.config = Some(value()?),
+            "--max-steps" => {
+                opts.max_steps = Some(value()?.parse().map_err(|_| "Invalid --max-steps")?)
+            }
+            "--time-limit" => {
+                opts.time_limit = Some(value()?.parse().map_err(|_| "Invalid --time-limit")?)
+            }
+            "--snapshot" => opts.snapshot = Some(value()?),
+            "--save-snapshot" => opts.save_snapshot = Some(value()?),
+            "--dump-code" => opts.dump_code = true,
+            "--dump" => opts.dump = Some(value()?),
+            "--dump-format" => {
+                opts.dump_format = match value()?.as_str() {
+                    "text" => DumpFormat::Text,
+                    "json" => DumpFormat::Json,
+                    _ => return Err("Invalid --dump-format".to_string()),
+                }
+            }
+            "--dump-memory" => {
+                let value = value()?;
+                let range = value
+                    .split_once(':')
+                    .and_then(|(start, len)| Some((parse_int(start)?, parse_int(len)?)))
+                    .ok_or("Invalid --dump-memory")?;
+                opts.dump_memory.push(MemoryReturnRange {
+                    start: range.0,
+                    len: range.1,
+                });
+            }
+            "-h" | "--help" => return Ok(None),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ if file.is_none() => file = Some(arg),
+            _ => return Err(format!("Unexpected argument {}", arg)),
+        }
+    }
+    opts.file = file.ok_or("Missing input file")?;
+    Ok(Some(opts))
+}
+
+/// Decimal or `0x` prefixed hexadecimal.
+fn parse_int(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+fn io_err(e: impl std::fmt::Display) -> (u8, String) {
+    (EXIT_USAGE, e.to_string())
+}
+
+fn run(opts: &Options) -> Result<(), (u8, String)> {
+    let mut config: AssemblerConfig = match &opts.config {
+        Some(path) => {
+            let json = std::fs::read_to_string(path).map_err(io_err)?;
+            serde_json::from_str(&json).map_err(|e| (EXIT_USAGE, format!("{}: {}", path, e)))?
+        }
+        None => AssemblerConfig::default(),
+    };
+    if let Some(max_steps) = opts.max_steps {
+        config.run_limits.max_instructions = max_steps;
+    }
+    if let Some(time_limit) = opts.time_limit {
+        config.run_limits.time_limit_ms = time_limit;
+    }
+    let code = std::fs::read_to_string(&opts.file).map_err(io_err)?;
+
+    let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(config.xlen));
+    parser.set_data_endianness(config.data_endianness);
+    let ast = parser.parse(&code).map_err(|e| {
+        let msg = e
+            .iter()
+            .map(|e| format!("{}:{}:{}: {}", opts.file, e.pos.0 + 1, e.pos.1 + 1, e.msg))
+            .collect::<Vec<_>>()
+            .join("\n");
+        (EXIT_ASSEMBLY, msg)
+    })?;
+    let mut assembler = RiscVAssembler::new();
+    assembler.update_config(&config);
+    let assembly_err = |e: Vec<_>| {
+        let msg = e
+            .iter()
+            .map(|e: &moras_core::interface::assembler::AssemblyError| {
+                format!("{}:{}: {}", opts.file, e.line + 1, e.msg)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        (EXIT_ASSEMBLY, msg)
+    };
+    if opts.dump_code {
+        let mem = assembler.dump(ast.clone()).map_err(assembly_err)?;
+        let path = std::path::Path::new(&opts.file);
+        for (ext, data) in [("text", &mem.text), ("data", &mem.data)] {
+            std::fs::write(path.with_extension(ext), data).map_err(io_err)?;
+        }
+    }
+    let inst = assembler.assemble(ast).map_err(assembly_err)?;
+
+    // the simulator threads outlive `run`
+    let sim = Box::leak(Box::new(RISCVSimulator::new(&opts.file)));
+    let sink = Arc::new(CliSink {
+        sim_ptr: Ptr::new(sim),
+        res: Mutex::new(None),
+        cv: Condvar::new(),
+    });
+    sim.set_event_sink(Some(sink.clone()));
+    sim.update_config(&config).map_err(|e| (EXIT_USAGE, e))?;
+    sim.load_inst(inst).map_err(|e| (EXIT_ASSEMBLY, e))?;
+    // loading notifies too
+    sink.res.lock().unwrap().take();
+
+    match &opts.snapshot {
+        // restoring pauses, the loop resumes it unless an input does
+        Some(path) => {
+            let snapshot = std::fs::read_to_string(path).map_err(io_err)?;
+            sim.restore_snapshot(&snapshot)
+                .map_err(|e| (EXIT_USAGE, format!("{}: {}", path, e)))?;
+        }
+        None => sim.run().map_err(|e| (EXIT_FAULT, e))?,
+    }
+    let res = loop {
+        let mut lock = sink.res.lock().unwrap();
+        while lock.is_none() {
+            lock = sink.cv.wait(lock).unwrap();
+        }
+        let (res, paused) = lock.take().unwrap();
+        drop(lock);
+        // only `ebreak` pauses a run, besides restoring
+        if paused {
+            sim.resume().map_err(|e| (EXIT_FAULT, e))?;
+            continue;
+        }
+        break match (res.success, sim.get_exceeded_limit()) {
+            (_, Some(_)) => Err((EXIT_LIMIT, res.message)),
+            (false, None) => Err((EXIT_FAULT, res.message)),
+            (true, None) => Ok(()),
+        };
+    };
+
+    if let Some(path) = &opts.save_snapshot {
+        let snapshot = sim.save_snapshot().map_err(|e| (EXIT_USAGE, e))?;
+        std::fs::write(path, snapshot).map_err(io_err)?;
+    }
+    if let Some(path) = &opts.dump {
+        let dump = dump(sim, &opts.dump_memory).map_err(|e| (EXIT_USAGE, e))?;
+        let out = match opts.dump_format {
+            DumpFormat::Text => dump_text(&dump, config.xlen),
+            DumpFormat::Json => serde_json::to_string_pretty(&dump).unwrap() + "\n",
+        };
+        if path == "-" {
+            print!("{}", out);
+        } else {
+            std::fs::write(path, out).map_err(io_err)?;
+        }
+    }
+    res
+}
+
+fn dump(sim: &mut RISCVSimulator, ranges: &[MemoryReturnRange]) -> Result<Dump, String> {
+    let pc = sim
+        .get_pc_idx()
+        .map(|idx| sim.get_raw_inst().as_ref().unwrap().instruction[idx].address);
+    let registers = sim
+        .get_register()
+        .iter()
+        .enumerate()
+        .map(|(i, &val)| Register {
+            name: RV32IRegister::VARIANTS[i].to_string(),
+            number: i.to_string(),
+            value: val,
+        })
+        .collect();
+    let mut memory = Vec::new();
+    for range in ranges {
+        sim.set_memory_return_range(*range)?;
+        memory.push(MemoryDump {
+            address: range.start,
+            words: sim.get_memory(),
+        });
+    }
+    Ok(Dump {
+        pc,
+        registers,
+        memory,
+    })
+}
+
+fn dump_text(dump: &Dump, xlen: Xlen) -> String {
+    let width = match xlen {
+        Xlen::Rv32 => 8,
+        Xlen::Rv64 => 16,
+    };
+    let mut out = String::new();
+    match dump.pc {
+        Some(pc) => out += &format!("pc   0x{:08x}\n", pc),
+        None => out += "pc   end\n",
+    }
+    for reg in &dump.registers {
+        out += &format!(
+            "x{:<2} {:<4} 0x{:0width$x}\n",
+            reg.number,
+            reg.name,
+            reg.value,
+            width = width
+        );
+    }
+    for mem in &dump.memory {
+        for (i, chunk) in mem.words.chunks(4).enumerate() {
+            out += &format!("0x{:08x}:", mem.address + i as u64 * 16);
+            for word in chunk {
+                out += &format!(" 0x{:08x}", word);
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Option<Options>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    /// Runs `code` from a temporary file with `extra` options, returns the
+    /// exit code.
+    fn run_code(name: &str, code: &str, extra: &[&str]) -> u8 {
+        let path = std::env::temp_dir().join(format!("moras-cli-{}.s", name));
+        std::fs::write(&path, code).unwrap();
+        let mut argv = extra.to_vec();
+        argv.push(path.to_str().unwrap());
+        match run(&args(&argv).unwrap().unwrap()) {
+            Ok(()) => 0,
+            Err((code, _)) => code,
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let opts = args(&[
+            "--max-steps",
+            "10",
+            "--dump",
+            "-",
+            "--dump-format",
+            "json",
+            "--dump-memory",
+            "0x10010000:16",
+            "--dump-memory",
+            "4:8",
+            "a.s",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(opts.file, "a.s");
+        assert_eq!(opts.max_steps, Some(10));
+        assert_eq!(opts.dump.as_deref(), Some("-"));
+        assert!(opts.dump_format == DumpFormat::Json);
+        assert_eq!(
+            opts.dump_memory,
+            vec![
+                MemoryReturnRange {
+                    start: 0x10010000,
+                    len: 16,
+                },
+                MemoryReturnRange { start: 4, len: 8 },
+            ]
+        );
+        assert!(args(&["a.s"]).unwrap().unwrap().dump_format == DumpFormat::Text);
+        assert!(args(&["-h"]).unwrap().is_none());
+        assert!(args(&["a.s", "--help"]).unwrap().is_none());
+
+        for argv in [
+            &["--dump-memory", "16", "a.s"][..],
+            &["--dump-memory", "0x:4", "a.s"],
+            &["--dump-format", "xml", "a.s"],
+            &["--max-steps", "ten", "a.s"],
+            &["a.s", "--max-steps"],
+            &["--frobnicate", "a.s"],
+            &["a.s", "b.s"],
+            &[],
+        ] {
+            assert!(args(argv).is_err());
+        }
+    }
+
+    #[test]
+    fn test_dump_text() {
+        let dump = Dump {
+            pc: Some(0x400004),
+            registers: vec![Register {
+                name: "ra".to_string(),
+                number: "1".to_string(),
+                value: 0xffff_fffe,
+            }],
+            memory: vec![MemoryDump {
+                address: 0x10010000,
+                words: vec![1, 2, 3, 4, 5],
+            }],
+        };
+        assert_eq!(
+            dump_text(&dump, Xlen::Rv32),
+            "pc   0x00400004\n\
+             x1  ra   0xfffffffe\n\
+             0x10010000: 0x00000001 0x00000002 0x00000003 0x00000004\n\
+             0x10010010: 0x00000005\n"
+        );
+        assert!(dump_text(&dump, Xlen::Rv64).contains("x1  ra   0x00000000fffffffe\n"));
+
+        let dump = Dump {
+            pc: None,
+            registers: Vec::new(),
+            memory: Vec::new(),
+        };
+        assert_eq!(dump_text(&dump, Xlen::Rv32), "pc   end\n");
+    }
+
+    #[test]
+    fn test_run() {
+        let out = std::env::temp_dir().join("moras-cli-dump.json");
+        let code = "
+.data
+v: .word 7
+.text
+    li a0, 1
+";
+        assert_eq!(
+            run_code(
+                "ok",
+                code,
+                &[
+                    "--dump",
+                    out.to_str().unwrap(),
+                    "--dump-format",
+                    "json",
+                    "--dump-memory",
+                    "0x10010000:4",
+                ]
+            ),
+            0
+        );
+        let dump: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(dump["pc"], serde_json::Value::Null);
+        assert_eq!(dump["registers"][10]["name"], "a0");
+        assert_eq!(dump["registers"][10]["value"], 1);
+        assert_eq!(dump["memory"][0]["address"], 0x10010000);
+        assert_eq!(dump["memory"][0]["words"][0], 7);
+
+        assert_eq!(
+            run_code("assembly", ".text\n    frob a0\n", &[]),
+            EXIT_ASSEMBLY
+        );
+        let code = "
+.text
+    li t0, 0x100
+    lw a0, 0(t0)
+";
+        assert_eq!(run_code("fault", code, &[]), EXIT_FAULT);
+        let code = "
+.text
+loop:
+    j loop
+";
+        assert_eq!(run_code("limit", code, &["--max-steps", "100"]), EXIT_LIMIT);
+        assert_eq!(
+            run(&args(&["moras-cli-missing.s"]).unwrap().unwrap())
+                .unwrap_err()
+                .0,
+            EXIT_USAGE
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display};
 
 use crate::{
     interface::parser::{ParserInstSet, ParserResult},
@@ -23,7 +23,18 @@ pub trait InstructionSetTrait {
 #[derive(Clone)]
 pub struct AssembleResult<IS: ParserInstSet + InstructionSetTrait> {
     pub data: Vec<u8>,
+    pub sections: Vec<AssembleSection>,
     pub instruction: Vec<InstructionSet<IS>>,
+    /// address of every label
+    pub labels: BTreeMap<String, u32>,
+}
+
+#[derive(Clone)]
+pub struct AssembleSection {
+    pub name: String,
+    pub address: u32,
+    pub data: Vec<u8>,
+    pub read_only: bool,
 }
 
 #[derive(Clone)]
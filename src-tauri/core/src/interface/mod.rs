@@ -0,0 +1,3 @@
+pub mod assembler;
+pub mod parser;
+pub mod simulator;
@@ -6,6 +6,7 @@ pub trait Parser<IS: ParserInstSet>: Send + Sync {
 pub trait ParserInstSet {
     type Operator: Clone + std::fmt::Debug + PartialEq + Eq;
     type Operand: Clone + std::fmt::Debug + PartialEq + Eq;
+    type Label: Clone + std::fmt::Debug + PartialEq + Eq;
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -14,7 +15,10 @@ pub struct Pos(pub usize, pub usize);
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ParserResult<IS: ParserInstSet> {
     pub data: Vec<ParserResultData>,
+    pub sections: Vec<ParserResultSection>,
     pub text: Vec<ParserResultText<IS>>,
+    /// every label defined in the code, by name
+    pub labels: Vec<(String, IS::Label)>,
 }
 
 #[derive(Clone, Debug)]
@@ -25,6 +29,15 @@ pub struct ParserError {
 
 pub type ParserResultData = u8;
 
+/// Data of a section other than `.data` and `.text` (e.g. `.rodata`, `.bss`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParserResultSection {
+    pub name: String,
+    /// line of the directive which first switches to the section
+    pub line: usize,
+    pub data: Vec<ParserResultData>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ParserResultText<IS: ParserInstSet> {
     Text(ParserInst<IS>),
@@ -49,6 +62,13 @@ impl<IS: ParserInstSet> std::fmt::Display for ParserResult<IS> {
             write!(f, "{:02x}", d)?;
         }
         f.write_str("\n")?;
+        for section in &self.sections {
+            write!(f, "{}:\n     0x", section.name)?;
+            for &d in &section.data {
+                write!(f, "{:02x}", d)?;
+            }
+            f.write_str("\n")?;
+        }
         f.write_str("text:\n")?;
         for (i, t) in self.text.iter().enumerate() {
             write!(f, "{:3} {}\n", i + 1, t.to_string())?;
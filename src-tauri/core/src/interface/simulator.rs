@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use crate::{
+    interface::assembler::AssembleResult,
+    modules::riscv::basic::interface::parser::RISCV,
+    types::middleware_types::{
+        AbiWarning,
+        AssemblerConfig,
+        BranchPredictorData,
+        CacheData,
+        MemoryFault,
+        MemoryReturnRange,
+        MemoryView,
+        MemoryViewData,
+        Optional,
+        PipelineData,
+        ProfileReport,
+        RunLimit,
+        SimulatorDelta,
+        TraceFormat,
+        UninitRead,
+        WatchData,
+    },
+};
+
+pub trait Simulator: Send + Sync {
+    fn load_inst(&mut self, inst: AssembleResult<RISCV>) -> Result<(), String>;
+    fn get_raw_inst(&self) -> &Option<AssembleResult<RISCV>>;
+    fn update_config(&mut self, config: &AssemblerConfig) -> Result<(), String>;
+    fn run(&mut self) -> Result<(), String>;
+    fn debug(&mut self) -> Result<(), String>;
+    fn stop(&mut self) -> Result<(), String>;
+    fn resume(&mut self) -> Result<(), String>;
+    fn step(&mut self) -> Result<(), String>;
+    /// Executes one instruction of `hart`, the scheduler continues from it.
+    fn step_hart(&mut self, hart: usize) -> Result<(), String>;
+    fn reset(&mut self) -> Result<(), String>;
+    fn undo(&mut self) -> Result<(), String>;
+    /// `hart` restricts the breakpoint to one hart, `None` means every hart.
+    fn set_breakpoint(&mut self, idx: usize, hart: Option<usize>) -> Result<(), String>;
+    fn remove_breakpoint(&mut self, idx: usize, hart: Option<usize>) -> Result<(), String>;
+    fn syscall_input(&mut self, input: &str) -> Result<(), String>;
+    /// Debugger edits of the current hart or the memory, only while paused
+    /// or stopped. They follow the rules of the stores and undo reverts them,
+    /// the simulator is paused afterwards.
+    fn set_register(&mut self, idx: usize, value: u64) -> Result<(), String>;
+    /// Writes at most 8 bytes.
+    fn set_memory(&mut self, addr: u32, data: &[u8]) -> Result<(), String>;
+    /// `addr` has to be the address of an instruction.
+    fn set_pc(&mut self, addr: u32) -> Result<(), String>;
+    /// Machine state of a paused or stopped simulator as a versioned
+    /// [`Snapshot`](crate::types::middleware_types::Snapshot) in JSON.
+    fn save_snapshot(&self) -> Result<String, String>;
+    /// Restores a snapshot of the loaded program, the simulator is paused
+    /// afterwards and a pending input is requested again.
+    fn restore_snapshot(&mut self, snapshot: &str) -> Result<(), String>;
+    /// Registers of the current hart.
+    fn get_register(&self) -> &[u64];
+    fn get_hart_register(&self, hart: usize) -> &[u64];
+    fn get_memory(&self) -> Vec<u32>;
+    /// Registers and words of the memory return range and the memory views
+    /// written since the last `take_delta` or `resync`.
+    fn take_delta(&self, res: Optional, paused: bool) -> SimulatorDelta;
+    /// Forgets the changes for a full update, returns the sequence number of
+    /// the update.
+    fn resync(&self) -> u64;
+    /// Pc index of the current hart.
+    fn get_pc_idx(&self) -> Option<usize>;
+    fn get_hart_pc_idx(&self, hart: usize) -> Option<usize>;
+    fn get_hart_count(&self) -> usize;
+    /// The hart executing instructions, or the last one which did.
+    fn get_current_hart(&self) -> usize;
+    /// Stage occupancy and statistics, `None` in functional mode.
+    fn get_pipeline(&self) -> Option<PipelineData>;
+    /// Statistics and lines of the L1 caches, `None` if disabled.
+    fn get_icache(&self) -> Option<CacheData>;
+    fn get_dcache(&self) -> Option<CacheData>;
+    /// Prediction statistics per branch site, `None` if disabled.
+    fn get_branch_predictor(&self) -> Option<BranchPredictorData>;
+    /// Calling convention violations since the last reset, `None` if
+    /// disabled.
+    fn get_abi_warnings(&self) -> Option<Vec<AbiWarning>>;
+    /// Reads of uninitialised data since the last reset, `None` if disabled.
+    fn get_uninit_reads(&self) -> Option<Vec<UninitRead>>;
+    /// Execution counts since the last reset, `None` if disabled.
+    fn get_profile(&self) -> Option<ProfileReport>;
+    /// Retired instructions since the last reset, fails if tracing is
+    /// disabled.
+    fn export_trace(&self, format: TraceFormat) -> Result<String, String>;
+    /// The limit of `AssemblerConfig::run_limits` the run stopped at.
+    fn get_exceeded_limit(&self) -> Option<RunLimit>;
+    /// The memory fault the run stopped at.
+    fn get_memory_fault(&self) -> Option<MemoryFault>;
+    /// Animates `run`, `debug` and `resume` at `rate` instructions, or
+    /// pipeline cycles, per second, 0 runs flat out. Takes effect immediately.
+    fn set_run_rate(&self, rate: u64);
+    fn get_filepath(&self) -> &str;
+    fn get_memory_return_range(&self) -> MemoryReturnRange;
+    fn set_memory_return_range(&mut self, range: MemoryReturnRange) -> Result<(), String>;
+    fn get_memory_views(&self) -> Vec<MemoryViewData>;
+    /// Adds `view`, or replaces the view with the same name.
+    fn set_memory_view(&mut self, view: MemoryView) -> Result<(), String>;
+    fn remove_memory_view(&mut self, name: &str) -> Result<(), String>;
+    /// The watch expressions evaluated on the current hart.
+    fn get_watches(&self) -> Vec<WatchData>;
+    /// Replaces the watch expressions, one that fails to parse evaluates to
+    /// its error.
+    fn set_watches(&mut self, expressions: Vec<String>);
+    fn is_paused(&self) -> bool;
+    /// Whether an input ecall waits for `syscall_input`.
+    fn is_waiting_input(&self) -> bool;
+    /// Events are dropped and input fails without a sink.
+    fn set_event_sink(&mut self, sink: Option<Arc<dyn SimulatorEventSink>>);
+}
+
+/// Receives the events of a simulator, implemented by every host.
+pub trait SimulatorEventSink: Send + Sync {
+    /// Called after every change of the simulator state, `paused` is set if
+    /// it can be resumed.
+    fn update(&self, simulator: &dyn Simulator, res: Optional, paused: bool);
+    /// Output of a print ecall of the simulator of `filepath`.
+    fn output(&self, filepath: &str, output: &str) -> Result<(), String>;
+    /// An input ecall waits until the host calls `syscall_input`, which must
+    /// not happen before this returns.
+    fn request_input(&self, filepath: &str) -> Result<(), String>;
+    /// Periodic changes of an animated run.
+    fn delta(&self, _delta: SimulatorDelta) {}
+}
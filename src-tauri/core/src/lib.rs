@@ -0,0 +1,23 @@
+#![cfg_attr(
+    debug_assertions,
+    allow(dead_code),
+    allow(unused_variables),
+    allow(unused_mut),
+    allow(unused_assignments),
+    allow(unreachable_code),
+    allow(unused_macros)
+)]
+
+//! Parser, assembler and simulator without any GUI dependency. Hosts drive a
+//! [`Simulator`] and receive its events through a [`SimulatorEventSink`].
+//!
+//! [`Simulator`]: crate::interface::simulator::Simulator
+//! [`SimulatorEventSink`]: crate::interface::simulator::SimulatorEventSink
+pub mod interface;
+pub mod modules;
+pub mod simulator;
+pub mod types;
+pub mod utility;
+
+#[cfg(test)]
+mod tests;
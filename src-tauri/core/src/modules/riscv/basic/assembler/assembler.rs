@@ -0,0 +1,1503 @@
+use std::borrow::Cow;
+
+use ux::{u12, u20, u5};
+
+use crate::{
+    interface::{
+        assembler::{
+            AssembleResult,
+            AssembleSection,
+            Assembler,
+            AssemblyError,
+            Instruction,
+            InstructionSet,
+            Memory,
+        },
+        parser::{ParserResult, ParserResultSection},
+    },
+    modules::riscv::{
+        basic::{
+            assembler::riscv::{Immediate12, Immediate20, Register},
+            interface::parser::*,
+        },
+        rv32a::assembler::rv32a::RV32A,
+        rv32c::{assembler::rv32c::RV32C, constants::RV32CInstruction},
+        rv32i::assembler::rv32i::RV32I,
+        rv64i::assembler::rv64i::RV64I,
+        rvb::assembler::rvb::RVB,
+    },
+    types::middleware_types::{AssemblerConfig, SectionConfig, Xlen},
+};
+const MAX_RELATIVE_OFFSET: i32 = 0b0111_1111_1111_1111_1111;
+const MIN_RELATIVE_OFFSET: i32 = -0b1000_0000_0000_0000_0000;
+
+macro_rules! modify_label {
+    ($self:ident, $label:ident, $line:expr, $imm:ident, $start:ident) => {
+        match $label {
+            ParserRISCVImmediate::Imm(imm) => {
+                $imm = *imm;
+            }
+            ParserRISCVImmediate::Lbl((label, handler)) => {
+                let address: u32 = $self.label_address(*label);
+                let mut line_addr: u32 = $self.text_addr[$start];
+                match handler {
+                    ParserRISCVLabelHandler::Low => $imm = get_32u_low(address),
+                    ParserRISCVLabelHandler::High => $imm = get_32u_high(address),
+                    ParserRISCVLabelHandler::DeltaHigh => {
+                        $imm = get_32u_high(address) - get_32u_high(line_addr)
+                    }
+                    ParserRISCVLabelHandler::DeltaMinusOneLow => {
+                        line_addr = $self.text_addr[$start - 1];
+                        $imm = get_32u_low(address) - get_32u_low(line_addr)
+                    }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! extract_opds {
+    ($inst:expr, R, $rd:ident, $rs1:ident, $rs2:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Reg(rs1), ParserRISCVInstOpd::Reg(rs2)] = &$inst[..] {
+            $rd = u32::from(*rd);
+            $rs1 = u32::from(*rs1);
+            $rs2 = u32::from(*rs2);
+        }
+    };
+    ($inst:expr, A, $rd:ident, $rs1:ident, $rs2:ident, $aqrl:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Reg(rs1), ParserRISCVInstOpd::Reg(rs2), ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Imm(aqrl))] = &$inst[..] {
+            $rd = u32::from(*rd);
+            $rs1 = u32::from(*rs1);
+            $rs2 = u32::from(*rs2);
+            $aqrl = *aqrl;
+        }
+    };
+    ($inst:expr, CSR, $rd:ident, $csr:ident, $src:ident) => {
+        match &$inst[..] {
+            [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Imm(csr)), ParserRISCVInstOpd::Reg(rs1)] => {
+                $rd = u32::from(*rd);
+                $csr = *csr;
+                $src = u32::from(*rs1);
+            }
+            [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Imm(csr)), ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Imm(uimm))] => {
+                $rd = u32::from(*rd);
+                $csr = *csr;
+                $src = *uimm as u32;
+            }
+            _ => {}
+        }
+    };
+    ($inst:expr, R2, $rd:ident, $rs1:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Reg(rs1)] = &$inst[..] {
+            $rd = u32::from(*rd);
+            $rs1 = u32::from(*rs1);
+        }
+    };
+    ($self:ident, $inst:expr, I, $rd:ident, $rs1:ident, $imm:ident, $start:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Reg(rs1), ParserRISCVInstOpd::Imm(imm)] = &$inst.opd[..] {
+            $rd = u32::from(*rd);
+            $rs1 = u32::from(*rs1);
+            modify_label!($self, imm, $inst.line, $imm, $start);
+        }
+    };
+    ($self:ident, $inst:expr, S, $rs1:ident, $rs2:ident, $imm:ident, $start:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rs1), ParserRISCVInstOpd::Imm(imm), ParserRISCVInstOpd::Reg(rs2)] = &$inst.opd[..] {
+            $rs2 = u32::from(*rs1);
+            $rs1 = u32::from(*rs2);
+            modify_label!($self, imm, $inst.line, $imm, $start);
+        }
+    };
+    ($self:ident, $inst:expr, J, $rd:ident, $imm:ident, $start:ident, $error:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Lbl(label)] = &$inst.opd[..] {
+            $rd = u32::from(*rd);
+            let label_addr = $self.label_address(*label);
+            let current_pc = $self.text_addr[$start];
+            let jump = label_addr.wrapping_sub(current_pc) as i32;
+            if jump > MAX_RELATIVE_OFFSET || jump < MIN_RELATIVE_OFFSET {
+                $error.push(AssemblyError{line: $inst.line, msg: "Jump offset exceeds 20-bit signed integer range!".to_string()})
+            } else {
+                $imm = jump as RISCVImmediate;
+            }
+        }
+    };
+    ($self:ident, $inst:expr, B, $rd:ident, $rs1:ident, $imm:ident, $start:ident, $error:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Reg(rs1), ParserRISCVInstOpd::Lbl(label)] = &$inst.opd[..] {
+            $rd = u32::from(*rd);
+            $rs1 = u32::from(*rs1);
+            let label_addr = $self.label_address(*label);
+            let current_pc = $self.text_addr[$start];
+            let jump = label_addr.wrapping_sub(current_pc) as i32;
+            if jump > MAX_RELATIVE_OFFSET || jump < MIN_RELATIVE_OFFSET {
+                $error.push(AssemblyError{line: $inst.line, msg: "Branch offset exceeds 20-bit signed integer range!".to_string()})
+            } else {
+                $imm = jump as RISCVImmediate;
+            }
+        }
+    };
+    ($self:ident, $inst:expr, U, $rd:ident, $imm:ident, $start:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Imm(imm)] = &$inst.opd[..] {
+            $rd = u32::from(*rd);
+            modify_label!($self, imm, $inst.line, $imm, $start);
+        }
+    };
+    ($inst:expr, R4, $rd:ident, $rs1:ident, $rs2:ident, $rs3:ident) => {
+        if let [ParserRISCVInstOpd::Reg(rd), ParserRISCVInstOpd::Reg(rs1), ParserRISCVInstOpd::Reg(rs2), ParserRISCVInstOpd::Reg(rs3)] = &$inst[..] {
+            $rd = u32::from(*rd);
+            $rs1 = u32::from(*rs1);
+            $rs2 = u32::from(*rs2);
+            $rs3 = u32::from(*rs3);
+        }
+    };
+}
+
+pub struct RiscVAssembler {
+    main: u32,
+    data: u32,
+    section_conf: Vec<SectionConfig>,
+    section_base: Vec<u32>,
+    // segments no section may overlap besides `.data` and the text
+    reserved: Vec<(&'static str, u64, u64)>,
+    compress: bool,
+    // address of every text element, with the end of the text at the end
+    text_addr: Vec<u32>,
+    compressed: Vec<Option<RV32CInstruction>>,
+    xlen: Xlen,
+}
+
+impl RiscVAssembler {
+    pub fn new() -> Self {
+        let mut res = RiscVAssembler {
+            main: 0,
+            data: 0,
+            section_conf: Vec::new(),
+            section_base: Vec::new(),
+            reserved: Vec::new(),
+            compress: false,
+            text_addr: Vec::new(),
+            compressed: Vec::new(),
+            xlen: Xlen::Rv32,
+        };
+        res.update_config(&AssemblerConfig::default());
+        res
+    }
+
+    fn label_address(&self, label: ParserRISCVLabel) -> u32 {
+        match label {
+            ParserRISCVLabel::Text(pos) => self.text_addr[pos],
+            ParserRISCVLabel::Data(_) => u32::from(label) + self.data,
+            ParserRISCVLabel::Section(idx, _) => u32::from(label) + self.section_base[idx],
+            ParserRISCVLabel::Unknown(_) => 0,
+        }
+    }
+
+    /// Decides which text elements are encoded in 16 bits and assigns every
+    /// element its address, must run before any label is resolved.
+    fn layout_text(&mut self, text: &Vec<ParserResultText<RISCV>>) {
+        let compressed: Vec<_> = text
+            .iter()
+            .map(|element| match element {
+                ParserResultText::Text(inst) => self.compressed_form(inst),
+                ParserResultText::Align(_) => None,
+            })
+            .collect();
+        let mut address = self.main;
+        self.text_addr = Vec::with_capacity(text.len() + 1);
+        for cins in &compressed {
+            self.text_addr.push(address);
+            address = address.wrapping_add(if cins.is_some() { 2 } else { 4 });
+        }
+        self.text_addr.push(address);
+        self.compressed = compressed;
+    }
+
+    /// The compressed instruction `inst` is encoded as, base instructions are
+    /// only compressed automatically if enabled and all operands are known.
+    /// RV64C is not supported, so nothing is compressed automatically in RV64.
+    fn compressed_form(&self, inst: &ParserInst<RISCV>) -> Option<RV32CInstruction> {
+        let ins = match inst.op {
+            ParserRISCVInstOp::RV32C(cins) => return Some(cins),
+            ParserRISCVInstOp::RV32I(ins) if self.compress && self.xlen == Xlen::Rv32 => ins,
+            _ => return None,
+        };
+        let reg = |opd: &ParserRISCVInstOpd| match opd {
+            ParserRISCVInstOpd::Reg(reg) => Some(u32::from(*reg)),
+            _ => None,
+        };
+        let imm = |opd: &ParserRISCVInstOpd| match opd {
+            ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Imm(imm)) => Some(*imm),
+            _ => None,
+        };
+        let (rd, rs1, rs2, imm) = match &inst.opd[..] {
+            [] => (0, 0, 0, 0),
+            [a, b] => (reg(a)?, 0, 0, imm(b)?),
+            [a, b, c] => match (reg(b), reg(c)) {
+                (Some(rs1), Some(rs2)) => (reg(a)?, rs1, rs2, 0),
+                (Some(rs1), None) => (reg(a)?, rs1, 0, imm(c)?),
+                // loads and stores: data, offset, base
+                (None, Some(base)) => (reg(a)?, base, reg(a)?, imm(b)?),
+                (None, None) => return None,
+            },
+            _ => return None,
+        };
+        RV32C::compress(ins, rd, rs1, rs2, imm)
+    }
+
+    /// Places every section of `ast` according to `section_conf`, sections
+    /// without a config follow the last placed one. Must run after
+    /// `layout_text`, no section may overlap the text.
+    fn layout_sections(
+        &mut self,
+        sections: &Vec<ParserResultSection>,
+        data_len: usize,
+    ) -> Result<Vec<AssembleSection>, Vec<AssemblyError>> {
+        let align_up = |addr: u64, align: u64| {
+            let align = align.max(1);
+            (addr + align - 1) / align * align
+        };
+        let mut placement = vec![None; sections.len()];
+        let mut next_free = self.data as u64 + data_len as u64;
+        for (idx, section) in sections.iter().enumerate() {
+            if let Some(conf) = self.section_conf.iter().find(|c| c.name == section.name) {
+                let address = align_up(conf.base_address, conf.alignment);
+                next_free = next_free.max(address + section.data.len() as u64);
+                placement[idx] = Some((address, conf.read_only));
+            }
+        }
+        for (idx, section) in sections.iter().enumerate() {
+            if placement[idx].is_none() {
+                let address = align_up(next_free, 4);
+                next_free = address + section.data.len() as u64;
+                placement[idx] = Some((address, section.name.starts_with(".rodata")));
+            }
+        }
+
+        let mut error = Vec::new();
+        let text_len = self.text_addr[self.text_addr.len() - 1].wrapping_sub(self.main);
+        let mut ranges = vec![
+            (
+                "section .data".to_string(),
+                self.data as u64,
+                data_len as u64,
+            ),
+            ("the text".to_string(), self.main as u64, text_len as u64),
+        ];
+        ranges.extend(
+            self.reserved
+                .iter()
+                .map(|&(name, start, len)| (name.to_string(), start, len)),
+        );
+        for (section, &(address, _)) in sections.iter().zip(placement.iter().flatten()) {
+            if address + section.data.len() as u64 > u32::MAX as u64 + 1 {
+                error.push(AssemblyError {
+                    line: section.line,
+                    msg: format!("section {} exceeds the address space", section.name),
+                });
+            }
+            for (name, start, len) in &ranges {
+                let (start, len) = (*start, *len);
+                if len != 0
+                    && !section.data.is_empty()
+                    && address < start + len
+                    && start < address + section.data.len() as u64
+                {
+                    error.push(AssemblyError {
+                        line: section.line,
+                        msg: format!("section {} overlaps {}", section.name, name),
+                    });
+                }
+            }
+            ranges.push((
+                format!("section {}", section.name),
+                address,
+                section.data.len() as u64,
+            ));
+        }
+        if !error.is_empty() {
+            return Err(error);
+        }
+
+        self.section_base = placement.iter().flatten().map(|p| p.0 as u32).collect();
+        Ok(sections
+            .iter()
+            .zip(placement.into_iter().flatten())
+            .map(|(section, (address, read_only))| AssembleSection {
+                name: section.name.clone(),
+                address: address as u32,
+                data: section.data.clone(),
+                read_only,
+            })
+            .collect())
+    }
+}
+
+impl Assembler<RISCV> for RiscVAssembler {
+    fn assemble(
+        &mut self,
+        ast: ParserResult<RISCV>,
+    ) -> Result<AssembleResult<RISCV>, Vec<AssemblyError>> {
+        let mut results: Vec<InstructionSet<RISCV>> = Vec::new();
+        let mut error: Vec<AssemblyError> = Vec::new();
+        self.layout_text(&ast.text);
+        let sections = self.layout_sections(&ast.sections, ast.data.len())?;
+        for (index, element) in ast.text.iter().enumerate() {
+            let element = expand_compressed(element);
+            let element = element.as_ref();
+            let mut result = Instruction::new(ParserRISCVInstOp::from(RV32IInstruction::Add));
+            let mut line = InstructionSet::new(Instruction::new(ParserRISCVInstOp::from(
+                RV32IInstruction::Add,
+            )));
+            match element {
+                ParserResultText::Text(inst) => {
+                    line.line_number = inst.line as u64;
+                    match inst.op {
+                        ParserRISCVInstOp::RV32I(ins) => {
+                            result.operation = ParserRISCVInstOp::from(ins);
+                            let mut rd: u32 = 0;
+                            let mut rs1: u32 = 0;
+                            let mut rs2: u32 = 0;
+                            let mut imm: RISCVImmediate = 0;
+                            match ins {
+                                RV32IInstruction::Add
+                                | RV32IInstruction::And
+                                | RV32IInstruction::Or
+                                | RV32IInstruction::Sltu
+                                | RV32IInstruction::Sll
+                                | RV32IInstruction::Slt
+                                | RV32IInstruction::Sra
+                                | RV32IInstruction::Srl
+                                | RV32IInstruction::Sub
+                                | RV32IInstruction::Xor => {
+                                    extract_opds!(inst.opd, R, rd, rs1, rs2);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        rs2 as RISCVImmediate,
+                                    ]);
+                                }
+                                RV32IInstruction::Addi
+                                | RV32IInstruction::Andi
+                                | RV32IInstruction::Ori
+                                | RV32IInstruction::Slti
+                                | RV32IInstruction::Sltiu
+                                | RV32IInstruction::Xori
+                                | RV32IInstruction::Slli
+                                | RV32IInstruction::Srai
+                                | RV32IInstruction::Srli
+                                | RV32IInstruction::Jalr
+                                | RV32IInstruction::Lb
+                                | RV32IInstruction::Lbu
+                                | RV32IInstruction::Lh
+                                | RV32IInstruction::Lhu
+                                | RV32IInstruction::Ebreak
+                                | RV32IInstruction::FenceI
+                                | RV32IInstruction::Ecall => {
+                                    extract_opds!(self, inst, I, rd, rs1, imm, index);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        imm,
+                                    ]);
+                                }
+                                RV32IInstruction::Csrrc
+                                | RV32IInstruction::Csrrci
+                                | RV32IInstruction::Csrrs
+                                | RV32IInstruction::Csrrsi
+                                | RV32IInstruction::Csrrw
+                                | RV32IInstruction::Csrrwi => {
+                                    // the source is a register or a 5-bit immediate
+                                    extract_opds!(inst.opd, CSR, rd, imm, rs1);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        imm,
+                                        rs1 as RISCVImmediate,
+                                    ]);
+                                }
+                                RV32IInstruction::Sb
+                                | RV32IInstruction::Sh
+                                | RV32IInstruction::Lw
+                                | RV32IInstruction::Sw => {
+                                    extract_opds!(self, inst, S, rs1, rs2, imm, index);
+                                    result.operands = Vec::from([
+                                        rs2 as RISCVImmediate,
+                                        imm,
+                                        rs1 as RISCVImmediate,
+                                    ]);
+                                }
+                                RV32IInstruction::Jal => {
+                                    extract_opds!(self, inst, J, rd, imm, index, error);
+                                    result.operands = Vec::from([rd as RISCVImmediate, imm]);
+                                }
+                                RV32IInstruction::Beq
+                                | RV32IInstruction::Bge
+                                | RV32IInstruction::Bgeu
+                                | RV32IInstruction::Blt
+                                | RV32IInstruction::Bltu
+                                | RV32IInstruction::Bne => {
+                                    extract_opds!(self, inst, B, rs1, rs2, imm, index, error);
+                                    result.operands = Vec::from([
+                                        rs1 as RISCVImmediate,
+                                        rs2 as RISCVImmediate,
+                                        imm,
+                                    ]);
+                                }
+                                RV32IInstruction::Auipc | RV32IInstruction::Lui => {
+                                    extract_opds!(self, inst, U, rd, imm, index);
+                                    result.operands = Vec::from([rd as RISCVImmediate, imm]);
+                                }
+                                RV32IInstruction::Fence => {
+                                    if let [ParserRISCVInstOpd::Imm(imm1), ParserRISCVInstOpd::Imm(imm2)] =
+                                        inst.opd[..]
+                                    {
+                                        let imm1 = RISCVImmediate::from(imm1);
+                                        let imm2 = RISCVImmediate::from(imm2);
+                                        result.operands = Vec::from([imm1, imm2]);
+                                    }
+                                }
+                            }
+                        }
+                        ParserRISCVInstOp::RV32F(fins) => {
+                            result.operation = ParserRISCVInstOp::from(fins);
+                            let mut rd: u32 = 0;
+                            let mut rs1: u32 = 0;
+                            let mut rs2: u32 = 0;
+                            let mut rs3: u32 = 0;
+                            let mut imm: RISCVImmediate = 0;
+                            match fins {
+                                RV32FInstruction::FaddS
+                                | RV32FInstruction::FclassS
+                                | RV32FInstruction::FcvtSW
+                                | RV32FInstruction::FcvtSWu
+                                | RV32FInstruction::FcvtWS
+                                | RV32FInstruction::FcvtWuS
+                                | RV32FInstruction::FdivS
+                                | RV32FInstruction::FeqS
+                                | RV32FInstruction::FleS
+                                | RV32FInstruction::FltS
+                                | RV32FInstruction::FmaxS
+                                | RV32FInstruction::FminS
+                                | RV32FInstruction::FmulS
+                                | RV32FInstruction::FmvSX
+                                | RV32FInstruction::FmvXS
+                                | RV32FInstruction::FsgnjS
+                                | RV32FInstruction::FsgnjnS
+                                | RV32FInstruction::FsgnjxS
+                                | RV32FInstruction::FsqrtS
+                                | RV32FInstruction::FsubS => {
+                                    extract_opds!(inst.opd, R, rd, rs1, rs2);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        rs2 as RISCVImmediate,
+                                    ]);
+                                }
+                                RV32FInstruction::FmaddS
+                                | RV32FInstruction::FmsubS
+                                | RV32FInstruction::FnmaddS
+                                | RV32FInstruction::FnmsubS => {
+                                    extract_opds!(inst.opd, R4, rd, rs1, rs2, rs3);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        rs2 as RISCVImmediate,
+                                        rs3 as RISCVImmediate,
+                                    ]);
+                                }
+                                RV32FInstruction::Flw => {
+                                    extract_opds!(self, inst, I, rd, rs1, imm, index);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        imm,
+                                    ]);
+                                }
+                                RV32FInstruction::Fsw => {
+                                    extract_opds!(self, inst, S, rs1, rs2, imm, index);
+                                    result.operands = Vec::from([
+                                        imm,
+                                        rs1 as RISCVImmediate,
+                                        rs2 as RISCVImmediate,
+                                    ]);
+                                }
+                            }
+                        }
+                        ParserRISCVInstOp::RV64I(ins) => {
+                            result.operation = ParserRISCVInstOp::from(ins);
+                            let mut rd: u32 = 0;
+                            let mut rs1: u32 = 0;
+                            let mut rs2: u32 = 0;
+                            let mut imm: RISCVImmediate = 0;
+                            match ins {
+                                RV64IInstruction::Addw
+                                | RV64IInstruction::Sllw
+                                | RV64IInstruction::Sraw
+                                | RV64IInstruction::Srlw
+                                | RV64IInstruction::Subw => {
+                                    extract_opds!(inst.opd, R, rd, rs1, rs2);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        rs2 as RISCVImmediate,
+                                    ]);
+                                }
+                                RV64IInstruction::Addiw
+                                | RV64IInstruction::Slliw
+                                | RV64IInstruction::Sraiw
+                                | RV64IInstruction::Srliw => {
+                                    extract_opds!(self, inst, I, rd, rs1, imm, index);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        imm,
+                                    ]);
+                                }
+                                RV64IInstruction::Ld
+                                | RV64IInstruction::Lwu
+                                | RV64IInstruction::Sd => {
+                                    extract_opds!(self, inst, S, rs1, rs2, imm, index);
+                                    result.operands = Vec::from([
+                                        rs2 as RISCVImmediate,
+                                        imm,
+                                        rs1 as RISCVImmediate,
+                                    ]);
+                                }
+                            }
+                        }
+                        ParserRISCVInstOp::RVB(ins) => {
+                            result.operation = ParserRISCVInstOp::from(ins);
+                            let mut rd: u32 = 0;
+                            let mut rs1: u32 = 0;
+                            let mut rs2: u32 = 0;
+                            let mut imm: RISCVImmediate = 0;
+                            match ins {
+                                RVBInstruction::AddUw
+                                | RVBInstruction::Andn
+                                | RVBInstruction::Bclr
+                                | RVBInstruction::Bext
+                                | RVBInstruction::Binv
+                                | RVBInstruction::Bset
+                                | RVBInstruction::Max
+                                | RVBInstruction::Maxu
+                                | RVBInstruction::Min
+                                | RVBInstruction::Minu
+                                | RVBInstruction::Orn
+                                | RVBInstruction::Rol
+                                | RVBInstruction::Rolw
+                                | RVBInstruction::Ror
+                                | RVBInstruction::Rorw
+                                | RVBInstruction::Sh1add
+                                | RVBInstruction::Sh1addUw
+                                | RVBInstruction::Sh2add
+                                | RVBInstruction::Sh2addUw
+                                | RVBInstruction::Sh3add
+                                | RVBInstruction::Sh3addUw
+                                | RVBInstruction::Xnor => {
+                                    extract_opds!(inst.opd, R, rd, rs1, rs2);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        rs2 as RISCVImmediate,
+                                    ]);
+                                }
+                                RVBInstruction::Clz
+                                | RVBInstruction::Clzw
+                                | RVBInstruction::Cpop
+                                | RVBInstruction::Cpopw
+                                | RVBInstruction::Ctz
+                                | RVBInstruction::Ctzw
+                                | RVBInstruction::OrcB
+                                | RVBInstruction::Rev8
+                                | RVBInstruction::SextB
+                                | RVBInstruction::SextH
+                                | RVBInstruction::ZextH => {
+                                    extract_opds!(inst.opd, R2, rd, rs1);
+                                    result.operands =
+                                        Vec::from([rd as RISCVImmediate, rs1 as RISCVImmediate]);
+                                }
+                                RVBInstruction::Bclri
+                                | RVBInstruction::Bexti
+                                | RVBInstruction::Binvi
+                                | RVBInstruction::Bseti
+                                | RVBInstruction::Rori
+                                | RVBInstruction::Roriw
+                                | RVBInstruction::SlliUw => {
+                                    extract_opds!(self, inst, I, rd, rs1, imm, index);
+                                    result.operands = Vec::from([
+                                        rd as RISCVImmediate,
+                                        rs1 as RISCVImmediate,
+                                        imm,
+                                    ]);
+                                }
+                            }
+                        }
+                        ParserRISCVInstOp::RV32A(ins) => {
+                            result.operation = ParserRISCVInstOp::from(ins);
+                            let mut rd: u32 = 0;
+                            let mut rs1: u32 = 0;
+                            let mut rs2: u32 = 0;
+                            let mut aqrl: RISCVImmediate = 0;
+                            extract_opds!(inst.opd, A, rd, rs1, rs2, aqrl);
+                            result.operands = Vec::from([
+                                rd as RISCVImmediate,
+                                rs1 as RISCVImmediate,
+                                rs2 as RISCVImmediate,
+                                aqrl,
+                            ]);
+                        }
+                        ParserRISCVInstOp::RV32C(_) => {}
+                    }
+                }
+                ParserResultText::Align(_) => {}
+            }
+            if let Some(cins) = self.compressed[index] {
+                result.operation = ParserRISCVInstOp::from(cins);
+            }
+            match process_code(self, index, element) {
+                Ok(result) => {
+                    line.code = result.code;
+                    line.basic = result.basic;
+                }
+                Err(err) => error.extend(err),
+            };
+            line.address = self.text_addr[index];
+            let old_operation = line.instruction.operation;
+            let new_instruction = std::mem::replace(&mut line.instruction, result);
+            results.push(line);
+        }
+        if error.is_empty() {
+            Ok(AssembleResult {
+                labels: ast
+                    .labels
+                    .iter()
+                    .map(|(name, label)| (name.clone(), self.label_address(*label)))
+                    .collect(),
+                data: ast.data,
+                sections,
+                instruction: results,
+            })
+        } else {
+            Err(error)
+        }
+    }
+
+    fn update_config(&mut self, config: &AssemblerConfig) {
+        self.main = config.dot_text_base_address as u32;
+        self.data = config.dot_data_base_address as u32;
+        self.compress = config.compress_instructions;
+        self.xlen = config.xlen;
+        // `gp` points to the middle of the extern segment
+        let extern_len = config
+            .global_pointer_gp
+            .saturating_sub(config.dot_extern_base_address)
+            * 2;
+        self.reserved = vec![
+            (
+                "the extern segment",
+                config.dot_extern_base_address,
+                extern_len,
+            ),
+            (
+                "the stack",
+                config.stack_limit_address,
+                (config.stack_base_address + 4).saturating_sub(config.stack_limit_address),
+            ),
+        ];
+        // user defined sections take precedence over the default ones
+        self.section_conf = config.sections.clone();
+        self.section_conf.push(SectionConfig {
+            name: ".rodata".to_string(),
+            base_address: config.dot_rodata_base_address,
+            alignment: config.dot_rodata_alignment,
+            read_only: true,
+        });
+        self.section_conf.push(SectionConfig {
+            name: ".bss".to_string(),
+            base_address: config.dot_bss_base_address,
+            alignment: config.dot_bss_alignment,
+            read_only: false,
+        });
+    }
+
+    fn dump(&mut self, ast: ParserResult<RISCV>) -> Result<Memory, Vec<AssemblyError>> {
+        // the dump only holds .data and .text, refuse rather than drop sections
+        let error: Vec<AssemblyError> = ast
+            .sections
+            .iter()
+            .filter(|section| !section.data.is_empty())
+            .map(|section| AssemblyError {
+                line: section.line,
+                msg: format!("section {} can't be dumped", section.name),
+            })
+            .collect();
+        if !error.is_empty() {
+            return Err(error);
+        }
+        self.layout_text(&ast.text);
+        self.layout_sections(&ast.sections, ast.data.len())?;
+        let data = ast.data;
+        let text = ast.text;
+        let mut data_segment = Vec::new();
+        let mut text_segment = Vec::new();
+        let mut error: Vec<AssemblyError> = Vec::new();
+        for chunk in data.chunks(4) {
+            let mut line = String::new();
+            for e in chunk.iter().rev() {
+                line.push_str(&format!("{:08b}", e));
+            }
+            data_segment.push(line);
+        }
+        let remaining_size = 1024 - data_segment.len() % 1024;
+        for _ in 0..remaining_size {
+            data_segment.push(String::from(format!("{:032b}", 0)));
+        }
+        for (index, element) in text.iter().enumerate() {
+            match process_code(self, index, element) {
+                Ok(line) => {
+                    let binary_string = match self.compressed[index] {
+                        Some(_) => format!("{:016b}", line.code),
+                        None => format!("{:032b}", line.code),
+                    };
+                    text_segment.push(binary_string);
+                }
+                Err(err) => error.extend(err),
+            };
+        }
+        let data_content = data_segment.join("\n");
+        let text_content = text_segment.join("\n");
+        if error.is_empty() {
+            Ok(Memory {
+                data: data_content,
+                text: text_content,
+            })
+        } else {
+            Err(error)
+        }
+    }
+}
+
+pub struct ProcessResult {
+    pub code: u32,
+    pub basic: String,
+}
+
+fn process_code(
+    assembler: &RiscVAssembler,
+    index: usize,
+    element: &ParserResultText<RISCV>,
+) -> Result<ProcessResult, Vec<AssemblyError>> {
+    let mut line: u32 = 0;
+    let mut basic = String::new();
+    let mut error: Vec<AssemblyError> = Vec::new();
+    let element = expand_compressed(element);
+    match element.as_ref() {
+        ParserResultText::Text(inst) => match inst.op {
+            ParserRISCVInstOp::RV32I(ins) => {
+                let mut rd: u32 = 0;
+                let mut rs1: u32 = 0;
+                let mut rs2: u32 = 0;
+                let mut imm: RISCVImmediate = 0;
+                let mut pred: RISCVImmediate = 0;
+                let mut succ: RISCVImmediate = 0;
+                match ins {
+                    RV32IInstruction::Add
+                    | RV32IInstruction::And
+                    | RV32IInstruction::Or
+                    | RV32IInstruction::Sltu
+                    | RV32IInstruction::Sll
+                    | RV32IInstruction::Slt
+                    | RV32IInstruction::Sra
+                    | RV32IInstruction::Srl
+                    | RV32IInstruction::Sub
+                    | RV32IInstruction::Xor => {
+                        extract_opds!(inst.opd, R, rd, rs1, rs2);
+                    }
+                    RV32IInstruction::Addi
+                    | RV32IInstruction::Andi
+                    | RV32IInstruction::Ori
+                    | RV32IInstruction::Slti
+                    | RV32IInstruction::Sltiu
+                    | RV32IInstruction::Xori
+                    | RV32IInstruction::Slli
+                    | RV32IInstruction::Srai
+                    | RV32IInstruction::Srli
+                    | RV32IInstruction::Jalr
+                    | RV32IInstruction::Lb
+                    | RV32IInstruction::Lbu
+                    | RV32IInstruction::Lh
+                    | RV32IInstruction::Lhu
+                    | RV32IInstruction::Ebreak
+                    | RV32IInstruction::FenceI
+                    | RV32IInstruction::Ecall => {
+                        extract_opds!(assembler, inst, I, rd, rs1, imm, index);
+                    }
+                    RV32IInstruction::Csrrc
+                    | RV32IInstruction::Csrrci
+                    | RV32IInstruction::Csrrs
+                    | RV32IInstruction::Csrrsi
+                    | RV32IInstruction::Csrrw
+                    | RV32IInstruction::Csrrwi => {
+                        extract_opds!(inst.opd, CSR, rd, imm, rs1);
+                    }
+                    RV32IInstruction::Sb
+                    | RV32IInstruction::Sh
+                    | RV32IInstruction::Lw
+                    | RV32IInstruction::Sw => {
+                        extract_opds!(assembler, inst, S, rs1, rs2, imm, index);
+                    }
+                    RV32IInstruction::Jal => {
+                        extract_opds!(assembler, inst, J, rd, imm, index, error);
+                    }
+                    RV32IInstruction::Beq
+                    | RV32IInstruction::Bge
+                    | RV32IInstruction::Bgeu
+                    | RV32IInstruction::Blt
+                    | RV32IInstruction::Bltu
+                    | RV32IInstruction::Bne => {
+                        extract_opds!(assembler, inst, B, rs1, rs2, imm, index, error);
+                    }
+                    RV32IInstruction::Auipc | RV32IInstruction::Lui => {
+                        extract_opds!(assembler, inst, U, rd, imm, index);
+                    }
+                    RV32IInstruction::Fence => {
+                        if let [ParserRISCVInstOpd::Imm(imm1), ParserRISCVInstOpd::Imm(imm2)] =
+                            inst.opd[..]
+                        {
+                            pred = RISCVImmediate::from(imm1);
+                            succ = RISCVImmediate::from(imm2);
+                            let imm1u: u32 = pred as u32;
+                            let imm2u: u32 = succ as u32;
+                            line = Into::<u32>::into(RV32I::fence(
+                                0x0.into(),
+                                imm1u.into(),
+                                imm2u.into(),
+                                0x0.into(),
+                                0x0.into(),
+                            ));
+                        }
+                    }
+                }
+                let shamt = if imm >= 0 {
+                    Register(u5::try_from((imm & 0x1F) as u8).unwrap())
+                } else {
+                    Register(u5::try_from(((!((-imm) as u8) & 0x1F) + 1) & 0x1F).unwrap())
+                };
+                let imm_u12 = if imm >= 0 {
+                    Immediate12(u12::try_from(imm as u16 & 0xFFF).unwrap())
+                } else {
+                    Immediate12(u12::try_from((!((-imm) as u16) & 0xFFF) + 1).unwrap())
+                };
+                let imm_u20 = if imm >= 0 {
+                    Immediate20(u20::try_from(imm as u32 & 0xFFFFF).unwrap())
+                } else {
+                    Immediate20(u20::try_from((!((-imm) as u32) & 0xFFFFF) + 1).unwrap())
+                };
+                basic = format_instruction(inst.op, rd, rs1, rs2, imm, pred, succ);
+                match ins {
+                    RV32IInstruction::Add => {
+                        line = Into::<u32>::into(RV32I::add(rs2.into(), rs1.into(), rd.into()));
+                    }
+                    RV32IInstruction::Addi => {
+                        line = Into::<u32>::into(RV32I::addi(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::And => {
+                        line = Into::<u32>::into(RV32I::and(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Andi => {
+                        line = Into::<u32>::into(RV32I::andi(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Auipc => {
+                        line = Into::<u32>::into(RV32I::auipc(imm_u20.into(), rd.into()))
+                    }
+                    RV32IInstruction::Beq => {
+                        line = Into::<u32>::into(RV32I::beq(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Bge => {
+                        line = Into::<u32>::into(RV32I::bge(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Bgeu => {
+                        line =
+                            Into::<u32>::into(RV32I::bgeu(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Blt => {
+                        line = Into::<u32>::into(RV32I::blt(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Bltu => {
+                        line =
+                            Into::<u32>::into(RV32I::bltu(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Bne => {
+                        line = Into::<u32>::into(RV32I::bne(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Csrrc => {
+                        line =
+                            Into::<u32>::into(RV32I::csrrc(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Csrrci => {
+                        line =
+                            Into::<u32>::into(RV32I::csrrci(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Csrrs => {
+                        line =
+                            Into::<u32>::into(RV32I::csrrs(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Csrrsi => {
+                        line =
+                            Into::<u32>::into(RV32I::csrrsi(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Csrrw => {
+                        line =
+                            Into::<u32>::into(RV32I::csrrw(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Csrrwi => {
+                        line =
+                            Into::<u32>::into(RV32I::csrrwi(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Ebreak => line = Into::<u32>::into(RV32I::ebreak()),
+                    RV32IInstruction::Ecall => line = Into::<u32>::into(RV32I::ecall()),
+                    RV32IInstruction::Fence => {}
+                    RV32IInstruction::FenceI => line = Into::<u32>::into(RV32I::fencei()),
+                    RV32IInstruction::Jal => {
+                        line = Into::<u32>::into(RV32I::jal(imm_u20.into(), rd.into()))
+                    }
+                    RV32IInstruction::Jalr => {
+                        line = Into::<u32>::into(RV32I::jalr(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Lb => {
+                        line = Into::<u32>::into(RV32I::lb(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Lbu => {
+                        line = Into::<u32>::into(RV32I::lbu(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Lh => {
+                        line = Into::<u32>::into(RV32I::lh(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Lhu => {
+                        line = Into::<u32>::into(RV32I::lhu(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Lui => {
+                        line = Into::<u32>::into(RV32I::lui(imm_u20.into(), rd.into()))
+                    }
+                    RV32IInstruction::Lw => {
+                        line = Into::<u32>::into(RV32I::lw(imm_u12.into(), rs1.into(), rs2.into()))
+                    }
+                    RV32IInstruction::Or => {
+                        line = Into::<u32>::into(RV32I::or(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Ori => {
+                        line = Into::<u32>::into(RV32I::ori(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Sb => {
+                        line = Into::<u32>::into(RV32I::sb(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Sh => {
+                        line = Into::<u32>::into(RV32I::sh(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Sll => {
+                        line = Into::<u32>::into(RV32I::sll(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Slli => {
+                        line = Into::<u32>::into(RV32I::slli(shamt.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Slt => {
+                        line = Into::<u32>::into(RV32I::slt(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Slti => {
+                        line = Into::<u32>::into(RV32I::slti(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Sltiu => {
+                        line =
+                            Into::<u32>::into(RV32I::sltiu(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Sltu => {
+                        line = Into::<u32>::into(RV32I::sltu(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Sra => {
+                        line = Into::<u32>::into(RV32I::sra(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Srai => {
+                        line = Into::<u32>::into(RV32I::srai(shamt.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Srl => {
+                        line = Into::<u32>::into(RV32I::srl(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Srli => {
+                        line = Into::<u32>::into(RV32I::srli(shamt.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Sub => {
+                        line = Into::<u32>::into(RV32I::sub(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Sw => {
+                        line = Into::<u32>::into(RV32I::sw(imm_u12.into(), rs2.into(), rs1.into()))
+                    }
+                    RV32IInstruction::Xor => {
+                        line = Into::<u32>::into(RV32I::xor(rs2.into(), rs1.into(), rd.into()))
+                    }
+                    RV32IInstruction::Xori => {
+                        line = Into::<u32>::into(RV32I::xori(imm_u12.into(), rs1.into(), rd.into()))
+                    }
+                }
+                if assembler.xlen == Xlen::Rv64 {
+                    let shamt = imm as u32 & 0x3f;
+                    match ins {
+                        RV32IInstruction::Slli => {
+                            line = Into::<u32>::into(RV64I::slli(shamt, rs1.into(), rd.into()))
+                        }
+                        RV32IInstruction::Srai => {
+                            line = Into::<u32>::into(RV64I::srai(shamt, rs1.into(), rd.into()))
+                        }
+                        RV32IInstruction::Srli => {
+                            line = Into::<u32>::into(RV64I::srli(shamt, rs1.into(), rd.into()))
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(cins) = assembler.compressed[index] {
+                    // loads keep the loaded register in rs2 like stores do
+                    let rd = if ins == RV32IInstruction::Lw { rs2 } else { rd };
+                    let encoded = match (assembler.xlen, cins) {
+                        // c.jal shares its encoding with c.addiw of RV64C
+                        (Xlen::Rv64, RV32CInstruction::CJal) => {
+                            Err(format!("{}: not available in RV64", cins.name()))
+                        }
+                        _ => RV32C::encode(cins, rd, rs1, rs2, imm),
+                    };
+                    match encoded {
+                        Ok(code) => {
+                            line = code.into();
+                            basic = format_instruction(cins.into(), rd, rs1, rs2, imm, pred, succ);
+                        }
+                        Err(msg) => error.push(AssemblyError {
+                            line: inst.line,
+                            msg,
+                        }),
+                    }
+                }
+            }
+            ParserRISCVInstOp::RV64I(ins) => {
+                let mut rd: u32 = 0;
+                let mut rs1: u32 = 0;
+                let mut rs2: u32 = 0;
+                let mut imm: RISCVImmediate = 0;
+                match ins {
+                    RV64IInstruction::Addw
+                    | RV64IInstruction::Sllw
+                    | RV64IInstruction::Sraw
+                    | RV64IInstruction::Srlw
+                    | RV64IInstruction::Subw => {
+                        extract_opds!(inst.opd, R, rd, rs1, rs2);
+                    }
+                    RV64IInstruction::Addiw
+                    | RV64IInstruction::Slliw
+                    | RV64IInstruction::Sraiw
+                    | RV64IInstruction::Srliw => {
+                        extract_opds!(assembler, inst, I, rd, rs1, imm, index);
+                    }
+                    RV64IInstruction::Ld | RV64IInstruction::Lwu | RV64IInstruction::Sd => {
+                        extract_opds!(assembler, inst, S, rs1, rs2, imm, index);
+                    }
+                }
+                let shamt = Register(u5::try_from((imm & 0x1F) as u8).unwrap());
+                let imm_u12 = Immediate12(u12::try_from(imm as u16 & 0xFFF).unwrap());
+                basic = format_instruction(inst.op, rd, rs1, rs2, imm, 0, 0);
+                line = match ins {
+                    RV64IInstruction::Addiw => RV64I::addiw(imm_u12, rs1.into(), rd.into()),
+                    RV64IInstruction::Addw => RV64I::addw(rs2.into(), rs1.into(), rd.into()),
+                    RV64IInstruction::Ld => RV64I::ld(imm_u12, rs1.into(), rs2.into()),
+                    RV64IInstruction::Lwu => RV64I::lwu(imm_u12, rs1.into(), rs2.into()),
+                    RV64IInstruction::Sd => RV64I::sd(imm_u12, rs2.into(), rs1.into()),
+                    RV64IInstruction::Slliw => RV64I::slliw(shamt, rs1.into(), rd.into()),
+                    RV64IInstruction::Sllw => RV64I::sllw(rs2.into(), rs1.into(), rd.into()),
+                    RV64IInstruction::Sraiw => RV64I::sraiw(shamt, rs1.into(), rd.into()),
+                    RV64IInstruction::Sraw => RV64I::sraw(rs2.into(), rs1.into(), rd.into()),
+                    RV64IInstruction::Srliw => RV64I::srliw(shamt, rs1.into(), rd.into()),
+                    RV64IInstruction::Srlw => RV64I::srlw(rs2.into(), rs1.into(), rd.into()),
+                    RV64IInstruction::Subw => RV64I::subw(rs2.into(), rs1.into(), rd.into()),
+                }
+                .into();
+            }
+            ParserRISCVInstOp::RVB(ins) => {
+                let mut rd: u32 = 0;
+                let mut rs1: u32 = 0;
+                let mut rs2: u32 = 0;
+                let mut imm: RISCVImmediate = 0;
+                match ins {
+                    RVBInstruction::AddUw
+                    | RVBInstruction::Andn
+                    | RVBInstruction::Bclr
+                    | RVBInstruction::Bext
+                    | RVBInstruction::Binv
+                    | RVBInstruction::Bset
+                    | RVBInstruction::Max
+                    | RVBInstruction::Maxu
+                    | RVBInstruction::Min
+                    | RVBInstruction::Minu
+                    | RVBInstruction::Orn
+                    | RVBInstruction::Rol
+                    | RVBInstruction::Rolw
+                    | RVBInstruction::Ror
+                    | RVBInstruction::Rorw
+                    | RVBInstruction::Sh1add
+                    | RVBInstruction::Sh1addUw
+                    | RVBInstruction::Sh2add
+                    | RVBInstruction::Sh2addUw
+                    | RVBInstruction::Sh3add
+                    | RVBInstruction::Sh3addUw
+                    | RVBInstruction::Xnor => {
+                        extract_opds!(inst.opd, R, rd, rs1, rs2);
+                    }
+                    RVBInstruction::Clz
+                    | RVBInstruction::Clzw
+                    | RVBInstruction::Cpop
+                    | RVBInstruction::Cpopw
+                    | RVBInstruction::Ctz
+                    | RVBInstruction::Ctzw
+                    | RVBInstruction::OrcB
+                    | RVBInstruction::Rev8
+                    | RVBInstruction::SextB
+                    | RVBInstruction::SextH
+                    | RVBInstruction::ZextH => {
+                        extract_opds!(inst.opd, R2, rd, rs1);
+                    }
+                    RVBInstruction::Bclri
+                    | RVBInstruction::Bexti
+                    | RVBInstruction::Binvi
+                    | RVBInstruction::Bseti
+                    | RVBInstruction::Rori
+                    | RVBInstruction::Roriw
+                    | RVBInstruction::SlliUw => {
+                        extract_opds!(assembler, inst, I, rd, rs1, imm, index);
+                    }
+                }
+                if assembler.xlen == Xlen::Rv32 {
+                    if ins.rv64_only() {
+                        error.push(AssemblyError {
+                            line: inst.line,
+                            msg: format!("{}: only available in RV64", ins.name()),
+                        });
+                    } else if imm >= 32 {
+                        error.push(AssemblyError {
+                            line: inst.line,
+                            msg: format!("{}: shift amount exceeds 31 in RV32", ins.name()),
+                        });
+                    }
+                }
+                basic = format_instruction(inst.op, rd, rs1, rs2, imm, 0, 0);
+                line = match ins {
+                    RVBInstruction::AddUw => RVB::add_uw(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Andn => RVB::andn(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Bclr => RVB::bclr(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Bclri => RVB::bclri(imm as u32, rs1.into(), rd.into()),
+                    RVBInstruction::Bext => RVB::bext(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Bexti => RVB::bexti(imm as u32, rs1.into(), rd.into()),
+                    RVBInstruction::Binv => RVB::binv(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Binvi => RVB::binvi(imm as u32, rs1.into(), rd.into()),
+                    RVBInstruction::Bset => RVB::bset(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Bseti => RVB::bseti(imm as u32, rs1.into(), rd.into()),
+                    RVBInstruction::Clz => RVB::clz(rs1.into(), rd.into()),
+                    RVBInstruction::Clzw => RVB::clzw(rs1.into(), rd.into()),
+                    RVBInstruction::Cpop => RVB::cpop(rs1.into(), rd.into()),
+                    RVBInstruction::Cpopw => RVB::cpopw(rs1.into(), rd.into()),
+                    RVBInstruction::Ctz => RVB::ctz(rs1.into(), rd.into()),
+                    RVBInstruction::Ctzw => RVB::ctzw(rs1.into(), rd.into()),
+                    RVBInstruction::Max => RVB::max(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Maxu => RVB::maxu(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Min => RVB::min(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Minu => RVB::minu(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::OrcB => RVB::orc_b(rs1.into(), rd.into()),
+                    RVBInstruction::Orn => RVB::orn(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Rev8 => RVB::rev8(assembler.xlen, rs1.into(), rd.into()),
+                    RVBInstruction::Rol => RVB::rol(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Rolw => RVB::rolw(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Ror => RVB::ror(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Rori => RVB::rori(imm as u32, rs1.into(), rd.into()),
+                    RVBInstruction::Roriw => RVB::roriw(imm as u32, rs1.into(), rd.into()),
+                    RVBInstruction::Rorw => RVB::rorw(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::SextB => RVB::sext_b(rs1.into(), rd.into()),
+                    RVBInstruction::SextH => RVB::sext_h(rs1.into(), rd.into()),
+                    RVBInstruction::Sh1add => RVB::sh1add(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Sh1addUw => RVB::sh1add_uw(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Sh2add => RVB::sh2add(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Sh2addUw => RVB::sh2add_uw(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Sh3add => RVB::sh3add(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::Sh3addUw => RVB::sh3add_uw(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::SlliUw => RVB::slli_uw(imm as u32, rs1.into(), rd.into()),
+                    RVBInstruction::Xnor => RVB::xnor(rs2.into(), rs1.into(), rd.into()),
+                    RVBInstruction::ZextH => RVB::zext_h(assembler.xlen, rs1.into(), rd.into()),
+                }
+                .into();
+            }
+            ParserRISCVInstOp::RV32A(ins) => {
+                let mut rd: u32 = 0;
+                let mut rs1: u32 = 0;
+                let mut rs2: u32 = 0;
+                let mut aqrl: RISCVImmediate = 0;
+                extract_opds!(inst.opd, A, rd, rs1, rs2, aqrl);
+                basic = format_instruction(inst.op, rd, rs1, rs2, aqrl, 0, 0);
+                let aqrl = aqrl as u32;
+                line = match ins {
+                    RV32AInstruction::AmoaddW => {
+                        RV32A::amoadd_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::AmoandW => {
+                        RV32A::amoand_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::AmomaxW => {
+                        RV32A::amomax_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::AmomaxuW => {
+                        RV32A::amomaxu_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::AmominW => {
+                        RV32A::amomin_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::AmominuW => {
+                        RV32A::amominu_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::AmoorW => {
+                        RV32A::amoor_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::AmoswapW => {
+                        RV32A::amoswap_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::AmoxorW => {
+                        RV32A::amoxor_w(aqrl, rs2.into(), rs1.into(), rd.into())
+                    }
+                    RV32AInstruction::LrW => RV32A::lr_w(aqrl, rs2.into(), rs1.into(), rd.into()),
+                    RV32AInstruction::ScW => RV32A::sc_w(aqrl, rs2.into(), rs1.into(), rd.into()),
+                }
+                .into();
+            }
+            ParserRISCVInstOp::RV32F(..) | ParserRISCVInstOp::RV32C(..) => {}
+        },
+        ParserResultText::Align(..) => {}
+    }
+    if error.is_empty() {
+        Ok(ProcessResult { code: line, basic })
+    } else {
+        Err(error)
+    }
+}
+
+/// Replaces a compressed instruction by its base instruction, the operands of
+/// both are in the same layout.
+fn expand_compressed(element: &ParserResultText<RISCV>) -> Cow<'_, ParserResultText<RISCV>> {
+    match element {
+        ParserResultText::Text(inst) => match inst.op {
+            ParserRISCVInstOp::RV32C(cins) => Cow::Owned(ParserResultText::Text(ParserInst {
+                line: inst.line,
+                op: cins.base().into(),
+                opd: inst.opd.clone(),
+            })),
+            _ => Cow::Borrowed(element),
+        },
+        ParserResultText::Align(_) => Cow::Borrowed(element),
+    }
+}
+
+pub(crate) fn format_instruction(
+    instruction: ParserRISCVInstOp,
+    rd: u32,
+    rs1: u32,
+    rs2: u32,
+    imm: RISCVImmediate,
+    imm1: RISCVImmediate,
+    imm2: RISCVImmediate,
+) -> String {
+    match instruction {
+        ParserRISCVInstOp::RV32I(ins) => match ins {
+            RV32IInstruction::Add
+            | RV32IInstruction::And
+            | RV32IInstruction::Or
+            | RV32IInstruction::Sltu
+            | RV32IInstruction::Sll
+            | RV32IInstruction::Slt
+            | RV32IInstruction::Sra
+            | RV32IInstruction::Srl
+            | RV32IInstruction::Sub
+            | RV32IInstruction::Xor => format!(
+                "{} x{},x{},x{}",
+                Into::<&'static str>::into(ins),
+                rd,
+                rs1,
+                rs2
+            ),
+            RV32IInstruction::Addi
+            | RV32IInstruction::Andi
+            | RV32IInstruction::Ori
+            | RV32IInstruction::Slti
+            | RV32IInstruction::Sltiu
+            | RV32IInstruction::Xori
+            | RV32IInstruction::Slli
+            | RV32IInstruction::Srai
+            | RV32IInstruction::Srli
+            | RV32IInstruction::Jalr => format!(
+                "{} x{},x{},{}",
+                Into::<&'static str>::into(ins),
+                rd,
+                rs1,
+                imm
+            ),
+            RV32IInstruction::Csrrc | RV32IInstruction::Csrrs | RV32IInstruction::Csrrw => {
+                format!(
+                    "{} x{},{},x{}",
+                    Into::<&'static str>::into(ins),
+                    rd,
+                    imm,
+                    rs1
+                )
+            }
+            RV32IInstruction::Csrrci | RV32IInstruction::Csrrsi | RV32IInstruction::Csrrwi => {
+                format!(
+                    "{} x{},{},{}",
+                    Into::<&'static str>::into(ins),
+                    rd,
+                    imm,
+                    rs1
+                )
+            }
+            RV32IInstruction::Lb
+            | RV32IInstruction::Lbu
+            | RV32IInstruction::Lh
+            | RV32IInstruction::Lhu => format!(
+                "{} x{},{}(x{})",
+                Into::<&'static str>::into(ins),
+                rd,
+                imm,
+                rs1
+            ),
+            RV32IInstruction::FenceI | RV32IInstruction::Ebreak | RV32IInstruction::Ecall => {
+                format!("{}", Into::<&'static str>::into(ins))
+            }
+            RV32IInstruction::Sb
+            | RV32IInstruction::Sh
+            | RV32IInstruction::Lw
+            | RV32IInstruction::Sw => format!(
+                "{} x{},{}(x{})",
+                Into::<&'static str>::into(ins),
+                rs2,
+                imm,
+                rs1
+            ),
+            RV32IInstruction::Jal => format!("{} x{},{}", Into::<&'static str>::into(ins), rd, imm),
+            RV32IInstruction::Beq
+            | RV32IInstruction::Bge
+            | RV32IInstruction::Bgeu
+            | RV32IInstruction::Blt
+            | RV32IInstruction::Bltu
+            | RV32IInstruction::Bne => format!(
+                "{} x{},x{},{}",
+                Into::<&'static str>::into(ins),
+                rs1,
+                rs2,
+                imm
+            ),
+            RV32IInstruction::Auipc | RV32IInstruction::Lui => {
+                format!("{} x{},{}", Into::<&'static str>::into(ins), rd, imm)
+            }
+            RV32IInstruction::Fence => {
+                format!("{} {},{}", Into::<&'static str>::into(ins), imm1, imm2)
+            }
+        },
+        ParserRISCVInstOp::RV32A(ins) => {
+            let name = match imm {
+                0b01 => format!("{}.rl", ins.name()),
+                0b10 => format!("{}.aq", ins.name()),
+                0b11 => format!("{}.aqrl", ins.name()),
+                _ => ins.name(),
+            };
+            match ins {
+                RV32AInstruction::LrW => format!("{} x{},(x{})", name, rd, rs1),
+                _ => format!("{} x{},x{},(x{})", name, rd, rs2, rs1),
+            }
+        }
+        ParserRISCVInstOp::RV32F(..) => String::new(),
+        ParserRISCVInstOp::RV64I(ins) => {
+            let name = Into::<&'static str>::into(ins);
+            match ins {
+                RV64IInstruction::Addw
+                | RV64IInstruction::Sllw
+                | RV64IInstruction::Sraw
+                | RV64IInstruction::Srlw
+                | RV64IInstruction::Subw => format!("{} x{},x{},x{}", name, rd, rs1, rs2),
+                RV64IInstruction::Addiw
+                | RV64IInstruction::Slliw
+                | RV64IInstruction::Sraiw
+                | RV64IInstruction::Srliw => format!("{} x{},x{},{}", name, rd, rs1, imm),
+                RV64IInstruction::Ld | RV64IInstruction::Lwu | RV64IInstruction::Sd => {
+                    format!("{} x{},{}(x{})", name, rs2, imm, rs1)
+                }
+            }
+        }
+        ParserRISCVInstOp::RVB(ins) => match ins {
+            RVBInstruction::AddUw
+            | RVBInstruction::Andn
+            | RVBInstruction::Bclr
+            | RVBInstruction::Bext
+            | RVBInstruction::Binv
+            | RVBInstruction::Bset
+            | RVBInstruction::Max
+            | RVBInstruction::Maxu
+            | RVBInstruction::Min
+            | RVBInstruction::Minu
+            | RVBInstruction::Orn
+            | RVBInstruction::Rol
+            | RVBInstruction::Rolw
+            | RVBInstruction::Ror
+            | RVBInstruction::Rorw
+            | RVBInstruction::Sh1add
+            | RVBInstruction::Sh1addUw
+            | RVBInstruction::Sh2add
+            | RVBInstruction::Sh2addUw
+            | RVBInstruction::Sh3add
+            | RVBInstruction::Sh3addUw
+            | RVBInstruction::Xnor => format!("{} x{},x{},x{}", ins.name(), rd, rs1, rs2),
+            RVBInstruction::Clz
+            | RVBInstruction::Clzw
+            | RVBInstruction::Cpop
+            | RVBInstruction::Cpopw
+            | RVBInstruction::Ctz
+            | RVBInstruction::Ctzw
+            | RVBInstruction::OrcB
+            | RVBInstruction::Rev8
+            | RVBInstruction::SextB
+            | RVBInstruction::SextH
+            | RVBInstruction::ZextH => format!("{} x{},x{}", ins.name(), rd, rs1),
+            RVBInstruction::Bclri
+            | RVBInstruction::Bexti
+            | RVBInstruction::Binvi
+            | RVBInstruction::Bseti
+            | RVBInstruction::Rori
+            | RVBInstruction::Roriw
+            | RVBInstruction::SlliUw => format!("{} x{},x{},{}", ins.name(), rd, rs1, imm),
+        },
+        ParserRISCVInstOp::RV32C(ins) => match ins {
+            RV32CInstruction::CAdd
+            | RV32CInstruction::CAnd
+            | RV32CInstruction::CMv
+            | RV32CInstruction::COr
+            | RV32CInstruction::CSub
+            | RV32CInstruction::CXor => format!("{} x{},x{}", ins.name(), rd, rs2),
+            RV32CInstruction::CAddi
+            | RV32CInstruction::CAddi4spn
+            | RV32CInstruction::CAndi
+            | RV32CInstruction::CLi
+            | RV32CInstruction::CLui
+            | RV32CInstruction::CSlli
+            | RV32CInstruction::CSrai
+            | RV32CInstruction::CSrli => format!("{} x{},{}", ins.name(), rd, imm),
+            RV32CInstruction::CLw | RV32CInstruction::CLwsp => {
+                format!("{} x{},{}(x{})", ins.name(), rd, imm, rs1)
+            }
+            RV32CInstruction::CSw | RV32CInstruction::CSwsp => {
+                format!("{} x{},{}(x{})", ins.name(), rs2, imm, rs1)
+            }
+            RV32CInstruction::CBeqz | RV32CInstruction::CBnez => {
+                format!("{} x{},{}", ins.name(), rs1, imm)
+            }
+            RV32CInstruction::CAddi16sp | RV32CInstruction::CJ | RV32CInstruction::CJal => {
+                format!("{} {}", ins.name(), imm)
+            }
+            RV32CInstruction::CJalr | RV32CInstruction::CJr => format!("{} x{}", ins.name(), rs1),
+            RV32CInstruction::CEbreak | RV32CInstruction::CNop => ins.name(),
+        },
+    }
+}
@@ -202,6 +202,9 @@ pub enum ROpcode {
     Shamt = 0b0010011,  // Slli, Srai, Srli
     ALUReg = 0b0110011, // Add, And, Or, Sll, Slt, Sltu, Sra, Srl, Sub, Xor
     Float = 0b1010011,
+    ShamtW = 0b0011011,  // Slliw, Sraiw, Srliw
+    ALURegW = 0b0111011, // Addw, Sllw, Sraw, Srlw, Subw
+    AMO = 0b0101111,     // Amo*W, LrW, ScW
 }
 
 #[repr(u8)]
@@ -215,8 +218,9 @@ pub enum R4Opcode {
 #[repr(u8)]
 pub enum IOpcode {
     JALR = 0b1100111,        //Jalr
-    Load = 0b0000011,        // Lb, Lbu, Lh, Lhu, Lw
+    Load = 0b0000011,        // Lb, Lbu, Lh, Lhu, Lw, Ld, Lwu
     ALUImm = 0b0010011,      // Addi, Andi, Ori, Slti, Sltiu, Xori
+    ALUImmW = 0b0011011,     // Addiw
     FENCE = 0b0001111,       // Fence, FenceI
     Environment = 0b1110011, // Csrrc, Csrrci, Csrrs, Csrrsi, Csrrw, Csrrwi. Ebreak, Ecall
     Float = 0b0000111,
@@ -224,7 +228,7 @@ pub enum IOpcode {
 
 #[repr(u8)]
 pub enum SOpcode {
-    Store = 0b100011, // Sb, Sh, Sw
+    Store = 0b100011, // Sb, Sh, Sw, Sd
     Float = 0b0100111,
 }
 
@@ -0,0 +1,35 @@
+use crate::modules::riscv::basic::interface::parser::{
+    ParserRISCVCsr,
+    ParserRISCVImmediate,
+    ParserRISCVLabel,
+    RISCVImmediate,
+};
+
+impl From<ParserRISCVImmediate> for RISCVImmediate {
+    fn from(imm: ParserRISCVImmediate) -> Self {
+        match imm {
+            ParserRISCVImmediate::Imm(imm) => imm,
+            ParserRISCVImmediate::Lbl((label, handler)) => u32::from(label) as RISCVImmediate,
+        }
+    }
+}
+
+impl From<ParserRISCVLabel> for u32 {
+    fn from(label: ParserRISCVLabel) -> Self {
+        match label {
+            ParserRISCVLabel::Text(pos) => pos as u32 * 4,
+            ParserRISCVLabel::Data(pos) => pos as u32,
+            ParserRISCVLabel::Section(_, pos) => pos as u32,
+            ParserRISCVLabel::Unknown(_) => 0,
+        }
+    }
+}
+
+impl From<ParserRISCVCsr> for u32 {
+    fn from(csr: ParserRISCVCsr) -> Self {
+        match csr {
+            ParserRISCVCsr::RV32I(csr) => csr.number(),
+            ParserRISCVCsr::RV32F(csr) => match csr {},
+        }
+    }
+}
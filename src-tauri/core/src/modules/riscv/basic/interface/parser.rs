@@ -3,10 +3,18 @@ use std::io::Write;
 
 use super::super::parser::parser::RISCVSymbolList;
 pub use super::super::{
-    super::{rv32f::constants::*, rv32i::constants::*},
+    super::{
+        rv32a::constants::*,
+        rv32c::constants::*,
+        rv32f::constants::*,
+        rv32i::constants::*,
+        rv64i::constants::*,
+        rvb::constants::*,
+    },
     parser::parser::RISCVParser,
 };
 pub use crate::interface::parser::*;
+use crate::types::middleware_types::Xlen;
 
 pub const MAX_DATA_SIZE: usize = 0xf_ffff;
 
@@ -15,12 +23,20 @@ pub struct RISCV;
 
 pub enum RISCVExtension {
     RV32I,
+    RV32A,
+    RV32C,
+    RV64I,
+    RVB,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ParserRISCVInstOp {
     RV32I(RV32IInstruction),
+    RV32A(RV32AInstruction),
     RV32F(RV32FInstruction),
+    RV32C(RV32CInstruction),
+    RV64I(RV64IInstruction),
+    RVB(RVBInstruction),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -50,9 +66,10 @@ pub enum ParserRISCVInstOpd {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ParserRISCVLabel {
-    Text(usize),  // ParserResult<RISCV>::text[usize]
-    Data(usize),  // ParserResult<RISCV>::data[usize]
-    Unknown(Pos), // the label position in the code (mustn't exist in the output)
+    Text(usize),           // ParserResult<RISCV>::text[usize]
+    Data(usize),           // ParserResult<RISCV>::data[usize]
+    Section(usize, usize), // ParserResult<RISCV>::sections[usize].data[usize]
+    Unknown(Pos),          // the label position in the code (mustn't exist in the output)
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -66,6 +83,7 @@ pub enum ParserRISCVLabelHandler {
 impl ParserInstSet for RISCV {
     type Operator = ParserRISCVInstOp;
     type Operand = ParserRISCVInstOpd;
+    type Label = ParserRISCVLabel;
 }
 
 // ------------------------- Implementations -------------------------
@@ -74,6 +92,30 @@ impl RISCVExtension {
     pub fn get_symbol_parser(&self) -> &RISCVSymbolList {
         match self {
             RISCVExtension::RV32I => &super::super::super::rv32i::parser::parser::RV32I_SYMBOL_LIST,
+            RISCVExtension::RV32A => &super::super::super::rv32a::parser::parser::RV32A_SYMBOL_LIST,
+            RISCVExtension::RV32C => &super::super::super::rv32c::parser::parser::RV32C_SYMBOL_LIST,
+            RISCVExtension::RV64I => &super::super::super::rv64i::parser::parser::RV64I_SYMBOL_LIST,
+            RISCVExtension::RVB => &super::super::super::rvb::parser::parser::RVB_SYMBOL_LIST,
+        }
+    }
+
+    /// Extensions a tab is parsed with for the register width `xlen`, later
+    /// extensions override the operators of earlier ones.
+    pub fn for_xlen(xlen: Xlen) -> Vec<RISCVExtension> {
+        match xlen {
+            Xlen::Rv32 => vec![
+                RISCVExtension::RV32I,
+                RISCVExtension::RV32A,
+                RISCVExtension::RV32C,
+                RISCVExtension::RVB,
+            ],
+            Xlen::Rv64 => vec![
+                RISCVExtension::RV32I,
+                RISCVExtension::RV32A,
+                RISCVExtension::RV32C,
+                RISCVExtension::RV64I,
+                RISCVExtension::RVB,
+            ],
         }
     }
 
@@ -81,6 +123,10 @@ impl RISCVExtension {
     pub fn export(&self, folder: &str) -> std::io::Result<()> {
         match self {
             RISCVExtension::RV32I => super::super::super::rv32i::parser::parser::export(folder),
+            RISCVExtension::RV32A => super::super::super::rv32a::parser::parser::export(folder),
+            RISCVExtension::RV32C => super::super::super::rv32c::parser::parser::export(folder),
+            RISCVExtension::RV64I => super::super::super::rv64i::parser::parser::export(folder),
+            RISCVExtension::RVB => super::super::super::rvb::parser::parser::export(folder),
         }
     }
 }
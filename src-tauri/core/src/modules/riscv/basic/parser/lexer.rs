@@ -119,6 +119,8 @@ pub enum RISCVToken<'a> {
     Ascii,
     #[token(".asciz", priority = 10)]
     Asciz,
+    #[token(".bss", priority = 10)]
+    Bss,
     #[token(".byte", priority = 10)]
     Byte,
     #[token(".data", priority = 10)]
@@ -145,6 +147,8 @@ pub enum RISCVToken<'a> {
     MacroDef,
     #[token(".macro", priority = 10)]
     Macro,
+    #[token(".rodata", priority = 10)]
+    Rodata,
     #[token(".section", priority = 10)]
     Section,
     #[token(".space", priority = 10)]
@@ -155,7 +159,7 @@ pub enum RISCVToken<'a> {
     Text,
     #[token(".word", priority = 10)]
     Word,
-    #[regex(r"\.[a-zA-Z_][a-zA-Z0-9_]*")]
+    #[regex(r"\.[a-zA-Z_][a-zA-Z0-9._]*")]
     UnknownDirective(&'a str),
 }
 
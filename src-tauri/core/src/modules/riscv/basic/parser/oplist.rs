@@ -13,10 +13,12 @@ use super::super::interface::parser::{
 pub enum RISCVExpectImm {
     U4,
     U5,
+    U6,
     U12,
     U20,
     I12,
     I32,
+    I64,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -95,6 +97,33 @@ pub fn idx_handler_imm_high(opd: ParserRISCVInstOpd) -> ParserRISCVInstOpd {
         opd
     }
 }
+// i = ((((hi20 << 12) + lo12) << 12 + c2) << 12 + c1) << 12 + c0, where
+// hi20 and lo12 build a 32-bit value with lui and addiw, c2..c0 are i12.
+// returns [hi20, lo12, c2, c1, c0]
+fn split_imm64(i: RISCVImmediate) -> [RISCVImmediate; 5] {
+    let mut rest = i as i128;
+    let mut chunk = [0; 3];
+    for c in chunk.iter_mut().rev() {
+        *c = (rest << 116 >> 116) as RISCVImmediate;
+        rest = (rest - *c as i128) >> 12;
+    }
+    let upper = rest as u32;
+    [
+        get_32u_high(upper),
+        get_32u_low(upper),
+        chunk[0],
+        chunk[1],
+        chunk[2],
+    ]
+}
+// used to load a 64-bit imm, get the N-th part of split_imm64
+pub fn idx_handler_imm64_part<const N: usize>(opd: ParserRISCVInstOpd) -> ParserRISCVInstOpd {
+    if let ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Imm(i)) = opd {
+        ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Imm(split_imm64(i)[N]))
+    } else {
+        opd
+    }
+}
 pub fn idx_handler_lbl_low(opd: ParserRISCVInstOpd) -> ParserRISCVInstOpd {
     if let ParserRISCVInstOpd::Lbl(lbl) = opd {
         ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Lbl((
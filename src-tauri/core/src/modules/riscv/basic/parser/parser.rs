@@ -9,12 +9,13 @@ use super::{
     oplist::{RISCVExpectImm, RISCVExpectToken, RISCVOpdSetAim, RISCVOpdSetAimOpd},
     r#macro::MacroData,
 };
-use crate::{dprintln, utility::ptr::Ptr};
+use crate::{dprintln, types::middleware_types::Endianness, utility::ptr::Ptr};
 
 pub struct RISCVParser {
     symbol_list: HashMap<&'static str, Symbol<'static>>,
     macro_list: BTreeMap<String, MacroData>,
     label_list: BTreeMap<String, LabelData>,
+    data_endianness: Endianness,
 }
 
 impl Parser<RISCV> for RISCVParser {
@@ -22,6 +23,7 @@ impl Parser<RISCV> for RISCVParser {
         self.init();
         dprintln!("parse code:\n{}", code_str);
         let mut _status = RISCVParserStatus::new(code_str);
+        _status.data_endianness = self.data_endianness;
         let status_ptr = Ptr::new(&_status);
         let status = status_ptr.as_mut();
 
@@ -29,6 +31,11 @@ impl Parser<RISCV> for RISCVParser {
             self.parse_token(status_ptr, token)?;
         }
         self.dispose_label_list()?;
+        status.result.labels = self
+            .label_list
+            .values()
+            .filter_map(|label| Some((label.name.clone(), label.def?)))
+            .collect();
         Self::load_data_to_result(status);
         Ok(_status.result)
     }
@@ -56,18 +63,31 @@ pub(super) enum RISCVDataType {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub(super) enum RISCVSegment {
     Data(RISCVDataType),
+    Rodata(RISCVDataType),
+    Bss,
+    Section(RISCVDataType),
     #[default]
     Text,
 }
 
+// sections[0] is always `.data`, the others are stored in
+// ParserResult<RISCV>::sections in the order they first appear
+pub(super) struct RISCVDataSection {
+    name: String,
+    line: usize,
+    size: usize,
+    buf: Vec<Vec<ParserResultData>>,
+}
+
 pub(super) struct RISCVParserStatus<'a> {
     segment: RISCVSegment,
     iter: LexerIter<'a>,
     #[allow(unused)]
     macro_def: Option<MacroData>,
     label_def: Option<String>,
-    data_seg_size: usize,
-    data_buf: Vec<Vec<ParserResultData>>,
+    section_idx: usize,
+    sections: Vec<RISCVDataSection>,
+    data_endianness: Endianness,
     result: ParserResult<RISCV>,
 }
 
@@ -80,14 +100,61 @@ impl RISCVParserStatus<'_> {
             },
             macro_def: None,
             label_def: None,
-            data_seg_size: 0,
-            data_buf: Vec::new(),
+            section_idx: 0,
+            sections: vec![RISCVDataSection::new(".data", 0)],
+            data_endianness: Endianness::Little,
             result: ParserResult {
                 data: Vec::new(),
+                sections: Vec::new(),
                 text: Vec::new(),
+                labels: Vec::new(),
             },
         }
     }
+
+    fn cur_section(&mut self) -> &mut RISCVDataSection {
+        &mut self.sections[self.section_idx]
+    }
+
+    fn cur_label(&self) -> ParserRISCVLabel {
+        let size = self.sections[self.section_idx].size;
+        if self.section_idx == 0 {
+            ParserRISCVLabel::Data(size)
+        } else {
+            ParserRISCVLabel::Section(self.section_idx - 1, size)
+        }
+    }
+
+    fn switch_section(&mut self, name: &str) {
+        self.section_idx = match self.sections.iter().position(|sec| sec.name == name) {
+            Some(idx) => idx,
+            None => {
+                let line = self.iter.line();
+                self.sections.push(RISCVDataSection::new(name, line));
+                self.sections.len() - 1
+            }
+        };
+        self.segment = if name == ".data" {
+            RISCVSegment::Data(RISCVDataType::default())
+        } else if name == ".rodata" || name.starts_with(".rodata.") {
+            RISCVSegment::Rodata(RISCVDataType::default())
+        } else if name == ".bss" || name.starts_with(".bss.") {
+            RISCVSegment::Bss
+        } else {
+            RISCVSegment::Section(RISCVDataType::default())
+        };
+    }
+}
+
+impl RISCVDataSection {
+    fn new(name: &str, line: usize) -> Self {
+        RISCVDataSection {
+            name: name.to_string(),
+            line,
+            size: 0,
+            buf: Vec::new(),
+        }
+    }
 }
 
 use RISCVExpectImm::*;
@@ -95,19 +162,20 @@ use RISCVExpectToken::*;
 
 macro_rules! load_data_helper {
     ($label_list:expr, $status:expr, $vec:expr) => {
-        if MAX_DATA_SIZE - $status.data_seg_size < $vec.len() {
+        if MAX_DATA_SIZE - $status.cur_section().size < $vec.len() {
             Err($status
                 .iter
                 .get_error("data segment size exceed max limit 0xfffff".to_string()))
         } else {
-            let label_pos = ParserRISCVLabel::Data($status.data_seg_size);
-            $status.data_seg_size += $vec.len();
-            match $status.data_buf.last_mut() {
+            let label_pos = $status.cur_label();
+            let section = $status.cur_section();
+            section.size += $vec.len();
+            match section.buf.last_mut() {
                 Some(chunk) if chunk.len() < DATA_CHUNK_RECOMMEND_SIZE => {
                     chunk.extend($vec);
                 }
                 _ => {
-                    $status.data_buf.push(Vec::from($vec));
+                    section.buf.push(Vec::from($vec));
                 }
             }
             if let Some(label_name) = &$status.label_def {
@@ -123,7 +191,10 @@ macro_rules! load_data_helper_int {
     ($label_list:expr, $status:expr, $data:expr, $ti:ty, $tu:ty) => {
         if let RISCVToken::ImmediateInt(val) = $data {
             if *val >= <$ti>::MIN as i128 && *val <= <$tu>::MAX as i128 {
-                let data = (*val as $tu).to_le_bytes();
+                let data = match $status.data_endianness {
+                    Endianness::Little => (*val as $tu).to_le_bytes(),
+                    Endianness::Big => (*val as $tu).to_be_bytes(),
+                };
                 load_data_helper!($label_list, $status, data)
             } else {
                 Err($status.iter.get_error("data out of range".to_string()))
@@ -137,7 +208,10 @@ macro_rules! load_data_helper_int {
 macro_rules! load_data_helper_float {
     ($label_list:expr, $status:expr, $data:expr, $t:ty) => {
         if let RISCVToken::ImmediateFloat(val) = $data {
-            let data = (*val as $t).to_le_bytes();
+            let data = match $status.data_endianness {
+                Endianness::Little => (*val as $t).to_le_bytes(),
+                Endianness::Big => (*val as $t).to_be_bytes(),
+            };
             load_data_helper!($label_list, $status, data)
         } else {
             Err($status.iter.get_error("requires float".to_string()))
@@ -165,6 +239,7 @@ impl RISCVParser {
             symbol_list: HashMap::new(),
             macro_list: BTreeMap::new(),
             label_list: BTreeMap::new(),
+            data_endianness: Endianness::Little,
         };
         for ext in ext {
             res.import_extension(ext.get_symbol_parser());
@@ -172,6 +247,12 @@ impl RISCVParser {
         res
     }
 
+    /// Byte order `.half`, `.word`, `.dword`, `.float` and `.double` data are
+    /// encoded in, little-endian by default.
+    pub fn set_data_endianness(&mut self, endianness: Endianness) {
+        self.data_endianness = endianness;
+    }
+
     pub fn import_extension(&mut self, symbol_list: &RISCVSymbolList) {
         for &set in symbol_list {
             for (key, value) in set {
@@ -251,12 +332,21 @@ impl RISCVParser {
         status: &mut RISCVParserStatus,
         data_type: RISCVDataType,
     ) -> Result<(), Vec<ParserError>> {
-        if status.segment == RISCVSegment::Text {
-            return Err(status
-                .iter
-                .get_error("invalid directive in text segment".to_string()));
-        }
-        status.segment = RISCVSegment::Data(data_type);
+        status.segment = match status.segment {
+            RISCVSegment::Data(_) => RISCVSegment::Data(data_type),
+            RISCVSegment::Rodata(_) => RISCVSegment::Rodata(data_type),
+            RISCVSegment::Section(_) => RISCVSegment::Section(data_type),
+            RISCVSegment::Bss => {
+                return Err(status
+                    .iter
+                    .get_error("only .space and .align are allowed in .bss".to_string()))
+            }
+            RISCVSegment::Text => {
+                return Err(status
+                    .iter
+                    .get_error("invalid directive in text segment".to_string()))
+            }
+        };
         Ok(())
     }
 
@@ -266,7 +356,9 @@ impl RISCVParser {
         data: &RISCVToken,
     ) -> Result<(), Vec<ParserError>> {
         match status.segment {
-            RISCVSegment::Data(data_type) => match data_type {
+            RISCVSegment::Data(data_type)
+            | RISCVSegment::Rodata(data_type)
+            | RISCVSegment::Section(data_type) => match data_type {
                 RISCVDataType::Byte => load_data_helper_int!(label_list, status, data, i8, u8),
                 RISCVDataType::Half => load_data_helper_int!(label_list, status, data, i16, u16),
                 RISCVDataType::Word => load_data_helper_int!(label_list, status, data, i32, u32),
@@ -276,14 +368,25 @@ impl RISCVParser {
                 RISCVDataType::Ascii => load_data_helper_string!(label_list, status, data, false),
                 RISCVDataType::Asciz => load_data_helper_string!(label_list, status, data, true),
             },
-            _ => Err(status
+            RISCVSegment::Bss => Err(status
+                .iter
+                .get_error("only .space and .align are allowed in .bss".to_string())),
+            RISCVSegment::Text => Err(status
                 .iter
                 .get_error("requires in data segment".to_string())),
         }
     }
 
     fn load_data_to_result(status: &mut RISCVParserStatus) {
-        status.result.data = status.data_buf.concat();
+        let mut sections = status.sections.iter();
+        status.result.data = sections.next().unwrap().buf.concat();
+        status.result.sections = sections
+            .map(|sec| ParserResultSection {
+                name: sec.name.clone(),
+                line: sec.line,
+                data: sec.buf.concat(),
+            })
+            .collect();
     }
 
     fn parse_op(
@@ -343,10 +446,19 @@ impl RISCVParser {
                     Imm(imm_t) => match imm_t {
                         U4 => type_fit = Self::in_bound_int(&token, 0, 0xf),
                         U5 => type_fit = Self::in_bound_int(&token, 0, 0x1f),
+                        U6 => type_fit = Self::in_bound_int(&token, 0, 0x3f),
                         U12 => type_fit = Self::in_bound_int(&token, 0, 0xfff),
                         U20 => type_fit = Self::in_bound_int(&token, 0, 0xf_ffff),
                         I12 => type_fit = Self::in_bound_int(&token, -0x800, 0x7ff),
                         I32 => type_fit = Self::in_bound_int(&token, -0x8000_0000, 0x7fff_ffff),
+                        // unsigned 64-bit values are accepted as their two's complement
+                        I64 => {
+                            type_fit = Self::in_bound_int(
+                                &token,
+                                -0x8000_0000_0000_0000,
+                                0xffff_ffff_ffff_ffff,
+                            )
+                        }
                     },
                     Lbl => type_fit = matches!(token, RISCVToken::Symbol(Symbol::Label(_))),
                 }
@@ -380,9 +492,11 @@ impl RISCVParser {
                     ))));
                     stash_label_name.push(lbl.to_string());
                 }
-                RISCVToken::Symbol(Symbol::Csr(_)) => {
-                    // TODO: csr
-                    unimplemented!("csr not implemented")
+                RISCVToken::Symbol(Symbol::Csr(csr)) => {
+                    stash_opd.push(Some(ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Imm(
+                        u32::from(csr) as RISCVImmediate,
+                    ))));
+                    stash_label_name.push(String::new());
                 }
                 _ => {
                     stash_opd.push(None);
@@ -509,10 +623,11 @@ impl RISCVParser {
                                 status.result.text.push(ParserResultText::Align(val as u8));
                             } else {
                                 let byte = 1 << val as usize;
+                                let size = status.cur_section().size;
                                 load_data_helper!(
                                     self.label_list,
                                     status,
-                                    vec![0; byte - status.data_seg_size % byte]
+                                    vec![0; byte - size % byte]
                                 )?;
                             }
                             Ok(())
@@ -530,9 +645,13 @@ impl RISCVParser {
             }
             RISCVToken::Ascii => Self::set_data_seg(status, RISCVDataType::Ascii),
             RISCVToken::Asciz => Self::set_data_seg(status, RISCVDataType::Asciz),
+            RISCVToken::Bss => {
+                status.switch_section(".bss");
+                Ok(())
+            }
             RISCVToken::Byte => Self::set_data_seg(status, RISCVDataType::Byte),
             RISCVToken::Data => {
-                status.segment = RISCVSegment::Data(RISCVDataType::default());
+                status.switch_section(".data");
                 Ok(())
             }
             RISCVToken::Double => Self::set_data_seg(status, RISCVDataType::Double),
@@ -554,13 +673,33 @@ impl RISCVParser {
                 .get_error("not implemented directive".to_string())),
             RISCVToken::MacroDef => Ok(()),
             RISCVToken::Macro => Err(status.iter.get_error("missing macro name".to_string())),
+            RISCVToken::Rodata => {
+                status.switch_section(".rodata");
+                Ok(())
+            }
             RISCVToken::Section => {
                 let next_token = status.iter.next(&self.symbol_list)?;
-                match next_token {
-                    Some(RISCVToken::Symbol(Symbol::Label(_)))
-                    | Some(RISCVToken::ImmediateString(_)) => Ok(()),
-                    Some(_) => Err(status.iter.get_error("invalid section name".to_string())),
-                    None => Err(status.iter.get_error("missing section name".to_string())),
+                let name = match next_token {
+                    Some(RISCVToken::Symbol(Symbol::Label(name)))
+                    | Some(RISCVToken::UnknownDirective(name)) => name.to_string(),
+                    Some(RISCVToken::ImmediateString(name)) => name[1..name.len() - 1].to_string(),
+                    Some(RISCVToken::Data) => ".data".to_string(),
+                    Some(RISCVToken::Rodata) => ".rodata".to_string(),
+                    Some(RISCVToken::Bss) => ".bss".to_string(),
+                    Some(RISCVToken::Text) => ".text".to_string(),
+                    Some(_) => {
+                        return Err(status.iter.get_error("invalid section name".to_string()))
+                    }
+                    None => return Err(status.iter.get_error("missing section name".to_string())),
+                };
+                if name.is_empty() {
+                    Err(status.iter.get_error("invalid section name".to_string()))
+                } else if name == ".text" || name.starts_with(".text.") {
+                    status.segment = RISCVSegment::Text;
+                    Ok(())
+                } else {
+                    status.switch_section(&name);
+                    Ok(())
                 }
             }
             RISCVToken::Space => {
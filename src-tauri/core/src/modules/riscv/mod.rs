@@ -0,0 +1,7 @@
+pub mod basic;
+pub mod rv32a;
+pub mod rv32c;
+pub mod rv32f;
+pub mod rv32i;
+pub mod rv64i;
+pub mod rvb;
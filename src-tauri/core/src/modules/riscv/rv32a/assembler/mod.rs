@@ -0,0 +1 @@
+pub mod rv32a;
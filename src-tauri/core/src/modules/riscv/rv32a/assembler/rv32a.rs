@@ -0,0 +1,47 @@
+use crate::modules::riscv::basic::assembler::{
+    basic::{Opcode, PackedInstruction, ROpcode},
+    riscv::*,
+};
+
+/// The ordering bits `aqrl` take the lowest two bits of funct7.
+macro_rules! amoimpl {
+    ($func_name:ident, $funct5:literal) => {
+        pub fn $func_name(
+            aqrl: u32,
+            rs2: Register,
+            rs1: Register,
+            rd: Register,
+        ) -> PackedInstruction {
+            ROpcode::AMO
+                .builder()
+                .funct7(
+                    ((($funct5 as u32) << 2) | (aqrl & 0b11))
+                        .try_into()
+                        .unwrap(),
+                )
+                .rs2(rs2.into())
+                .rs1(rs1.into())
+                .funct3((0b010 as u32).try_into().unwrap())
+                .rd(rd.into())
+                .build()
+                .unwrap()
+                .into()
+        }
+    };
+}
+
+pub struct RV32A {}
+
+impl RV32A {
+    amoimpl!(amoadd_w, 0b00000);
+    amoimpl!(amoand_w, 0b01100);
+    amoimpl!(amomax_w, 0b10100);
+    amoimpl!(amomaxu_w, 0b11100);
+    amoimpl!(amomin_w, 0b10000);
+    amoimpl!(amominu_w, 0b11000);
+    amoimpl!(amoor_w, 0b01000);
+    amoimpl!(amoswap_w, 0b00001);
+    amoimpl!(amoxor_w, 0b00100);
+    amoimpl!(lr_w, 0b00010);
+    amoimpl!(sc_w, 0b00011);
+}
@@ -0,0 +1,28 @@
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+/// Instructions of the atomic extension (RV32A).
+///
+/// The operands are `[rd, rs1, rs2, aqrl]`, `rs1` holds the address and
+/// `aqrl` the acquire (`0b10`) and release (`0b01`) bits. `lr.w` has no
+/// `rs2`, it is always `x0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RV32AInstruction {
+    AmoaddW,
+    AmoandW,
+    AmomaxW,
+    AmomaxuW,
+    AmominW,
+    AmominuW,
+    AmoorW,
+    AmoswapW,
+    AmoxorW,
+    LrW,
+    ScW,
+}
+
+impl RV32AInstruction {
+    pub fn name(&self) -> String {
+        Into::<&'static str>::into(self).replace("_", ".")
+    }
+}
@@ -0,0 +1,69 @@
+use strum_macros::{IntoStaticStr, VariantArray};
+
+use super::{super::super::basic::parser::lexer::RISCVOpToken, oplist::OP_LIST};
+
+/// RV32A operators, the `.aq`, `.rl` and `.aqrl` suffixes set the ordering
+/// bits of the instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RV32AOpToken {
+    AmoaddW,
+    AmoaddWAq,
+    AmoaddWRl,
+    AmoaddWAqrl,
+    AmoandW,
+    AmoandWAq,
+    AmoandWRl,
+    AmoandWAqrl,
+    AmomaxW,
+    AmomaxWAq,
+    AmomaxWRl,
+    AmomaxWAqrl,
+    AmomaxuW,
+    AmomaxuWAq,
+    AmomaxuWRl,
+    AmomaxuWAqrl,
+    AmominW,
+    AmominWAq,
+    AmominWRl,
+    AmominWAqrl,
+    AmominuW,
+    AmominuWAq,
+    AmominuWRl,
+    AmominuWAqrl,
+    AmoorW,
+    AmoorWAq,
+    AmoorWRl,
+    AmoorWAqrl,
+    AmoswapW,
+    AmoswapWAq,
+    AmoswapWRl,
+    AmoswapWAqrl,
+    AmoxorW,
+    AmoxorWAq,
+    AmoxorWRl,
+    AmoxorWAqrl,
+    LrW,
+    LrWAq,
+    LrWRl,
+    LrWAqrl,
+    ScW,
+    ScWAq,
+    ScWRl,
+    ScWAqrl,
+}
+
+impl RV32AOpToken {
+    pub fn name(&self) -> String {
+        Into::<&'static str>::into(self).replace("_", ".")
+    }
+}
+
+impl From<RV32AOpToken> for RISCVOpToken {
+    fn from(op: RV32AOpToken) -> RISCVOpToken {
+        RISCVOpToken {
+            val: op as u8,
+            get_opd_set_fn: |v| &OP_LIST[v as usize],
+        }
+    }
+}
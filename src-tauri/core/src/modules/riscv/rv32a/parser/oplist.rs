@@ -0,0 +1,292 @@
+use once_cell::sync::Lazy;
+use RV32IRegister::*;
+
+pub use super::super::super::basic::parser::oplist::RISCVOpdSet;
+use super::{
+    super::{
+        super::{
+            basic::{interface::parser::RISCVImmediate, parser::oplist::*},
+            rv32i::constants::RV32IRegister,
+        },
+        constants::RV32AInstruction,
+    },
+    lexer::RV32AOpToken,
+};
+use crate::utility::enum_map::build_map_mut_data;
+
+// --------------------set-------------------------
+fn opd_set_lr(aqrl: RISCVImmediate, name: &str) -> Vec<RISCVOpdSet> {
+    vec![opd_set(
+        expect_opd(vec![Reg, Comma, LParen, Reg, RParen]),
+        vec![basic_op(
+            RV32AInstruction::LrW.into(),
+            vec![idx(0), idx(3), reg(Zero), imm(aqrl)],
+        )],
+        format!("{} t1, (t2) (t1 = mem[t2]; reserve t2)", name),
+    )]
+}
+
+fn opd_set_sc(aqrl: RISCVImmediate, name: &str) -> Vec<RISCVOpdSet> {
+    vec![opd_set(
+        expect_opd(vec![Reg, Comma, Reg, Comma, LParen, Reg, RParen]),
+        vec![basic_op(
+            RV32AInstruction::ScW.into(),
+            vec![idx(0), idx(5), idx(2), imm(aqrl)],
+        )],
+        format!(
+            "{} t1, t2, (t3) (if t3 is reserved: mem[t3] = t2, t1 = 0; else t1 = 1)",
+            name
+        ),
+    )]
+}
+
+fn opd_set_amo(
+    op: RV32AInstruction,
+    aqrl: RISCVImmediate,
+    name: &str,
+    val: &str,
+) -> Vec<RISCVOpdSet> {
+    vec![opd_set(
+        expect_opd(vec![Reg, Comma, Reg, Comma, LParen, Reg, RParen]),
+        vec![basic_op(op.into(), vec![idx(0), idx(5), idx(2), imm(aqrl)])],
+        format!("{} t1, t2, (t3) (t1 = mem[t3]; mem[t3] = {})", name, val),
+    )]
+}
+
+pub static OP_LIST: Lazy<Vec<Vec<RISCVOpdSet>>> = Lazy::new(|| {
+    let mut op_def = [
+        (
+            RV32AOpToken::AmoaddW,
+            opd_set_amo(RV32AInstruction::AmoaddW, 0, "amoadd.w", "t1 + t2"),
+        ),
+        (
+            RV32AOpToken::AmoaddWAq,
+            opd_set_amo(RV32AInstruction::AmoaddW, 2, "amoadd.w.aq", "t1 + t2"),
+        ),
+        (
+            RV32AOpToken::AmoaddWRl,
+            opd_set_amo(RV32AInstruction::AmoaddW, 1, "amoadd.w.rl", "t1 + t2"),
+        ),
+        (
+            RV32AOpToken::AmoaddWAqrl,
+            opd_set_amo(RV32AInstruction::AmoaddW, 3, "amoadd.w.aqrl", "t1 + t2"),
+        ),
+        (
+            RV32AOpToken::AmoandW,
+            opd_set_amo(RV32AInstruction::AmoandW, 0, "amoand.w", "t1 & t2"),
+        ),
+        (
+            RV32AOpToken::AmoandWAq,
+            opd_set_amo(RV32AInstruction::AmoandW, 2, "amoand.w.aq", "t1 & t2"),
+        ),
+        (
+            RV32AOpToken::AmoandWRl,
+            opd_set_amo(RV32AInstruction::AmoandW, 1, "amoand.w.rl", "t1 & t2"),
+        ),
+        (
+            RV32AOpToken::AmoandWAqrl,
+            opd_set_amo(RV32AInstruction::AmoandW, 3, "amoand.w.aqrl", "t1 & t2"),
+        ),
+        (
+            RV32AOpToken::AmomaxW,
+            opd_set_amo(
+                RV32AInstruction::AmomaxW,
+                0,
+                "amomax.w",
+                "max(t1, t2) (signed)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmomaxWAq,
+            opd_set_amo(
+                RV32AInstruction::AmomaxW,
+                2,
+                "amomax.w.aq",
+                "max(t1, t2) (signed)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmomaxWRl,
+            opd_set_amo(
+                RV32AInstruction::AmomaxW,
+                1,
+                "amomax.w.rl",
+                "max(t1, t2) (signed)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmomaxWAqrl,
+            opd_set_amo(
+                RV32AInstruction::AmomaxW,
+                3,
+                "amomax.w.aqrl",
+                "max(t1, t2) (signed)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmomaxuW,
+            opd_set_amo(
+                RV32AInstruction::AmomaxuW,
+                0,
+                "amomaxu.w",
+                "max(t1, t2) (unsigned)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmomaxuWAq,
+            opd_set_amo(
+                RV32AInstruction::AmomaxuW,
+                2,
+                "amomaxu.w.aq",
+                "max(t1, t2) (unsigned)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmomaxuWRl,
+            opd_set_amo(
+                RV32AInstruction::AmomaxuW,
+                1,
+                "amomaxu.w.rl",
+                "max(t1, t2) (unsigned)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmomaxuWAqrl,
+            opd_set_amo(
+                RV32AInstruction::AmomaxuW,
+                3,
+                "amomaxu.w.aqrl",
+                "max(t1, t2) (unsigned)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmominW,
+            opd_set_amo(
+                RV32AInstruction::AmominW,
+                0,
+                "amomin.w",
+                "min(t1, t2) (signed)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmominWAq,
+            opd_set_amo(
+                RV32AInstruction::AmominW,
+                2,
+                "amomin.w.aq",
+                "min(t1, t2) (signed)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmominWRl,
+            opd_set_amo(
+                RV32AInstruction::AmominW,
+                1,
+                "amomin.w.rl",
+                "min(t1, t2) (signed)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmominWAqrl,
+            opd_set_amo(
+                RV32AInstruction::AmominW,
+                3,
+                "amomin.w.aqrl",
+                "min(t1, t2) (signed)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmominuW,
+            opd_set_amo(
+                RV32AInstruction::AmominuW,
+                0,
+                "amominu.w",
+                "min(t1, t2) (unsigned)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmominuWAq,
+            opd_set_amo(
+                RV32AInstruction::AmominuW,
+                2,
+                "amominu.w.aq",
+                "min(t1, t2) (unsigned)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmominuWRl,
+            opd_set_amo(
+                RV32AInstruction::AmominuW,
+                1,
+                "amominu.w.rl",
+                "min(t1, t2) (unsigned)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmominuWAqrl,
+            opd_set_amo(
+                RV32AInstruction::AmominuW,
+                3,
+                "amominu.w.aqrl",
+                "min(t1, t2) (unsigned)",
+            ),
+        ),
+        (
+            RV32AOpToken::AmoorW,
+            opd_set_amo(RV32AInstruction::AmoorW, 0, "amoor.w", "t1 | t2"),
+        ),
+        (
+            RV32AOpToken::AmoorWAq,
+            opd_set_amo(RV32AInstruction::AmoorW, 2, "amoor.w.aq", "t1 | t2"),
+        ),
+        (
+            RV32AOpToken::AmoorWRl,
+            opd_set_amo(RV32AInstruction::AmoorW, 1, "amoor.w.rl", "t1 | t2"),
+        ),
+        (
+            RV32AOpToken::AmoorWAqrl,
+            opd_set_amo(RV32AInstruction::AmoorW, 3, "amoor.w.aqrl", "t1 | t2"),
+        ),
+        (
+            RV32AOpToken::AmoswapW,
+            opd_set_amo(RV32AInstruction::AmoswapW, 0, "amoswap.w", "t2"),
+        ),
+        (
+            RV32AOpToken::AmoswapWAq,
+            opd_set_amo(RV32AInstruction::AmoswapW, 2, "amoswap.w.aq", "t2"),
+        ),
+        (
+            RV32AOpToken::AmoswapWRl,
+            opd_set_amo(RV32AInstruction::AmoswapW, 1, "amoswap.w.rl", "t2"),
+        ),
+        (
+            RV32AOpToken::AmoswapWAqrl,
+            opd_set_amo(RV32AInstruction::AmoswapW, 3, "amoswap.w.aqrl", "t2"),
+        ),
+        (
+            RV32AOpToken::AmoxorW,
+            opd_set_amo(RV32AInstruction::AmoxorW, 0, "amoxor.w", "t1 ^ t2"),
+        ),
+        (
+            RV32AOpToken::AmoxorWAq,
+            opd_set_amo(RV32AInstruction::AmoxorW, 2, "amoxor.w.aq", "t1 ^ t2"),
+        ),
+        (
+            RV32AOpToken::AmoxorWRl,
+            opd_set_amo(RV32AInstruction::AmoxorW, 1, "amoxor.w.rl", "t1 ^ t2"),
+        ),
+        (
+            RV32AOpToken::AmoxorWAqrl,
+            opd_set_amo(RV32AInstruction::AmoxorW, 3, "amoxor.w.aqrl", "t1 ^ t2"),
+        ),
+        (RV32AOpToken::LrW, opd_set_lr(0, "lr.w")),
+        (RV32AOpToken::LrWAq, opd_set_lr(2, "lr.w.aq")),
+        (RV32AOpToken::LrWRl, opd_set_lr(1, "lr.w.rl")),
+        (RV32AOpToken::LrWAqrl, opd_set_lr(3, "lr.w.aqrl")),
+        (RV32AOpToken::ScW, opd_set_sc(0, "sc.w")),
+        (RV32AOpToken::ScWAq, opd_set_sc(2, "sc.w.aq")),
+        (RV32AOpToken::ScWRl, opd_set_sc(1, "sc.w.rl")),
+        (RV32AOpToken::ScWAqrl, opd_set_sc(3, "sc.w.aqrl")),
+    ];
+    build_map_mut_data(&mut op_def, |def| (def.0, std::mem::take(&mut def.1)))
+});
@@ -0,0 +1,282 @@
+use crate::modules::riscv::{
+    basic::assembler::basic::PackedInstruction,
+    rv32c::constants::RV32CInstruction,
+    rv32i::constants::{RISCVImmediate, RV32IInstruction},
+};
+
+pub struct RV32C {}
+
+/// Bits `hi..=lo` of `value`, shifted down to bit 0.
+fn bits(value: u32, hi: u32, lo: u32) -> u32 {
+    (value >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+/// The 3-bit register field of the CIW/CL/CS/CA/CB formats, only x8-x15 can be
+/// encoded.
+fn creg(reg: u32) -> Option<u32> {
+    (8..16).contains(&reg).then(|| reg - 8)
+}
+
+fn ensure(cond: bool, op: RV32CInstruction, what: &str) -> Result<(), String> {
+    match cond {
+        true => Ok(()),
+        false => Err(format!("{}: {}", op.name(), what)),
+    }
+}
+
+fn ensure_creg(reg: u32, op: RV32CInstruction) -> Result<u32, String> {
+    creg(reg).ok_or(format!(
+        "{}: register x{} is not one of x8-x15",
+        op.name(),
+        reg
+    ))
+}
+
+fn ensure_range(
+    imm: RISCVImmediate,
+    min: RISCVImmediate,
+    max: RISCVImmediate,
+    align: RISCVImmediate,
+    op: RV32CInstruction,
+) -> Result<u32, String> {
+    ensure(
+        imm >= min && imm <= max && imm % align == 0,
+        op,
+        &match align {
+            1 => format!("immediate {} out of range [{}, {}]", imm, min, max),
+            _ => format!(
+                "immediate {} out of range [{}, {}] or not a multiple of {}",
+                imm, min, max, align
+            ),
+        },
+    )?;
+    Ok(imm as u32)
+}
+
+fn ci(funct3: u32, imm: u32, rd: u32, op: u32) -> u32 {
+    (funct3 << 13) | (bits(imm, 5, 5) << 12) | (rd << 7) | (bits(imm, 4, 0) << 2) | op
+}
+
+fn cr(funct4: u32, rd: u32, rs2: u32) -> u32 {
+    (funct4 << 12) | (rd << 7) | (rs2 << 2) | 0b10
+}
+
+fn cj(funct3: u32, offset: u32) -> u32 {
+    (funct3 << 13)
+        | (bits(offset, 11, 11) << 12)
+        | (bits(offset, 4, 4) << 11)
+        | (bits(offset, 9, 8) << 9)
+        | (bits(offset, 10, 10) << 8)
+        | (bits(offset, 6, 6) << 7)
+        | (bits(offset, 7, 7) << 6)
+        | (bits(offset, 3, 1) << 3)
+        | (bits(offset, 5, 5) << 2)
+        | 0b01
+}
+
+fn cb_branch(funct3: u32, rs1: u32, offset: u32) -> u32 {
+    (funct3 << 13)
+        | (bits(offset, 8, 8) << 12)
+        | (bits(offset, 4, 3) << 10)
+        | (rs1 << 7)
+        | (bits(offset, 7, 6) << 5)
+        | (bits(offset, 2, 1) << 3)
+        | (bits(offset, 5, 5) << 2)
+        | 0b01
+}
+
+fn cl_cs(funct3: u32, rs1: u32, reg: u32, uimm: u32) -> u32 {
+    (funct3 << 13)
+        | (bits(uimm, 5, 3) << 10)
+        | (rs1 << 7)
+        | (bits(uimm, 2, 2) << 6)
+        | (bits(uimm, 6, 6) << 5)
+        | (reg << 2)
+}
+
+fn ca(funct2: u32, rd: u32, rs2: u32) -> u32 {
+    (0b100011 << 10) | (rd << 7) | (funct2 << 5) | (rs2 << 2) | 0b01
+}
+
+fn cb_alu(funct2: u32, rd: u32, imm: u32) -> u32 {
+    (0b100 << 13)
+        | (bits(imm, 5, 5) << 12)
+        | (funct2 << 10)
+        | (rd << 7)
+        | (bits(imm, 4, 0) << 2)
+        | 0b01
+}
+
+impl RV32C {
+    /// Encode a compressed instruction into its 16-bit form.
+    ///
+    /// The operands are the ones of the base instruction: `rd` is the
+    /// destination (the loaded register for `c.lw`/`c.lwsp`), `rs2` is the
+    /// stored register for `c.sw`/`c.swsp`, and `imm` is the base immediate
+    /// (the byte offset for branches and jumps, the 20-bit value for `c.lui`).
+    pub fn encode(
+        op: RV32CInstruction,
+        rd: u32,
+        rs1: u32,
+        rs2: u32,
+        imm: RISCVImmediate,
+    ) -> Result<PackedInstruction, String> {
+        use RV32CInstruction::*;
+        let code = match op {
+            CAddi4spn => {
+                ensure(rs1 == 2, op, "source register must be sp")?;
+                let rd = ensure_creg(rd, op)?;
+                let imm = ensure_range(imm, 4, 1020, 4, op)?;
+                (bits(imm, 5, 4) << 11)
+                    | (bits(imm, 9, 6) << 7)
+                    | (bits(imm, 2, 2) << 6)
+                    | (bits(imm, 3, 3) << 5)
+                    | (rd << 2)
+            }
+            CLw => {
+                let rs1 = ensure_creg(rs1, op)?;
+                let rd = ensure_creg(rd, op)?;
+                cl_cs(0b010, rs1, rd, ensure_range(imm, 0, 124, 4, op)?)
+            }
+            CSw => {
+                let rs1 = ensure_creg(rs1, op)?;
+                let rs2 = ensure_creg(rs2, op)?;
+                cl_cs(0b110, rs1, rs2, ensure_range(imm, 0, 124, 4, op)?)
+            }
+            CNop => {
+                ensure(rd == 0 && rs1 == 0 && imm == 0, op, "takes no operand")?;
+                0b01
+            }
+            CAddi => {
+                ensure(rd == rs1 && rd != 0, op, "rd must equal rs1 and not be x0")?;
+                ensure(imm != 0, op, "immediate must not be zero")?;
+                ci(0b000, ensure_range(imm, -32, 31, 1, op)?, rd, 0b01)
+            }
+            CJal => {
+                ensure(rd == 1, op, "link register must be ra")?;
+                cj(0b001, ensure_range(imm, -2048, 2046, 2, op)?)
+            }
+            CLi => {
+                ensure(rs1 == 0 && rd != 0, op, "rd must not be x0")?;
+                ci(0b010, ensure_range(imm, -32, 31, 1, op)?, rd, 0b01)
+            }
+            CAddi16sp => {
+                ensure(rd == 2 && rs1 == 2, op, "register must be sp")?;
+                ensure(imm != 0, op, "immediate must not be zero")?;
+                let imm = ensure_range(imm, -512, 496, 16, op)?;
+                (0b011 << 13)
+                    | (bits(imm, 9, 9) << 12)
+                    | (2 << 7)
+                    | (bits(imm, 4, 4) << 6)
+                    | (bits(imm, 6, 6) << 5)
+                    | (bits(imm, 8, 7) << 3)
+                    | (bits(imm, 5, 5) << 2)
+                    | 0b01
+            }
+            CLui => {
+                ensure(rd != 0 && rd != 2, op, "rd must not be x0 or sp")?;
+                let imm = imm as u32;
+                ensure(
+                    (1..=0x1f).contains(&imm) || (0xfffe0..=0xfffff).contains(&imm),
+                    op,
+                    &format!("immediate 0x{:x} is not a non-zero 6-bit signed value", imm),
+                )?;
+                ci(0b011, imm, rd, 0b01)
+            }
+            CSrli | CSrai | CAndi => {
+                ensure(rd == rs1, op, "rd must equal rs1")?;
+                let rd = ensure_creg(rd, op)?;
+                match op {
+                    CAndi => cb_alu(0b10, rd, ensure_range(imm, -32, 31, 1, op)?),
+                    CSrli => cb_alu(0b00, rd, ensure_range(imm, 1, 31, 1, op)?),
+                    _ => cb_alu(0b01, rd, ensure_range(imm, 1, 31, 1, op)?),
+                }
+            }
+            CSub | CXor | COr | CAnd => {
+                ensure(rd == rs1, op, "rd must equal rs1")?;
+                let rd = ensure_creg(rd, op)?;
+                let rs2 = ensure_creg(rs2, op)?;
+                let funct2 = match op {
+                    CSub => 0b00,
+                    CXor => 0b01,
+                    COr => 0b10,
+                    _ => 0b11,
+                };
+                ca(funct2, rd, rs2)
+            }
+            CJ => {
+                ensure(rd == 0, op, "must not link")?;
+                cj(0b101, ensure_range(imm, -2048, 2046, 2, op)?)
+            }
+            CBeqz | CBnez => {
+                ensure(rs2 == 0, op, "must compare with zero")?;
+                let rs1 = ensure_creg(rs1, op)?;
+                let offset = ensure_range(imm, -256, 254, 2, op)?;
+                cb_branch(if op == CBeqz { 0b110 } else { 0b111 }, rs1, offset)
+            }
+            CSlli => {
+                ensure(rd == rs1 && rd != 0, op, "rd must equal rs1 and not be x0")?;
+                ci(0b000, ensure_range(imm, 1, 31, 1, op)?, rd, 0b10)
+            }
+            CLwsp => {
+                ensure(rs1 == 2, op, "base register must be sp")?;
+                ensure(rd != 0, op, "rd must not be x0")?;
+                let imm = ensure_range(imm, 0, 252, 4, op)?;
+                (0b010 << 13)
+                    | (bits(imm, 5, 5) << 12)
+                    | (rd << 7)
+                    | (bits(imm, 4, 2) << 4)
+                    | (bits(imm, 7, 6) << 2)
+                    | 0b10
+            }
+            CSwsp => {
+                ensure(rs1 == 2, op, "base register must be sp")?;
+                let imm = ensure_range(imm, 0, 252, 4, op)?;
+                (0b110 << 13) | (bits(imm, 5, 2) << 9) | (bits(imm, 7, 6) << 7) | (rs2 << 2) | 0b10
+            }
+            CJr | CJalr => {
+                ensure(
+                    rd == if op == CJr { 0 } else { 1 },
+                    op,
+                    "wrong link register",
+                )?;
+                ensure(
+                    rs1 != 0 && imm == 0,
+                    op,
+                    "rs1 must not be x0 and offset must be 0",
+                )?;
+                cr(if op == CJr { 0b1000 } else { 0b1001 }, rs1, 0)
+            }
+            CMv => {
+                ensure(
+                    rs1 == 0 && rd != 0 && rs2 != 0,
+                    op,
+                    "registers must not be x0",
+                )?;
+                cr(0b1000, rd, rs2)
+            }
+            CAdd => {
+                ensure(rd == rs1 && rd != 0, op, "rd must equal rs1 and not be x0")?;
+                ensure(rs2 != 0, op, "rs2 must not be x0")?;
+                cr(0b1001, rd, rs2)
+            }
+            CEbreak => cr(0b1001, 0, 0),
+        };
+        Ok(code.into())
+    }
+
+    /// Find a compressed instruction equivalent to the base instruction with
+    /// the given operands, see [`RV32C::encode`] for the operand layout.
+    pub fn compress(
+        base: RV32IInstruction,
+        rd: u32,
+        rs1: u32,
+        rs2: u32,
+        imm: RISCVImmediate,
+    ) -> Option<RV32CInstruction> {
+        RV32CInstruction::candidates(base)
+            .iter()
+            .find(|&&op| Self::encode(op, rd, rs1, rs2, imm).is_ok())
+            .copied()
+    }
+}
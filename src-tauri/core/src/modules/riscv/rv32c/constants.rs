@@ -0,0 +1,108 @@
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+use super::super::rv32i::constants::RV32IInstruction;
+
+/// Compressed instructions of the C extension (RV32C, integer subset).
+///
+/// Every compressed instruction expands to exactly one base instruction, the
+/// operands of a compressed instruction are always stored in the layout of
+/// its base instruction, so that the assembler and simulator can reuse the
+/// RV32I implementation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RV32CInstruction {
+    CAdd,
+    CAddi,
+    CAddi16sp,
+    CAddi4spn,
+    CAnd,
+    CAndi,
+    CBeqz,
+    CBnez,
+    CEbreak,
+    #[strum(serialize = "c_j")]
+    CJ,
+    CJal,
+    CJalr,
+    CJr,
+    CLi,
+    CLui,
+    CLw,
+    CLwsp,
+    CMv,
+    CNop,
+    COr,
+    CSlli,
+    CSrai,
+    CSrli,
+    CSub,
+    CSw,
+    CSwsp,
+    CXor,
+}
+
+impl RV32CInstruction {
+    pub fn name(&self) -> String {
+        Into::<&'static str>::into(self).replace("_", ".")
+    }
+
+    /// The base instruction this compressed instruction expands to.
+    pub fn base(&self) -> RV32IInstruction {
+        match self {
+            RV32CInstruction::CAdd | RV32CInstruction::CMv => RV32IInstruction::Add,
+            RV32CInstruction::CAddi
+            | RV32CInstruction::CAddi16sp
+            | RV32CInstruction::CAddi4spn
+            | RV32CInstruction::CLi
+            | RV32CInstruction::CNop => RV32IInstruction::Addi,
+            RV32CInstruction::CAnd => RV32IInstruction::And,
+            RV32CInstruction::CAndi => RV32IInstruction::Andi,
+            RV32CInstruction::CBeqz => RV32IInstruction::Beq,
+            RV32CInstruction::CBnez => RV32IInstruction::Bne,
+            RV32CInstruction::CEbreak => RV32IInstruction::Ebreak,
+            RV32CInstruction::CJ | RV32CInstruction::CJal => RV32IInstruction::Jal,
+            RV32CInstruction::CJalr | RV32CInstruction::CJr => RV32IInstruction::Jalr,
+            RV32CInstruction::CLui => RV32IInstruction::Lui,
+            RV32CInstruction::CLw | RV32CInstruction::CLwsp => RV32IInstruction::Lw,
+            RV32CInstruction::COr => RV32IInstruction::Or,
+            RV32CInstruction::CSlli => RV32IInstruction::Slli,
+            RV32CInstruction::CSrai => RV32IInstruction::Srai,
+            RV32CInstruction::CSrli => RV32IInstruction::Srli,
+            RV32CInstruction::CSub => RV32IInstruction::Sub,
+            RV32CInstruction::CSw | RV32CInstruction::CSwsp => RV32IInstruction::Sw,
+            RV32CInstruction::CXor => RV32IInstruction::Xor,
+        }
+    }
+
+    /// The compressed instructions a base instruction may be compressed to
+    /// automatically, in the order they should be tried.
+    ///
+    /// Control transfer instructions with a label operand are excluded, as
+    /// their offset is unknown before the text layout is fixed.
+    pub fn candidates(base: RV32IInstruction) -> &'static [RV32CInstruction] {
+        match base {
+            RV32IInstruction::Add => &[RV32CInstruction::CMv, RV32CInstruction::CAdd],
+            RV32IInstruction::Addi => &[
+                RV32CInstruction::CNop,
+                RV32CInstruction::CAddi,
+                RV32CInstruction::CLi,
+                RV32CInstruction::CAddi16sp,
+                RV32CInstruction::CAddi4spn,
+            ],
+            RV32IInstruction::And => &[RV32CInstruction::CAnd],
+            RV32IInstruction::Andi => &[RV32CInstruction::CAndi],
+            RV32IInstruction::Ebreak => &[RV32CInstruction::CEbreak],
+            RV32IInstruction::Jalr => &[RV32CInstruction::CJr, RV32CInstruction::CJalr],
+            RV32IInstruction::Lui => &[RV32CInstruction::CLui],
+            RV32IInstruction::Lw => &[RV32CInstruction::CLwsp, RV32CInstruction::CLw],
+            RV32IInstruction::Or => &[RV32CInstruction::COr],
+            RV32IInstruction::Slli => &[RV32CInstruction::CSlli],
+            RV32IInstruction::Srai => &[RV32CInstruction::CSrai],
+            RV32IInstruction::Srli => &[RV32CInstruction::CSrli],
+            RV32IInstruction::Sub => &[RV32CInstruction::CSub],
+            RV32IInstruction::Sw => &[RV32CInstruction::CSwsp, RV32CInstruction::CSw],
+            RV32IInstruction::Xor => &[RV32CInstruction::CXor],
+            _ => &[],
+        }
+    }
+}
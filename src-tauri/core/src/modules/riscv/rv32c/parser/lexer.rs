@@ -0,0 +1,51 @@
+use strum_macros::{IntoStaticStr, VariantArray};
+
+use super::{super::super::basic::parser::lexer::RISCVOpToken, oplist::OP_LIST};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RV32COpToken {
+    CAdd,
+    CAddi,
+    CAddi16sp,
+    CAddi4spn,
+    CAnd,
+    CAndi,
+    CBeqz,
+    CBnez,
+    CEbreak,
+    #[strum(serialize = "c_j")]
+    CJ,
+    CJal,
+    CJalr,
+    CJr,
+    CLi,
+    CLui,
+    CLw,
+    CLwsp,
+    CMv,
+    CNop,
+    COr,
+    CSlli,
+    CSrai,
+    CSrli,
+    CSub,
+    CSw,
+    CSwsp,
+    CXor,
+}
+
+impl RV32COpToken {
+    pub fn name(&self) -> String {
+        Into::<&'static str>::into(self).replace("_", ".")
+    }
+}
+
+impl From<RV32COpToken> for RISCVOpToken {
+    fn from(op: RV32COpToken) -> RISCVOpToken {
+        RISCVOpToken {
+            val: op as u8,
+            get_opd_set_fn: |v| &OP_LIST[v as usize],
+        }
+    }
+}
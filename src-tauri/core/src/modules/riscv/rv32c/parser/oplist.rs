@@ -0,0 +1,292 @@
+use once_cell::sync::Lazy;
+use RV32IRegister::*;
+
+pub use super::super::super::basic::parser::oplist::RISCVOpdSet;
+use super::{
+    super::{
+        super::{
+            basic::{interface::parser::ParserRISCVInstOp, parser::oplist::*},
+            rv32i::constants::RV32IRegister,
+        },
+        constants::RV32CInstruction,
+    },
+    lexer::RV32COpToken,
+};
+use crate::utility::enum_map::build_map_mut_data;
+
+// --------------------set-------------------------
+fn opd_set_mem(op: ParserRISCVInstOp, name: &str, base: &str, load: bool) -> Vec<RISCVOpdSet> {
+    let hint = |addr: &str| match load {
+        true => format!("t1 = mem[{}]", addr),
+        false => format!("mem[{}] = t1", addr),
+    };
+    vec![
+        opd_set(
+            expect_opd(vec![Reg, Comma, Imm(I12), LParen, Reg, RParen]),
+            vec![basic_op_024(op)],
+            format!(
+                "{} t1, 0x4({}) ({})",
+                name,
+                base,
+                hint(&format!("{} + 0x4", base))
+            ),
+        ),
+        opd_set(
+            expect_opd(vec![Reg, Comma, LParen, Reg, RParen]),
+            vec![basic_op(op, vec![idx(0), imm(0), idx(3)])],
+            format!("{} t1, ({}) ({})", name, base, hint(base)),
+        ),
+    ]
+}
+fn opd_set_rd_rd_any(op: ParserRISCVInstOp, any: RISCVExpectToken, hint: String) -> RISCVOpdSet {
+    opd_set(
+        expect_reg_any(any),
+        vec![basic_op(op, vec![idx(0), idx(0), idx(2)])],
+        hint,
+    )
+}
+
+pub static OP_LIST: Lazy<Vec<Vec<RISCVOpdSet>>> = Lazy::new(|| {
+    let mut op_def = [
+        (
+            RV32COpToken::CAdd,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CAdd.into(),
+                Reg,
+                "c.add t1, t2 (t1 = t1 + t2)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CAddi,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CAddi.into(),
+                Imm(I12),
+                "c.addi t1, 0x1 (t1 = t1 + 0x1(i6))".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CAddi16sp,
+            vec![
+                opd_set(
+                    expect_opd(vec![Imm(I12)]),
+                    vec![basic_op(
+                        RV32CInstruction::CAddi16sp.into(),
+                        vec![reg(Sp), reg(Sp), idx(0)],
+                    )],
+                    "c.addi16sp 0x10 (sp = sp + 0x10(i10, scaled by 16))".to_string(),
+                ),
+                opd_set(
+                    expect_reg_any(Imm(I12)),
+                    vec![basic_op(
+                        RV32CInstruction::CAddi16sp.into(),
+                        vec![idx(0), idx(0), idx(2)],
+                    )],
+                    "c.addi16sp sp, 0x10 (sp = sp + 0x10(i10, scaled by 16))".to_string(),
+                ),
+            ],
+        ),
+        (
+            RV32COpToken::CAddi4spn,
+            vec![opd_set(
+                expect_reg_any(Imm(U12)),
+                vec![basic_op(
+                    RV32CInstruction::CAddi4spn.into(),
+                    vec![idx(0), reg(Sp), idx(2)],
+                )],
+                "c.addi4spn s0, 0x4 (s0 = sp + 0x4(u10, scaled by 4))".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CAnd,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CAnd.into(),
+                Reg,
+                "c.and s0, s1 (s0 = s0 & s1)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CAndi,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CAndi.into(),
+                Imm(I12),
+                "c.andi s0, 0x1 (s0 = s0 & 0x1(i6))".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CBeqz,
+            vec![opd_set(
+                expect_reg_any(Lbl),
+                vec![basic_op(
+                    RV32CInstruction::CBeqz.into(),
+                    vec![idx(0), reg(Zero), idx(2)],
+                )],
+                hint_branch_zero("c.beqz", "==", ""),
+            )],
+        ),
+        (
+            RV32COpToken::CBnez,
+            vec![opd_set(
+                expect_reg_any(Lbl),
+                vec![basic_op(
+                    RV32CInstruction::CBnez.into(),
+                    vec![idx(0), reg(Zero), idx(2)],
+                )],
+                hint_branch_zero("c.bnez", "!=", ""),
+            )],
+        ),
+        (
+            RV32COpToken::CEbreak,
+            vec![opd_set_no_opd(RV32CInstruction::CEbreak.into(), "c.ebreak")],
+        ),
+        (
+            RV32COpToken::CJ,
+            vec![opd_set(
+                expect_opd(vec![Lbl]),
+                vec![basic_op(
+                    RV32CInstruction::CJ.into(),
+                    vec![reg(Zero), idx(0)],
+                )],
+                "c.j label (pc = label)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CJal,
+            vec![opd_set(
+                expect_opd(vec![Lbl]),
+                vec![basic_op(
+                    RV32CInstruction::CJal.into(),
+                    vec![reg(Ra), idx(0)],
+                )],
+                "c.jal label (ra = pc + 2; pc = label)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CJalr,
+            vec![opd_set(
+                expect_opd(vec![Reg]),
+                vec![basic_op(
+                    RV32CInstruction::CJalr.into(),
+                    vec![reg(Ra), idx(0), imm(0)],
+                )],
+                "c.jalr t0 (ra = pc + 2; pc = t0)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CJr,
+            vec![opd_set(
+                expect_opd(vec![Reg]),
+                vec![basic_op(
+                    RV32CInstruction::CJr.into(),
+                    vec![reg(Zero), idx(0), imm(0)],
+                )],
+                "c.jr t0 (pc = t0)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CLi,
+            vec![opd_set(
+                expect_reg_any(Imm(I12)),
+                vec![basic_op(
+                    RV32CInstruction::CLi.into(),
+                    vec![idx(0), reg(Zero), idx(2)],
+                )],
+                "c.li t1, 0x1 (t1 = 0x1(i6))".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CLui,
+            vec![opd_set(
+                expect_reg_any(Imm(U20)),
+                vec![basic_op_02(RV32CInstruction::CLui.into())],
+                "c.lui t1, 0x1 (t1 = 0x1(i6) << 12)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CLw,
+            opd_set_mem(RV32CInstruction::CLw.into(), "c.lw", "s1", true),
+        ),
+        (
+            RV32COpToken::CLwsp,
+            opd_set_mem(RV32CInstruction::CLwsp.into(), "c.lwsp", "sp", true),
+        ),
+        (
+            RV32COpToken::CMv,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op(
+                    RV32CInstruction::CMv.into(),
+                    vec![idx(0), reg(Zero), idx(2)],
+                )],
+                "c.mv t1, t2 (t1 = t2)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CNop,
+            vec![opd_set(
+                vec![],
+                vec![basic_op(
+                    RV32CInstruction::CNop.into(),
+                    vec![reg(Zero), reg(Zero), imm(0)],
+                )],
+                "c.nop".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::COr,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::COr.into(),
+                Reg,
+                "c.or s0, s1 (s0 = s0 | s1)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CSlli,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CSlli.into(),
+                Imm(U5),
+                "c.slli t1, 0x1 (t1 = t1 << 0x1(u5))".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CSrai,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CSrai.into(),
+                Imm(U5),
+                "c.srai s0, 0x1 (s0 = s0 >> 0x1(u5), signed)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CSrli,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CSrli.into(),
+                Imm(U5),
+                "c.srli s0, 0x1 (s0 = s0 >> 0x1(u5))".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CSub,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CSub.into(),
+                Reg,
+                "c.sub s0, s1 (s0 = s0 - s1)".to_string(),
+            )],
+        ),
+        (
+            RV32COpToken::CSw,
+            opd_set_mem(RV32CInstruction::CSw.into(), "c.sw", "s1", false),
+        ),
+        (
+            RV32COpToken::CSwsp,
+            opd_set_mem(RV32CInstruction::CSwsp.into(), "c.swsp", "sp", false),
+        ),
+        (
+            RV32COpToken::CXor,
+            vec![opd_set_rd_rd_any(
+                RV32CInstruction::CXor.into(),
+                Reg,
+                "c.xor s0, s1 (s0 = s0 ^ s1)".to_string(),
+            )],
+        ),
+    ];
+    build_map_mut_data(&mut op_def, |def| (def.0, std::mem::take(&mut def.1)))
+});
@@ -0,0 +1,77 @@
+#[cfg(export_lexers)]
+use std::io::Write;
+
+use once_cell::sync::Lazy;
+use strum::VariantArray;
+
+#[cfg(export_lexers)]
+use super::super::super::basic::{
+    interface::parser::{export_list, export_pair},
+    parser::lexer::RISCVOpToken,
+};
+use super::{
+    super::super::{
+        basic::{
+            interface::parser::ParserRISCVInstOp,
+            parser::{lexer::Symbol, parser::RISCVSymbolList},
+        },
+        rv32c::constants::RV32CInstruction,
+    },
+    lexer::RV32COpToken,
+};
+
+pub static RV32C_SYMBOL_LIST: Lazy<RISCVSymbolList> = Lazy::new(|| vec![&OP_TOKEN]);
+
+pub static OP_TOKEN: Lazy<Vec<(&'static str, Symbol<'static>)>> = Lazy::new(|| {
+    OP_TOKEN_STASH
+        .iter()
+        .map(|op| (op.0.as_str(), op.1))
+        .collect()
+});
+
+#[cfg(export_lexers)]
+pub fn export(folder: &str) -> std::io::Result<()> {
+    let path = format!("{}/rv32c.json", folder);
+    let mut file = std::fs::File::create(&path)?;
+    let mut output = std::io::BufWriter::new(&mut file);
+    let indent = {
+        const LEVEL: usize = 5;
+        let mut indent: [String; LEVEL] = Default::default();
+        for i in 0..LEVEL {
+            indent[i] = "    ".repeat(i);
+        }
+        indent
+    };
+    output.write(format!("{}{{\n", indent[0]).as_bytes())?;
+    output.write(format!("{}\"operator\": ", indent[1]).as_bytes())?;
+    export_pair(
+        RV32COpToken::VARIANTS,
+        |&op| op.name(),
+        |&op, output| {
+            export_list(
+                Into::<RISCVOpToken>::into(op).get_opd_set().as_slice(),
+                |opd_set| Ok(opd_set.hint.clone()),
+                [&indent[2], &indent[3]],
+                output,
+            )
+        },
+        [&indent[1], &indent[2]],
+        &mut output,
+    )?;
+    output.write("\n".as_bytes())?;
+    output.write(format!("{}}}", indent[0]).as_bytes())?;
+    Ok(())
+}
+
+static OP_TOKEN_STASH: Lazy<Vec<(String, Symbol<'static>)>> = Lazy::new(|| {
+    RV32COpToken::VARIANTS
+        .iter()
+        .map(|&op| (op.name(), Symbol::Op(op.into())))
+        .collect()
+});
+
+impl From<RV32CInstruction> for ParserRISCVInstOp {
+    fn from(inst: RV32CInstruction) -> Self {
+        ParserRISCVInstOp::RV32C(inst)
+    }
+}
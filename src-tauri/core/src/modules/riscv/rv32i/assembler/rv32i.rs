@@ -39,14 +39,15 @@ impl RV32I {
     }
 
     pub fn fencei() -> PackedInstruction {
-        (IOpcode::FENCE as u32 | 0b0000000000000000000100000000000).into()
+        (IOpcode::FENCE as u32 | 0b001 << 12).into()
     }
 
     pub fn jal(imm: Immediate20, rd: Register) -> PackedInstruction {
         let imm: u32 = Into::<u32>::into(imm).into();
         JOpcode::JAL
             .builder()
-            .imm20((imm >> 20).try_into().unwrap())
+            // offsets are 20-bit signed values, bit 19 is the sign
+            .imm20(((imm >> 19) & 1).try_into().unwrap())
             .imm19_12(((imm >> 12) & 0b11111111).try_into().unwrap())
             .imm11(((imm >> 11) & 1).try_into().unwrap())
             .imm10_1(((imm >> 1) & 0b1111111111).try_into().unwrap())
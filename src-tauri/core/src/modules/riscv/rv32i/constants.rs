@@ -123,10 +123,22 @@ pub enum RV32IInstruction {
     Xori,
 }
 
-pub type RISCVImmediate = i32;
+pub type RISCVImmediate = i64;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, EnumString, IntoStaticStr)]
-pub enum RV32ICsr {}
+#[strum(serialize_all = "snake_case")]
+pub enum RV32ICsr {
+    Mhartid,
+}
+
+impl RV32ICsr {
+    /// The 12-bit CSR address.
+    pub fn number(self) -> u32 {
+        match self {
+            RV32ICsr::Mhartid => 0xf14,
+        }
+    }
+}
 
 pub static RV32I_REGISTER_VALID_NAME: [&'static str; 65] = [
     "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
@@ -146,7 +158,7 @@ impl From<RV32IRegister> for &'static str {
 }
 
 pub fn get_32u_low(i: u32) -> RISCVImmediate {
-    ((i & 0x7ff) | -(i as i32 & 0x800) as u32) as RISCVImmediate
+    ((i as i32) << 20 >> 20) as RISCVImmediate
 }
 pub fn get_32u_high(i: u32) -> RISCVImmediate {
     ((i >> 12) + ((i & 0x800) >> 11)) as RISCVImmediate
@@ -21,7 +21,8 @@ use super::{
     lexer::RV32IOpToken,
 };
 
-pub static RV32I_SYMBOL_LIST: Lazy<RISCVSymbolList> = Lazy::new(|| vec![&OP_TOKEN, &REG_TOKEN]);
+pub static RV32I_SYMBOL_LIST: Lazy<RISCVSymbolList> =
+    Lazy::new(|| vec![&OP_TOKEN, &REG_TOKEN, &CSR_TOKEN]);
 
 pub static OP_TOKEN: Lazy<Vec<(&'static str, Symbol<'static>)>> = Lazy::new(|| {
     OP_TOKEN_STASH
@@ -66,9 +67,9 @@ pub fn export(folder: &str) -> std::io::Result<()> {
     output.write(format!("{}\"directive\": ", indent[1]).as_bytes())?;
     export_list(
         &[
-            ".align", ".ascii", ".asciz", ".byte", ".data", ".double", ".dword", ".eqv", ".extern",
-            ".float", ".global", ".half", ".include", ".section", ".space", ".string", ".text",
-            ".word",
+            ".align", ".ascii", ".asciz", ".bss", ".byte", ".data", ".double", ".dword", ".eqv",
+            ".extern", ".float", ".global", ".half", ".include", ".rodata", ".section", ".space",
+            ".string", ".text", ".word",
         ],
         |&dir| Ok(dir),
         [&indent[1], &indent[2]],
@@ -0,0 +1,56 @@
+use crate::modules::riscv::basic::assembler::{
+    basic::{IOpcode, ImmediateFormatter, Opcode, PackedInstruction, ROpcode, SOpcode},
+    riscv::*,
+};
+
+pub struct RV64I {}
+
+impl RV64I {
+    /// `slli`, `srli` and `srai` take a 6-bit shift amount in RV64I, its
+    /// highest bit takes the place of the lowest bit of funct7.
+    fn shift(
+        funct6: u32,
+        funct3: u32,
+        shamt: u32,
+        rs1: Register,
+        rd: Register,
+    ) -> PackedInstruction {
+        ROpcode::Shamt
+            .builder()
+            .funct7(((funct6 << 1) | ((shamt >> 5) & 1)).try_into().unwrap())
+            .rs2((shamt & 0x1f).try_into().unwrap())
+            .rs1(rs1.into())
+            .funct3(funct3.try_into().unwrap())
+            .rd(rd.into())
+            .build()
+            .unwrap()
+            .into()
+    }
+
+    pub fn slli(shamt: u32, rs1: Register, rd: Register) -> PackedInstruction {
+        Self::shift(0b000000, 0b001, shamt, rs1, rd)
+    }
+
+    pub fn srai(shamt: u32, rs1: Register, rd: Register) -> PackedInstruction {
+        Self::shift(0b010000, 0b101, shamt, rs1, rd)
+    }
+
+    pub fn srli(shamt: u32, rs1: Register, rd: Register) -> PackedInstruction {
+        Self::shift(0b000000, 0b101, shamt, rs1, rd)
+    }
+
+    crate::rinstimpl!(ShamtW, slliw, 0b0000000, 0b001, shamt);
+    crate::rinstimpl!(ShamtW, sraiw, 0b0100000, 0b101, shamt);
+    crate::rinstimpl!(ShamtW, srliw, 0b0000000, 0b101, shamt);
+    crate::rinstimpl!(ALURegW, addw, 0b0000000, 0b000, rs2);
+    crate::rinstimpl!(ALURegW, sllw, 0b0000000, 0b001, rs2);
+    crate::rinstimpl!(ALURegW, sraw, 0b0100000, 0b101, rs2);
+    crate::rinstimpl!(ALURegW, srlw, 0b0000000, 0b101, rs2);
+    crate::rinstimpl!(ALURegW, subw, 0b0100000, 0b000, rs2);
+
+    crate::iinstimpl!(ALUImmW, addiw, 0b000);
+    crate::iinstimpl!(Load, ld, 0b011);
+    crate::iinstimpl!(Load, lwu, 0b110);
+
+    crate::sinstimpl!(Store, sd, 0b011);
+}
@@ -0,0 +1,22 @@
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+/// Instructions RV64I adds on top of RV32I.
+///
+/// The operands follow the layout of the RV32I instruction of the same
+/// format, loads and stores are `[data, offset, base]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RV64IInstruction {
+    Addiw,
+    Addw,
+    Ld,
+    Lwu,
+    Sd,
+    Slliw,
+    Sllw,
+    Sraiw,
+    Sraw,
+    Srliw,
+    Srlw,
+    Subw,
+}
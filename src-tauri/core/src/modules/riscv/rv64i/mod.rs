@@ -0,0 +1,5 @@
+#[allow(dead_code, unused_imports)]
+pub mod assembler;
+#[allow(unreachable_patterns)]
+pub mod constants;
+pub mod parser;
@@ -0,0 +1,47 @@
+use strum_macros::{IntoStaticStr, VariantArray};
+
+use super::{super::super::basic::parser::lexer::RISCVOpToken, oplist::OP_LIST};
+
+/// RV64I operators, including the RV32I ones that behave differently when
+/// registers are 64 bits wide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RV64IOpToken {
+    Addiw,
+    Addw,
+    Ld,
+    Li,
+    Lwu,
+    Negw,
+    Sd,
+    SextB,
+    SextH,
+    SextW,
+    Slli,
+    Slliw,
+    Sllw,
+    Srai,
+    Sraiw,
+    Sraw,
+    Srli,
+    Srliw,
+    Srlw,
+    Subw,
+    ZextB,
+    ZextH,
+}
+
+impl RV64IOpToken {
+    pub fn name(&self) -> String {
+        Into::<&'static str>::into(self).replace("_", ".")
+    }
+}
+
+impl From<RV64IOpToken> for RISCVOpToken {
+    fn from(op: RV64IOpToken) -> RISCVOpToken {
+        RISCVOpToken {
+            val: op as u8,
+            get_opd_set_fn: |v| &OP_LIST[v as usize],
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use once_cell::sync::Lazy;
+use RV32IRegister::*;
+
+pub use super::super::super::basic::parser::oplist::RISCVOpdSet;
+use super::{
+    super::{
+        super::{
+            basic::{
+                interface::parser::{ParserRISCVInstOp, RISCVImmediate},
+                parser::oplist::*,
+            },
+            rv32i::{
+                constants::{RV32IInstruction, RV32IRegister},
+                parser::oplist::{opd_set_load_mem, opd_set_store_mem},
+            },
+        },
+        constants::RV64IInstruction,
+    },
+    lexer::RV64IOpToken,
+};
+use crate::utility::enum_map::build_map_mut_data;
+
+// --------------------set-------------------------
+fn opd_set_shift_pair(
+    shl: ParserRISCVInstOp,
+    shr: ParserRISCVInstOp,
+    amount: RISCVImmediate,
+    hint: &str,
+) -> Vec<RISCVOpdSet> {
+    vec![opd_set(
+        expect_reg_reg(),
+        vec![
+            basic_op(shl, vec![idx(0), idx(2), imm(amount)]),
+            basic_op(shr, vec![idx(0), idx(0), imm(amount)]),
+        ],
+        hint.to_string(),
+    )]
+}
+
+pub static OP_LIST: Lazy<Vec<Vec<RISCVOpdSet>>> = Lazy::new(|| {
+    let mut op_def = [
+        (
+            RV64IOpToken::Addiw,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(I12)),
+                vec![basic_op_024(RV64IInstruction::Addiw.into())],
+                "addiw t1, t2, -0x1 (t1 = (i32)(t2 + -0x1(i12)))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Addw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RV64IInstruction::Addw.into())],
+                "addw t1, t2, t3 (t1 = (i32)(t2 + t3))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Ld,
+            opd_set_load_mem(RV64IInstruction::Ld.into(), "ld", "(i64)"),
+        ),
+        (
+            RV64IOpToken::Li,
+            vec![
+                opd_set(
+                    expect_reg_any(Imm(I12)),
+                    vec![basic_op(
+                        RV32IInstruction::Addi.into(),
+                        vec![idx(0), reg(Zero), idx(2)],
+                    )],
+                    "li t1, -0x1 (t1 = -0x1(i12))".to_string(),
+                ),
+                opd_set(
+                    expect_reg_any(Imm(I32)),
+                    vec![
+                        basic_op(
+                            RV32IInstruction::Lui.into(),
+                            vec![idx(0), idx_handler(2, idx_handler_imm_high)],
+                        ),
+                        basic_op(
+                            RV64IInstruction::Addiw.into(),
+                            vec![idx(0), idx(0), idx_handler(2, idx_handler_imm_low)],
+                        ),
+                    ],
+                    "li t1, 0x100000 (t1 = 0x100000[12:31](i32); t1 = (i32)(t1 + 0x100000[0:11]))"
+                        .to_string(),
+                ),
+                opd_set(
+                    expect_reg_any(Imm(I64)),
+                    vec![
+                        basic_op(
+                            RV32IInstruction::Lui.into(),
+                            vec![idx(0), idx_handler(2, idx_handler_imm64_part::<0>)],
+                        ),
+                        basic_op(
+                            RV64IInstruction::Addiw.into(),
+                            vec![idx(0), idx(0), idx_handler(2, idx_handler_imm64_part::<1>)],
+                        ),
+                        basic_op(RV32IInstruction::Slli.into(), vec![idx(0), idx(0), imm(12)]),
+                        basic_op(
+                            RV32IInstruction::Addi.into(),
+                            vec![idx(0), idx(0), idx_handler(2, idx_handler_imm64_part::<2>)],
+                        ),
+                        basic_op(RV32IInstruction::Slli.into(), vec![idx(0), idx(0), imm(12)]),
+                        basic_op(
+                            RV32IInstruction::Addi.into(),
+                            vec![idx(0), idx(0), idx_handler(2, idx_handler_imm64_part::<3>)],
+                        ),
+                        basic_op(RV32IInstruction::Slli.into(), vec![idx(0), idx(0), imm(12)]),
+                        basic_op(
+                            RV32IInstruction::Addi.into(),
+                            vec![idx(0), idx(0), idx_handler(2, idx_handler_imm64_part::<4>)],
+                        ),
+                    ],
+                    "li t1, 0x100000000 (t1 = 0x100000000[36:63]; t1 = (t1 << 12) + 0x100000000[24:35]; ...)"
+                        .to_string(),
+                ),
+            ],
+        ),
+        (
+            RV64IOpToken::Lwu,
+            opd_set_load_mem(RV64IInstruction::Lwu.into(), "lwu", "(u32)"),
+        ),
+        (
+            RV64IOpToken::Negw,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op(
+                    RV64IInstruction::Subw.into(),
+                    vec![idx(0), reg(Zero), idx(2)],
+                )],
+                "negw t1, t2 (t1 = (i32)(-t2))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Sd,
+            opd_set_store_mem(RV64IInstruction::Sd.into(), "sd", "(u64)"),
+        ),
+        (
+            RV64IOpToken::SextB,
+            opd_set_shift_pair(
+                RV32IInstruction::Slli.into(),
+                RV32IInstruction::Srai.into(),
+                56,
+                "sext.b t1, t2 (t1 = (i8)t2[0:7])",
+            ),
+        ),
+        (
+            RV64IOpToken::SextH,
+            opd_set_shift_pair(
+                RV32IInstruction::Slli.into(),
+                RV32IInstruction::Srai.into(),
+                48,
+                "sext.h t1, t2 (t1 = (i16)t2[0:15])",
+            ),
+        ),
+        (
+            RV64IOpToken::SextW,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op(
+                    RV64IInstruction::Addiw.into(),
+                    vec![idx(0), idx(2), imm(0)],
+                )],
+                "sext.w t1, t2 (t1 = (i32)t2[0:31])".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Slli,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RV32IInstruction::Slli.into())],
+                hint_reg_reg_any("slli", "0x1(u6)", "<<"),
+            )],
+        ),
+        (
+            RV64IOpToken::Slliw,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U5)),
+                vec![basic_op_024(RV64IInstruction::Slliw.into())],
+                "slliw t1, t2, 0x1 (t1 = (i32)(t2 << 0x1(u5)))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Sllw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RV64IInstruction::Sllw.into())],
+                "sllw t1, t2, t3 (t1 = (i32)(t2 << t3[0:4]))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Srai,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RV32IInstruction::Srai.into())],
+                hint_reg_reg_any("srai", "0x1(u6)", ">>"),
+            )],
+        ),
+        (
+            RV64IOpToken::Sraiw,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U5)),
+                vec![basic_op_024(RV64IInstruction::Sraiw.into())],
+                "sraiw t1, t2, 0x1 (t1 = (i32)t2 >> 0x1(u5))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Sraw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RV64IInstruction::Sraw.into())],
+                "sraw t1, t2, t3 (t1 = (i32)t2 >> t3[0:4])".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Srli,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RV32IInstruction::Srli.into())],
+                hint_reg_reg_any("srli", "0x1(u6)", ">>"),
+            )],
+        ),
+        (
+            RV64IOpToken::Srliw,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U5)),
+                vec![basic_op_024(RV64IInstruction::Srliw.into())],
+                "srliw t1, t2, 0x1 (t1 = (i32)((u32)t2 >> 0x1(u5)))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Srlw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RV64IInstruction::Srlw.into())],
+                "srlw t1, t2, t3 (t1 = (i32)((u32)t2 >> t3[0:4]))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::Subw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RV64IInstruction::Subw.into())],
+                "subw t1, t2, t3 (t1 = (i32)(t2 - t3))".to_string(),
+            )],
+        ),
+        (
+            RV64IOpToken::ZextB,
+            opd_set_shift_pair(
+                RV32IInstruction::Slli.into(),
+                RV32IInstruction::Srli.into(),
+                56,
+                "zext.b t1, t2 (t1 = (u8)t2[0:7])",
+            ),
+        ),
+        (
+            RV64IOpToken::ZextH,
+            opd_set_shift_pair(
+                RV32IInstruction::Slli.into(),
+                RV32IInstruction::Srli.into(),
+                48,
+                "zext.h t1, t2 (t1 = (u16)t2[0:15])",
+            ),
+        ),
+    ];
+    build_map_mut_data(&mut op_def, |def| (def.0, std::mem::take(&mut def.1)))
+});
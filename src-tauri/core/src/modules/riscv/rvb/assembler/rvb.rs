@@ -0,0 +1,119 @@
+use crate::{
+    modules::riscv::basic::assembler::{
+        basic::{Opcode, PackedInstruction, ROpcode},
+        riscv::*,
+    },
+    types::middleware_types::Xlen,
+};
+
+/// Unary instructions keep their function code in the place of funct7 and
+/// rs2.
+macro_rules! unaryimpl {
+    ($name:ident, $func_name:ident, $funct12:literal, $funct3:literal) => {
+        pub fn $func_name(rs1: Register, rd: Register) -> PackedInstruction {
+            Self::unary(ROpcode::$name, $funct12, $funct3, rs1, rd)
+        }
+    };
+}
+
+/// Instructions with a shift amount or bit index, which is 6 bits wide in
+/// RV64 and takes the lowest bit of funct7.
+macro_rules! shiftimpl {
+    ($name:ident, $func_name:ident, $funct6:literal, $funct3:literal) => {
+        pub fn $func_name(shamt: u32, rs1: Register, rd: Register) -> PackedInstruction {
+            ROpcode::$name
+                .builder()
+                .funct7((($funct6 << 1) | ((shamt >> 5) & 1)).try_into().unwrap())
+                .rs2((shamt & 0x1f).try_into().unwrap())
+                .rs1(rs1.into())
+                .funct3(($funct3 as u32).try_into().unwrap())
+                .rd(rd.into())
+                .build()
+                .unwrap()
+                .into()
+        }
+    };
+}
+
+pub struct RVB {}
+
+impl RVB {
+    fn unary(
+        opcode: ROpcode,
+        funct12: u32,
+        funct3: u32,
+        rs1: Register,
+        rd: Register,
+    ) -> PackedInstruction {
+        opcode
+            .builder()
+            .funct7((funct12 >> 5).try_into().unwrap())
+            .rs2((funct12 & 0x1f).try_into().unwrap())
+            .rs1(rs1.into())
+            .funct3(funct3.try_into().unwrap())
+            .rd(rd.into())
+            .build()
+            .unwrap()
+            .into()
+    }
+
+    crate::rinstimpl!(ALUReg, andn, 0b0100000, 0b111, rs2);
+    crate::rinstimpl!(ALUReg, bclr, 0b0100100, 0b001, rs2);
+    crate::rinstimpl!(ALUReg, bext, 0b0100100, 0b101, rs2);
+    crate::rinstimpl!(ALUReg, binv, 0b0110100, 0b001, rs2);
+    crate::rinstimpl!(ALUReg, bset, 0b0010100, 0b001, rs2);
+    crate::rinstimpl!(ALUReg, max, 0b0000101, 0b110, rs2);
+    crate::rinstimpl!(ALUReg, maxu, 0b0000101, 0b111, rs2);
+    crate::rinstimpl!(ALUReg, min, 0b0000101, 0b100, rs2);
+    crate::rinstimpl!(ALUReg, minu, 0b0000101, 0b101, rs2);
+    crate::rinstimpl!(ALUReg, orn, 0b0100000, 0b110, rs2);
+    crate::rinstimpl!(ALUReg, rol, 0b0110000, 0b001, rs2);
+    crate::rinstimpl!(ALUReg, ror, 0b0110000, 0b101, rs2);
+    crate::rinstimpl!(ALUReg, sh1add, 0b0010000, 0b010, rs2);
+    crate::rinstimpl!(ALUReg, sh2add, 0b0010000, 0b100, rs2);
+    crate::rinstimpl!(ALUReg, sh3add, 0b0010000, 0b110, rs2);
+    crate::rinstimpl!(ALUReg, xnor, 0b0100000, 0b100, rs2);
+    crate::rinstimpl!(ALURegW, add_uw, 0b0000100, 0b000, rs2);
+    crate::rinstimpl!(ALURegW, rolw, 0b0110000, 0b001, rs2);
+    crate::rinstimpl!(ALURegW, rorw, 0b0110000, 0b101, rs2);
+    crate::rinstimpl!(ALURegW, sh1add_uw, 0b0010000, 0b010, rs2);
+    crate::rinstimpl!(ALURegW, sh2add_uw, 0b0010000, 0b100, rs2);
+    crate::rinstimpl!(ALURegW, sh3add_uw, 0b0010000, 0b110, rs2);
+
+    unaryimpl!(Shamt, clz, 0x600, 0b001);
+    unaryimpl!(Shamt, ctz, 0x601, 0b001);
+    unaryimpl!(Shamt, cpop, 0x602, 0b001);
+    unaryimpl!(Shamt, sext_b, 0x604, 0b001);
+    unaryimpl!(Shamt, sext_h, 0x605, 0b001);
+    unaryimpl!(Shamt, orc_b, 0x287, 0b101);
+    unaryimpl!(ShamtW, clzw, 0x600, 0b001);
+    unaryimpl!(ShamtW, ctzw, 0x601, 0b001);
+    unaryimpl!(ShamtW, cpopw, 0x602, 0b001);
+
+    shiftimpl!(Shamt, bclri, 0b010010, 0b001);
+    shiftimpl!(Shamt, bexti, 0b010010, 0b101);
+    shiftimpl!(Shamt, binvi, 0b011010, 0b001);
+    shiftimpl!(Shamt, bseti, 0b001010, 0b001);
+    shiftimpl!(Shamt, rori, 0b011000, 0b101);
+    shiftimpl!(ShamtW, roriw, 0b011000, 0b101);
+    shiftimpl!(ShamtW, slli_uw, 0b000010, 0b001);
+
+    /// The byte count is part of the function code of `rev8`.
+    pub fn rev8(xlen: Xlen, rs1: Register, rd: Register) -> PackedInstruction {
+        let funct12 = match xlen {
+            Xlen::Rv32 => 0x698,
+            Xlen::Rv64 => 0x6b8,
+        };
+        Self::unary(ROpcode::Shamt, funct12, 0b101, rs1, rd)
+    }
+
+    /// `zext.h` is encoded as `pack rd, rs1, zero` of the register-register
+    /// opcode, whose 32-bit variant is used in RV64.
+    pub fn zext_h(xlen: Xlen, rs1: Register, rd: Register) -> PackedInstruction {
+        let opcode = match xlen {
+            Xlen::Rv32 => ROpcode::ALUReg,
+            Xlen::Rv64 => ROpcode::ALURegW,
+        };
+        Self::unary(opcode, 0x080, 0b100, rs1, rd)
+    }
+}
@@ -0,0 +1,74 @@
+use strum::{EnumString, IntoStaticStr, VariantArray};
+
+/// Instructions of the bit-manipulation extensions Zba, Zbb and Zbs.
+///
+/// Register instructions take `[rd, rs1, rs2]`, the unary ones `[rd, rs1]`
+/// and the ones with a shift amount or bit index `[rd, rs1, imm]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RVBInstruction {
+    AddUw,
+    Andn,
+    Bclr,
+    Bclri,
+    Bext,
+    Bexti,
+    Binv,
+    Binvi,
+    Bset,
+    Bseti,
+    Clz,
+    Clzw,
+    Cpop,
+    Cpopw,
+    Ctz,
+    Ctzw,
+    Max,
+    Maxu,
+    Min,
+    Minu,
+    OrcB,
+    Orn,
+    Rev8,
+    Rol,
+    Rolw,
+    Ror,
+    Rori,
+    Roriw,
+    Rorw,
+    SextB,
+    SextH,
+    Sh1add,
+    Sh1addUw,
+    Sh2add,
+    Sh2addUw,
+    Sh3add,
+    Sh3addUw,
+    SlliUw,
+    Xnor,
+    ZextH,
+}
+
+impl RVBInstruction {
+    pub fn name(&self) -> String {
+        Into::<&'static str>::into(self).replace("_", ".")
+    }
+
+    /// Instructions that only exist when registers are 64 bits wide.
+    pub fn rv64_only(&self) -> bool {
+        matches!(
+            self,
+            RVBInstruction::AddUw
+                | RVBInstruction::Clzw
+                | RVBInstruction::Cpopw
+                | RVBInstruction::Ctzw
+                | RVBInstruction::Rolw
+                | RVBInstruction::Roriw
+                | RVBInstruction::Rorw
+                | RVBInstruction::Sh1addUw
+                | RVBInstruction::Sh2addUw
+                | RVBInstruction::Sh3addUw
+                | RVBInstruction::SlliUw
+        )
+    }
+}
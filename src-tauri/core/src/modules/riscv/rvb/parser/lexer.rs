@@ -0,0 +1,67 @@
+use strum_macros::{IntoStaticStr, VariantArray};
+
+use super::{super::super::basic::parser::lexer::RISCVOpToken, oplist::OP_LIST};
+
+/// Zba, Zbb and Zbs operators, `sext.*` and `zext.*` replace the RV32I
+/// pseudo instructions of the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, VariantArray, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum RVBOpToken {
+    AddUw,
+    Andn,
+    Bclr,
+    Bclri,
+    Bext,
+    Bexti,
+    Binv,
+    Binvi,
+    Bset,
+    Bseti,
+    Clz,
+    Clzw,
+    Cpop,
+    Cpopw,
+    Ctz,
+    Ctzw,
+    Max,
+    Maxu,
+    Min,
+    Minu,
+    OrcB,
+    Orn,
+    Rev8,
+    Rol,
+    Rolw,
+    Ror,
+    Rori,
+    Roriw,
+    Rorw,
+    SextB,
+    SextH,
+    Sh1add,
+    Sh1addUw,
+    Sh2add,
+    Sh2addUw,
+    Sh3add,
+    Sh3addUw,
+    SlliUw,
+    Xnor,
+    ZextB,
+    ZextH,
+    ZextW,
+}
+
+impl RVBOpToken {
+    pub fn name(&self) -> String {
+        Into::<&'static str>::into(self).replace("_", ".")
+    }
+}
+
+impl From<RVBOpToken> for RISCVOpToken {
+    fn from(op: RVBOpToken) -> RISCVOpToken {
+        RISCVOpToken {
+            val: op as u8,
+            get_opd_set_fn: |v| &OP_LIST[v as usize],
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub(in super::super) mod lexer;
+pub(in super::super) mod oplist;
+pub(in super::super) mod parser;
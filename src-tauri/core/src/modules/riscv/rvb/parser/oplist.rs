@@ -0,0 +1,364 @@
+use once_cell::sync::Lazy;
+use RV32IRegister::*;
+
+pub use super::super::super::basic::parser::oplist::RISCVOpdSet;
+use super::{
+    super::{
+        super::{
+            basic::parser::oplist::*,
+            rv32i::constants::{RV32IInstruction, RV32IRegister},
+        },
+        constants::RVBInstruction,
+    },
+    lexer::RVBOpToken,
+};
+use crate::utility::enum_map::build_map_mut_data;
+
+pub static OP_LIST: Lazy<Vec<Vec<RISCVOpdSet>>> = Lazy::new(|| {
+    let mut op_def = [
+        (
+            RVBOpToken::AddUw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::AddUw.into())],
+                "add.uw t1, t2, t3 (t1 = (u32)t2 + t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Andn,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Andn.into())],
+                "andn t1, t2, t3 (t1 = t2 & ~t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Bclr,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Bclr.into())],
+                "bclr t1, t2, t3 (t1 = t2 with bit t3 cleared)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Bclri,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RVBInstruction::Bclri.into())],
+                "bclri t1, t2, 0x1 (t1 = t2 with bit 0x1(u6) cleared)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Bext,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Bext.into())],
+                "bext t1, t2, t3 (t1 = bit t3 of t2)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Bexti,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RVBInstruction::Bexti.into())],
+                "bexti t1, t2, 0x1 (t1 = bit 0x1(u6) of t2)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Binv,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Binv.into())],
+                "binv t1, t2, t3 (t1 = t2 with bit t3 inverted)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Binvi,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RVBInstruction::Binvi.into())],
+                "binvi t1, t2, 0x1 (t1 = t2 with bit 0x1(u6) inverted)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Bset,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Bset.into())],
+                "bset t1, t2, t3 (t1 = t2 with bit t3 set)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Bseti,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RVBInstruction::Bseti.into())],
+                "bseti t1, t2, 0x1 (t1 = t2 with bit 0x1(u6) set)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Clz,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::Clz.into())],
+                "clz t1, t2 (t1 = count of leading zero bits of t2)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Clzw,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::Clzw.into())],
+                "clzw t1, t2 (t1 = count of leading zero bits of t2[0:31])".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Cpop,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::Cpop.into())],
+                "cpop t1, t2 (t1 = count of set bits of t2)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Cpopw,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::Cpopw.into())],
+                "cpopw t1, t2 (t1 = count of set bits of t2[0:31])".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Ctz,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::Ctz.into())],
+                "ctz t1, t2 (t1 = count of trailing zero bits of t2)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Ctzw,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::Ctzw.into())],
+                "ctzw t1, t2 (t1 = count of trailing zero bits of t2[0:31])".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Max,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Max.into())],
+                "max t1, t2, t3 (t1 = max(t2, t3)) (signed)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Maxu,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Maxu.into())],
+                "maxu t1, t2, t3 (t1 = max(t2, t3)) (unsigned)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Min,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Min.into())],
+                "min t1, t2, t3 (t1 = min(t2, t3)) (signed)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Minu,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Minu.into())],
+                "minu t1, t2, t3 (t1 = min(t2, t3)) (unsigned)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::OrcB,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::OrcB.into())],
+                "orc.b t1, t2 (every byte of t1 = 0xff if the byte of t2 is not 0 else 0)"
+                    .to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Orn,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Orn.into())],
+                "orn t1, t2, t3 (t1 = t2 | ~t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Rev8,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::Rev8.into())],
+                "rev8 t1, t2 (t1 = t2 with its bytes reversed)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Rol,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Rol.into())],
+                "rol t1, t2, t3 (t1 = t2 rotated left by t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Rolw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Rolw.into())],
+                "rolw t1, t2, t3 (t1 = (i32)(t2[0:31] rotated left by t3))".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Ror,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Ror.into())],
+                "ror t1, t2, t3 (t1 = t2 rotated right by t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Rori,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RVBInstruction::Rori.into())],
+                "rori t1, t2, 0x1 (t1 = t2 rotated right by 0x1(u6))".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Roriw,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U5)),
+                vec![basic_op_024(RVBInstruction::Roriw.into())],
+                "roriw t1, t2, 0x1 (t1 = (i32)(t2[0:31] rotated right by 0x1(u5)))".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Rorw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Rorw.into())],
+                "rorw t1, t2, t3 (t1 = (i32)(t2[0:31] rotated right by t3))".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::SextB,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::SextB.into())],
+                "sext.b t1, t2 (t1 = (i8)t2[0:7])".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::SextH,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::SextH.into())],
+                "sext.h t1, t2 (t1 = (i16)t2[0:15])".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Sh1add,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Sh1add.into())],
+                "sh1add t1, t2, t3 (t1 = (t2 << 1) + t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Sh1addUw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Sh1addUw.into())],
+                "sh1add.uw t1, t2, t3 (t1 = ((u32)t2 << 1) + t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Sh2add,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Sh2add.into())],
+                "sh2add t1, t2, t3 (t1 = (t2 << 2) + t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Sh2addUw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Sh2addUw.into())],
+                "sh2add.uw t1, t2, t3 (t1 = ((u32)t2 << 2) + t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Sh3add,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Sh3add.into())],
+                "sh3add t1, t2, t3 (t1 = (t2 << 3) + t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Sh3addUw,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Sh3addUw.into())],
+                "sh3add.uw t1, t2, t3 (t1 = ((u32)t2 << 3) + t3)".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::SlliUw,
+            vec![opd_set(
+                expect_reg_reg_any(Imm(U6)),
+                vec![basic_op_024(RVBInstruction::SlliUw.into())],
+                "slli.uw t1, t2, 0x1 (t1 = (u32)t2 << 0x1(u6))".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::Xnor,
+            vec![opd_set(
+                expect_reg_reg_reg(),
+                vec![basic_op_024(RVBInstruction::Xnor.into())],
+                "xnor t1, t2, t3 (t1 = ~(t2 ^ t3))".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::ZextB,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op(
+                    RV32IInstruction::Andi.into(),
+                    vec![idx(0), idx(2), imm(0xff)],
+                )],
+                "zext.b t1, t2 (t1 = (u8)t2[0:7])".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::ZextH,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op_02(RVBInstruction::ZextH.into())],
+                "zext.h t1, t2 (t1 = (u16)t2[0:15])".to_string(),
+            )],
+        ),
+        (
+            RVBOpToken::ZextW,
+            vec![opd_set(
+                expect_reg_reg(),
+                vec![basic_op(
+                    RVBInstruction::AddUw.into(),
+                    vec![idx(0), idx(2), reg(Zero)],
+                )],
+                "zext.w t1, t2 (t1 = (u32)t2[0:31])".to_string(),
+            )],
+        ),
+    ];
+    build_map_mut_data(&mut op_def, |def| (def.0, std::mem::take(&mut def.1)))
+});
@@ -0,0 +1,207 @@
+use strum::VariantArray;
+
+use super::pipeline::{Control, Deps};
+use crate::{
+    interface::assembler::Operand,
+    modules::riscv::basic::interface::parser::{RV32IRegister, RISCV},
+    types::middleware_types::{AbiViolation, AbiWarning},
+};
+
+const RA: Operand<RISCV> = RV32IRegister::Ra as Operand<RISCV>;
+const SP: usize = RV32IRegister::Sp as usize;
+/// `s0`-`s11`
+const CALLEE_SAVED: [usize; 12] = [8, 9, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27];
+/// `t0`-`t6`
+const TEMPORARIES: u32 = 0b111 << 5 | 0b1111 << 28;
+
+#[derive(Clone)]
+struct Frame {
+    // line of the call
+    line: u64,
+    sp: u64,
+    saved: [u64; 12],
+}
+
+#[derive(Clone, Copy)]
+struct Temporaries {
+    // temporaries not written since `line`
+    uninit: u32,
+    line: u64,
+    // `line` is a call that returned, not the one that was entered
+    returned: bool,
+}
+
+struct HartFrames {
+    frames: Vec<Frame>,
+    temps: Temporaries,
+}
+
+/// What a step changed, undo puts it back.
+pub(super) struct AbiUndo {
+    hart: usize,
+    temps: Temporaries,
+    frames: usize,
+    popped: Option<Frame>,
+    warnings: usize,
+}
+
+/// Tracks the calls and returns of every hart, a call is a `jal` or `jalr`
+/// linking `ra` and a return is a `jalr` through `ra` discarding the link.
+pub(super) struct AbiChecker {
+    harts: Vec<HartFrames>,
+    warnings: Vec<AbiWarning>,
+}
+
+impl AbiChecker {
+    pub fn new(harts: usize) -> Self {
+        AbiChecker {
+            harts: (0..harts)
+                .map(|_| HartFrames {
+                    frames: Vec::new(),
+                    // the program starts with every register defined
+                    temps: Temporaries {
+                        uninit: 0,
+                        line: 0,
+                        returned: false,
+                    },
+                })
+                .collect(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Checks the instruction at `line` about to execute on `hart`.
+    pub fn before(&mut self, hart: usize, line: u64, deps: &Deps, reg: &[u64; 32]) -> AbiUndo {
+        let state = &mut self.harts[hart];
+        let undo = AbiUndo {
+            hart,
+            temps: state.temps,
+            frames: state.frames.len(),
+            popped: None,
+            warnings: self.warnings.len(),
+        };
+        for rs in deps.rs {
+            if rs == 0 || state.temps.uninit & (1 << rs) == 0 {
+                continue;
+            }
+            // once is enough, loops would repeat it
+            state.temps.uninit &= !(1 << rs);
+            let name = RV32IRegister::VARIANTS[rs as usize];
+            let message = if state.temps.returned {
+                format!(
+                    "{} is read after the call at line {} without being written again",
+                    name,
+                    state.temps.line + 1
+                )
+            } else {
+                format!(
+                    "{} is read before being written in the function called at line {}",
+                    name,
+                    state.temps.line + 1
+                )
+            };
+            self.warnings.push(AbiWarning {
+                hart: hart as u64,
+                line,
+                kind: AbiViolation::UninitializedTemporary,
+                message,
+            });
+        }
+        if is_call(deps) && reg[SP] % 16 != 0 {
+            self.warnings.push(AbiWarning {
+                hart: hart as u64,
+                line,
+                kind: AbiViolation::MisalignedStack,
+                message: format!("sp = 0x{:x} isn't 16-byte aligned at a call", reg[SP]),
+            });
+        }
+        undo
+    }
+
+    /// Records the instruction checked by `before` after it retired.
+    pub fn retire(&mut self, undo: &mut AbiUndo, line: u64, deps: &Deps, reg: &[u64; 32]) {
+        let hart = undo.hart;
+        let state = &mut self.harts[hart];
+        if deps.rd != 0 {
+            state.temps.uninit &= !(1 << deps.rd);
+        }
+        if is_call(deps) {
+            state.frames.push(Frame {
+                line,
+                sp: reg[SP],
+                saved: CALLEE_SAVED.map(|idx| reg[idx]),
+            });
+            state.temps = Temporaries {
+                uninit: TEMPORARIES,
+                line,
+                returned: false,
+            };
+        } else if is_return(deps) {
+            // returning from the entry point isn't a call boundary
+            let frame = match state.frames.pop() {
+                Some(frame) => frame,
+                None => return,
+            };
+            let mut warn = |kind, message| {
+                self.warnings.push(AbiWarning {
+                    hart: hart as u64,
+                    line,
+                    kind,
+                    message,
+                })
+            };
+            if reg[SP] != frame.sp {
+                warn(
+                    AbiViolation::StackPointer,
+                    format!(
+                        "sp is 0x{:x} on return from the call at line {}, it was 0x{:x}",
+                        reg[SP],
+                        frame.line + 1,
+                        frame.sp
+                    ),
+                );
+            }
+            for (idx, saved) in CALLEE_SAVED.into_iter().zip(frame.saved) {
+                if reg[idx] != saved {
+                    warn(
+                        AbiViolation::CalleeSaved,
+                        format!(
+                            "{} is 0x{:x} on return from the call at line {}, it was 0x{:x}",
+                            RV32IRegister::VARIANTS[idx],
+                            reg[idx],
+                            frame.line + 1,
+                            saved
+                        ),
+                    );
+                }
+            }
+            // the callee may have overwritten every temporary
+            state.temps = Temporaries {
+                uninit: TEMPORARIES,
+                line: frame.line,
+                returned: true,
+            };
+            undo.popped = Some(frame);
+        }
+    }
+
+    pub fn undo(&mut self, undo: AbiUndo) {
+        let state = &mut self.harts[undo.hart];
+        state.temps = undo.temps;
+        state.frames.truncate(undo.frames);
+        state.frames.extend(undo.popped);
+        self.warnings.truncate(undo.warnings);
+    }
+
+    pub fn warnings(&self) -> Vec<AbiWarning> {
+        self.warnings.clone()
+    }
+}
+
+fn is_call(deps: &Deps) -> bool {
+    matches!(deps.control, Control::Jal | Control::Jalr) && deps.rd == RA
+}
+
+fn is_return(deps: &Deps) -> bool {
+    deps.control == Control::Jalr && deps.rd == 0 && deps.rs[0] == RA
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use crate::types::middleware_types::{
+    BranchPredictorConfig,
+    BranchPredictorData,
+    BranchPredictorKind,
+    BranchSiteData,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum BranchKind {
+    Conditional,
+    Jal,
+    Jalr,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Site {
+    address: u32,
+    predictions: u64,
+    mispredictions: u64,
+}
+
+/// Predicts the instruction fetched after every control transfer. A
+/// prediction is correct when it fetches the right target, so a taken
+/// prediction missing in the BTB falls through.
+pub(super) struct BranchPredictor {
+    conf: BranchPredictorConfig,
+    counters: Vec<u8>,
+    history: u64,
+    // (pc, target), direct mapped
+    btb: Vec<Option<(u32, u32)>>,
+    btb_hits: u64,
+    btb_misses: u64,
+    // by instruction index
+    sites: BTreeMap<usize, Site>,
+}
+
+impl BranchPredictor {
+    pub fn valid_config(conf: &BranchPredictorConfig) -> bool {
+        (1..=16).contains(&conf.table_bits)
+            && conf.history_bits <= conf.table_bits
+            && (conf.btb_entries == 0 || conf.btb_entries.is_power_of_two())
+            && conf.btb_entries <= 1 << 16
+    }
+
+    /// `None` if `conf` is disabled.
+    pub fn new(conf: &BranchPredictorConfig) -> Option<Self> {
+        if !conf.enabled {
+            return None;
+        }
+        // 1-bit counters start not taken, 2-bit ones weakly not taken
+        let init = match conf.kind {
+            BranchPredictorKind::TwoBit | BranchPredictorKind::Gshare => 1,
+            _ => 0,
+        };
+        Some(BranchPredictor {
+            conf: conf.clone(),
+            counters: vec![init; 1 << conf.table_bits],
+            history: 0,
+            btb: vec![None; conf.btb_entries as usize],
+            btb_hits: 0,
+            btb_misses: 0,
+            sites: BTreeMap::new(),
+        })
+    }
+
+    /// Records the outcome of the control transfer at `pc`, `target` is only
+    /// relevant when taken.
+    pub fn update(&mut self, idx: usize, pc: u32, kind: BranchKind, taken: bool, target: u32) {
+        let counter = self.counter_idx(pc);
+        let predict_taken = match (kind, self.conf.kind) {
+            (BranchKind::Jal | BranchKind::Jalr, _) => true,
+            (_, BranchPredictorKind::StaticTaken) => true,
+            (_, BranchPredictorKind::StaticNotTaken) => false,
+            (_, BranchPredictorKind::OneBit) => self.counters[counter] == 1,
+            (_, BranchPredictorKind::TwoBit | BranchPredictorKind::Gshare) => {
+                self.counters[counter] >= 2
+            }
+        };
+        let predicted = match (predict_taken, self.btb.len()) {
+            (false, _) => None,
+            (true, 0) if kind == BranchKind::Jalr => None,
+            (true, 0) => Some(target),
+            (true, len) => {
+                let entry = self.btb[(pc as usize >> 1) % len];
+                match entry {
+                    Some((tag, target)) if tag == pc => {
+                        self.btb_hits += 1;
+                        Some(target)
+                    }
+                    _ => {
+                        self.btb_misses += 1;
+                        None
+                    }
+                }
+            }
+        };
+        let site = self.sites.entry(idx).or_insert(Site {
+            address: pc,
+            ..Default::default()
+        });
+        site.predictions += 1;
+        if predicted != taken.then(|| target) {
+            site.mispredictions += 1;
+        }
+
+        if kind == BranchKind::Conditional {
+            let state = &mut self.counters[counter];
+            *state = match self.conf.kind {
+                BranchPredictorKind::OneBit => taken as u8,
+                _ if taken => (*state + 1).min(3),
+                _ => state.saturating_sub(1),
+            };
+            self.history = (self.history << 1 | taken as u64) & ((1 << self.conf.history_bits) - 1);
+        }
+        if taken && !self.btb.is_empty() {
+            let len = self.btb.len();
+            self.btb[(pc as usize >> 1) % len] = Some((pc, target));
+        }
+    }
+
+    fn counter_idx(&self, pc: u32) -> usize {
+        let mut idx = (pc >> 1) as u64;
+        if self.conf.kind == BranchPredictorKind::Gshare {
+            idx ^= self.history;
+        }
+        (idx & ((1 << self.conf.table_bits) - 1)) as usize
+    }
+
+    /// `line` maps an instruction index to its source line.
+    pub fn data(&self, line: impl Fn(usize) -> u64) -> BranchPredictorData {
+        let accuracy = |predictions: u64, mispredictions: u64| {
+            if predictions == 0 {
+                0.0
+            } else {
+                (predictions - mispredictions) as f64 / predictions as f64
+            }
+        };
+        let sites: Vec<BranchSiteData> = self
+            .sites
+            .iter()
+            .map(|(&idx, site)| BranchSiteData {
+                line: line(idx),
+                address: site.address,
+                predictions: site.predictions,
+                mispredictions: site.mispredictions,
+                accuracy: accuracy(site.predictions, site.mispredictions),
+            })
+            .collect();
+        let predictions = sites.iter().map(|site| site.predictions).sum();
+        let mispredictions = sites.iter().map(|site| site.mispredictions).sum();
+        BranchPredictorData {
+            predictions,
+            mispredictions,
+            accuracy: accuracy(predictions, mispredictions),
+            btb_hits: self.btb_hits,
+            btb_misses: self.btb_misses,
+            sites,
+        }
+    }
+}
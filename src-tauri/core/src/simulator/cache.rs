@@ -0,0 +1,152 @@
+use super::simulator::next_random;
+use crate::types::middleware_types::{
+    CacheAccess,
+    CacheConfig,
+    CacheData,
+    CacheLine,
+    CacheReplacement,
+    CacheWritePolicy,
+};
+
+#[derive(Clone, Copy, Default)]
+struct Line {
+    valid: bool,
+    dirty: bool,
+    tag: u32,
+    // last use for LRU, fill time for FIFO
+    stamp: u64,
+}
+
+/// Set associative cache model, it only tracks tags so the memory stays
+/// authoritative. Statistics are not rewound by undo.
+pub(super) struct Cache {
+    conf: CacheConfig,
+    sets: u32,
+    // `associativity` lines per set, set by set
+    lines: Vec<Line>,
+    clock: u64,
+    rng: u64,
+    hits: u64,
+    misses: u64,
+    writebacks: u64,
+    last_access: Option<CacheAccess>,
+}
+
+impl Cache {
+    pub fn valid_config(conf: &CacheConfig) -> bool {
+        // the bounds come first, the config isn't trusted
+        conf.size.is_power_of_two()
+            && conf.size <= 1 << 24
+            && conf.block_size.is_power_of_two()
+            && conf.block_size <= conf.size
+            && conf.associativity > 0
+            && conf.associativity <= conf.size
+            && conf
+                .block_size
+                .checked_mul(conf.associativity)
+                .map_or(false, |set| set != 0 && conf.size % set == 0)
+    }
+
+    /// `None` if `conf` is disabled or invalid.
+    pub fn new(conf: &CacheConfig) -> Option<Self> {
+        if !conf.enabled || !Self::valid_config(conf) {
+            return None;
+        }
+        let sets = conf.size / (conf.block_size * conf.associativity);
+        Some(Cache {
+            conf: conf.clone(),
+            sets: sets as u32,
+            lines: vec![Line::default(); (sets * conf.associativity) as usize],
+            clock: 0,
+            rng: 0,
+            hits: 0,
+            misses: 0,
+            writebacks: 0,
+            last_access: None,
+        })
+    }
+
+    /// Looks up every block overlapping `[addr, addr + len)`.
+    pub fn access(&mut self, addr: u32, len: u32, write: bool) {
+        let block_size = self.conf.block_size;
+        let first = addr as u64 / block_size;
+        let last = (addr as u64 + len.max(1) as u64 - 1) / block_size;
+        for block in first..=last {
+            let hit = self.access_block(block, write);
+            self.last_access = Some(CacheAccess {
+                address: (block * block_size).max(addr as u64) as u32,
+                write,
+                hit,
+            });
+        }
+    }
+
+    fn access_block(&mut self, block: u64, write: bool) -> bool {
+        self.clock += 1;
+        let ways = self.conf.associativity as usize;
+        let set = (block % self.sets as u64) as usize;
+        let tag = (block / self.sets as u64) as u32;
+        let write_back = self.conf.write_policy == CacheWritePolicy::WriteBack;
+        let lines = &mut self.lines[set * ways..(set + 1) * ways];
+        if let Some(line) = lines.iter_mut().find(|line| line.valid && line.tag == tag) {
+            self.hits += 1;
+            if self.conf.replacement == CacheReplacement::Lru {
+                line.stamp = self.clock;
+            }
+            line.dirty |= write && write_back;
+            return true;
+        }
+        self.misses += 1;
+        if write && !write_back {
+            return false;
+        }
+        let victim = match lines.iter().position(|line| !line.valid) {
+            Some(way) => way,
+            None => match self.conf.replacement {
+                CacheReplacement::Lru | CacheReplacement::Fifo => {
+                    (0..ways).min_by_key(|&way| lines[way].stamp).unwrap()
+                }
+                CacheReplacement::Random => (next_random(&mut self.rng) % ways as u64) as usize,
+            },
+        };
+        if lines[victim].valid && lines[victim].dirty {
+            self.writebacks += 1;
+        }
+        lines[victim] = Line {
+            valid: true,
+            dirty: write,
+            tag,
+            stamp: self.clock,
+        };
+        false
+    }
+
+    pub fn data(&self) -> CacheData {
+        let ways = self.conf.associativity as usize;
+        let accesses = self.hits + self.misses;
+        CacheData {
+            accesses,
+            hits: self.hits,
+            misses: self.misses,
+            writebacks: self.writebacks,
+            hit_rate: if accesses == 0 {
+                0.0
+            } else {
+                self.hits as f64 / accesses as f64
+            },
+            last_access: self.last_access,
+            lines: self
+                .lines
+                .iter()
+                .enumerate()
+                .map(|(idx, line)| CacheLine {
+                    set: (idx / ways) as u64,
+                    way: (idx % ways) as u64,
+                    valid: line.valid,
+                    dirty: line.dirty,
+                    tag: line.tag,
+                })
+                .collect(),
+        }
+    }
+}
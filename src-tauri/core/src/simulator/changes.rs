@@ -0,0 +1,79 @@
+/// Dirty ranges kept before the tracker gives up and asks for a resync.
+const MAX_RANGES: usize = 256;
+
+/// Registers and memory written since the last emit, shared between the
+/// simulator thread and the host.
+pub(super) struct ChangeTracker {
+    // sequence number of the last emit
+    seq: u64,
+    // bit `n` of hart `h` is set if register `n` was written
+    regs: Vec<u32>,
+    // sorted and disjoint [start, end) byte ranges
+    mem: Vec<(u64, u64)>,
+    // the changes are unknown, e.g. after a reset
+    lost: bool,
+}
+
+/// Changes taken from the tracker.
+pub(super) struct Changes {
+    pub seq: u64,
+    pub regs: Vec<u32>,
+    pub mem: Vec<(u64, u64)>,
+    pub lost: bool,
+}
+
+impl ChangeTracker {
+    pub fn new() -> Self {
+        ChangeTracker {
+            seq: 0,
+            regs: Vec::new(),
+            mem: Vec::new(),
+            lost: true,
+        }
+    }
+
+    pub fn reg(&mut self, hart: usize, idx: usize) {
+        if self.lost {
+            return;
+        }
+        if hart >= self.regs.len() {
+            self.regs.resize(hart + 1, 0);
+        }
+        self.regs[hart] |= 1 << idx;
+    }
+
+    pub fn mem(&mut self, addr: u32, len: u32) {
+        if self.lost || len == 0 {
+            return;
+        }
+        let (mut start, mut end) = (addr as u64, addr as u64 + len as u64);
+        // merge every overlapping or adjacent range
+        let first = self.mem.partition_point(|&(_, e)| e < start);
+        let last = self.mem.partition_point(|&(s, _)| s <= end);
+        if first < last {
+            start = start.min(self.mem[first].0);
+            end = end.max(self.mem[last - 1].1);
+        }
+        self.mem.splice(first..last, [(start, end)]);
+        if self.mem.len() > MAX_RANGES {
+            self.invalidate();
+        }
+    }
+
+    pub fn invalidate(&mut self) {
+        self.lost = true;
+        self.regs.clear();
+        self.mem.clear();
+    }
+
+    /// Changes since the last emit, the next emit follows them.
+    pub fn take(&mut self) -> Changes {
+        self.seq += 1;
+        Changes {
+            seq: self.seq,
+            regs: std::mem::take(&mut self.regs),
+            mem: std::mem::take(&mut self.mem),
+            lost: std::mem::replace(&mut self.lost, false),
+        }
+    }
+}
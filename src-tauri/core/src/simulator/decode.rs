@@ -0,0 +1,639 @@
+use std::collections::HashMap;
+
+use crate::{
+    interface::assembler::{Instruction, InstructionSet},
+    modules::riscv::basic::{assembler::assembler::format_instruction, interface::parser::*},
+    types::middleware_types::Xlen,
+};
+
+/// Where the assembler puts the fields of an instruction in its operands.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    // rd, rs1, rs2
+    R,
+    // rd, rs1
+    R2,
+    // rd, rs1, imm
+    I,
+    // rd, imm, rs1
+    Load,
+    // rs2, imm, rs1
+    Store,
+    // rs1, rs2, imm
+    B,
+    // rd, imm
+    U,
+    // rd, csr, rs1 or uimm
+    Csr,
+    // rd, rs1, rs2, aqrl
+    Amo,
+    // pred, succ
+    Fence,
+}
+
+#[derive(Default)]
+struct Fields {
+    rd: u32,
+    rs1: u32,
+    rs2: u32,
+    imm: RISCVImmediate,
+}
+
+/// Compressed instructions take 2 bytes, all others 4 bytes.
+pub(super) fn size(op: ParserRISCVInstOp) -> u32 {
+    match op {
+        ParserRISCVInstOp::RV32C(_) => 2,
+        _ => 4,
+    }
+}
+
+/// Bits `hi..=lo` of `value`, shifted down to bit 0.
+fn bits(value: u32, hi: u32, lo: u32) -> u32 {
+    (value >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+/// `value` sign extended from `width` bits.
+fn sext(value: u32, width: u32) -> RISCVImmediate {
+    ((value << (32 - width)) as i32 >> (32 - width)) as RISCVImmediate
+}
+
+fn layout(op: ParserRISCVInstOp) -> Layout {
+    use RV32IInstruction::*;
+
+    match op {
+        ParserRISCVInstOp::RV32I(op) => match op {
+            Add | And | Or | Sll | Slt | Sltu | Sra | Srl | Sub | Xor => Layout::R,
+            Addi | Andi | Ori | Slti | Sltiu | Xori | Slli | Srai | Srli | Jalr | Ebreak
+            | Ecall | FenceI => Layout::I,
+            Lb | Lbu | Lh | Lhu | Lw => Layout::Load,
+            Sb | Sh | Sw => Layout::Store,
+            Beq | Bge | Bgeu | Blt | Bltu | Bne => Layout::B,
+            Auipc | Lui | Jal => Layout::U,
+            Csrrc | Csrrci | Csrrs | Csrrsi | Csrrw | Csrrwi => Layout::Csr,
+            Fence => Layout::Fence,
+        },
+        ParserRISCVInstOp::RV64I(op) => match op {
+            RV64IInstruction::Ld | RV64IInstruction::Lwu => Layout::Load,
+            RV64IInstruction::Sd => Layout::Store,
+            RV64IInstruction::Addiw
+            | RV64IInstruction::Slliw
+            | RV64IInstruction::Sraiw
+            | RV64IInstruction::Srliw => Layout::I,
+            _ => Layout::R,
+        },
+        ParserRISCVInstOp::RVB(op) => match op {
+            RVBInstruction::Clz
+            | RVBInstruction::Clzw
+            | RVBInstruction::Cpop
+            | RVBInstruction::Cpopw
+            | RVBInstruction::Ctz
+            | RVBInstruction::Ctzw
+            | RVBInstruction::OrcB
+            | RVBInstruction::Rev8
+            | RVBInstruction::SextB
+            | RVBInstruction::SextH
+            | RVBInstruction::ZextH => Layout::R2,
+            RVBInstruction::Bclri
+            | RVBInstruction::Bexti
+            | RVBInstruction::Binvi
+            | RVBInstruction::Bseti
+            | RVBInstruction::Rori
+            | RVBInstruction::Roriw
+            | RVBInstruction::SlliUw => Layout::I,
+            _ => Layout::R,
+        },
+        ParserRISCVInstOp::RV32A(_) => Layout::Amo,
+        ParserRISCVInstOp::RV32C(op) => layout(op.base().into()),
+        // never decoded, float registers aren't simulated
+        ParserRISCVInstOp::RV32F(_) => Layout::R,
+    }
+}
+
+/// Decodes the instruction at the start of `code`, the low 16 bits tell
+/// whether it is compressed. The operands are laid out as by the assembler,
+/// `None` if the encoding isn't one of a supported instruction.
+pub(super) fn decode(code: u32, xlen: Xlen) -> Option<InstructionSet<RISCV>> {
+    let (op, fields, code) = if code & 0b11 == 0b11 {
+        let (op, fields) = decode_base(code, xlen)?;
+        (op, fields, code)
+    } else {
+        let code = code & 0xffff;
+        let (op, fields) = decode_compressed(code, xlen)?;
+        (ParserRISCVInstOp::from(op), fields, code)
+    };
+    let Fields { rd, rs1, rs2, imm } = fields;
+    let layout = layout(op);
+    let operands = match layout {
+        Layout::R => vec![
+            rd as RISCVImmediate,
+            rs1 as RISCVImmediate,
+            rs2 as RISCVImmediate,
+        ],
+        Layout::R2 => vec![rd as RISCVImmediate, rs1 as RISCVImmediate],
+        Layout::I => vec![rd as RISCVImmediate, rs1 as RISCVImmediate, imm],
+        Layout::Load => vec![rd as RISCVImmediate, imm, rs1 as RISCVImmediate],
+        Layout::Store => vec![rs2 as RISCVImmediate, imm, rs1 as RISCVImmediate],
+        Layout::B => vec![rs1 as RISCVImmediate, rs2 as RISCVImmediate, imm],
+        Layout::U => vec![rd as RISCVImmediate, imm],
+        Layout::Csr => vec![rd as RISCVImmediate, imm, rs1 as RISCVImmediate],
+        Layout::Amo => vec![
+            rd as RISCVImmediate,
+            rs1 as RISCVImmediate,
+            rs2 as RISCVImmediate,
+            imm,
+        ],
+        Layout::Fence => vec![
+            bits(code, 27, 24) as RISCVImmediate,
+            bits(code, 23, 20) as RISCVImmediate,
+        ],
+    };
+    // the basic form of `lw` names the loaded register rs2, like a store
+    let rs2 = if layout == Layout::Load { rd } else { rs2 };
+    let (pred, succ) = match layout {
+        Layout::Fence => (operands[0], operands[1]),
+        _ => (0, 0),
+    };
+    let mut line = InstructionSet::new(Instruction {
+        operation: op,
+        operands,
+    });
+    line.code = code;
+    line.basic = format_instruction(op, rd, rs1, rs2, imm, pred, succ);
+    Some(line)
+}
+
+fn decode_base(code: u32, xlen: Xlen) -> Option<(ParserRISCVInstOp, Fields)> {
+    use RV32IInstruction::*;
+
+    // 48-bit and longer encodings
+    if code & 0b11100 == 0b11100 {
+        return None;
+    }
+    let rd = bits(code, 11, 7);
+    let rs1 = bits(code, 19, 15);
+    let rs2 = bits(code, 24, 20);
+    let funct3 = bits(code, 14, 12);
+    let funct7 = bits(code, 31, 25);
+    let funct6 = bits(code, 31, 26);
+    let funct12 = bits(code, 31, 20);
+    // the shift amount takes the lowest bit of funct7 in RV64
+    let shamt = match xlen {
+        Xlen::Rv32 if bits(code, 25, 25) == 1 => None,
+        _ => Some(bits(code, 25, 20) as RISCVImmediate),
+    };
+    let r = |op: ParserRISCVInstOp| {
+        Some((
+            op,
+            Fields {
+                rd,
+                rs1,
+                rs2,
+                imm: 0,
+            },
+        ))
+    };
+    let i = |op: ParserRISCVInstOp, imm| {
+        Some((
+            op,
+            Fields {
+                rd,
+                rs1,
+                rs2: 0,
+                imm,
+            },
+        ))
+    };
+    let i_imm = sext(code >> 20, 12);
+    match code & 0x7f {
+        // register-register
+        0b0110011 => r(match (funct7, funct3) {
+            (0b0000000, 0b000) => Add.into(),
+            (0b0000000, 0b001) => Sll.into(),
+            (0b0000000, 0b010) => Slt.into(),
+            (0b0000000, 0b011) => Sltu.into(),
+            (0b0000000, 0b100) => Xor.into(),
+            (0b0000000, 0b101) => Srl.into(),
+            (0b0000000, 0b110) => Or.into(),
+            (0b0000000, 0b111) => And.into(),
+            (0b0100000, 0b000) => Sub.into(),
+            (0b0100000, 0b101) => Sra.into(),
+            (0b0100000, 0b100) => RVBInstruction::Xnor.into(),
+            (0b0100000, 0b110) => RVBInstruction::Orn.into(),
+            (0b0100000, 0b111) => RVBInstruction::Andn.into(),
+            (0b0100100, 0b001) => RVBInstruction::Bclr.into(),
+            (0b0100100, 0b101) => RVBInstruction::Bext.into(),
+            (0b0110100, 0b001) => RVBInstruction::Binv.into(),
+            (0b0010100, 0b001) => RVBInstruction::Bset.into(),
+            (0b0000101, 0b100) => RVBInstruction::Min.into(),
+            (0b0000101, 0b101) => RVBInstruction::Minu.into(),
+            (0b0000101, 0b110) => RVBInstruction::Max.into(),
+            (0b0000101, 0b111) => RVBInstruction::Maxu.into(),
+            (0b0110000, 0b001) => RVBInstruction::Rol.into(),
+            (0b0110000, 0b101) => RVBInstruction::Ror.into(),
+            (0b0010000, 0b010) => RVBInstruction::Sh1add.into(),
+            (0b0010000, 0b100) => RVBInstruction::Sh2add.into(),
+            (0b0010000, 0b110) => RVBInstruction::Sh3add.into(),
+            (0b0000100, 0b100) if rs2 == 0 && xlen == Xlen::Rv32 => RVBInstruction::ZextH.into(),
+            _ => return None,
+        }),
+        0b0111011 => r(match (funct7, funct3) {
+            (0b0000000, 0b000) => RV64IInstruction::Addw.into(),
+            (0b0000000, 0b001) => RV64IInstruction::Sllw.into(),
+            (0b0000000, 0b101) => RV64IInstruction::Srlw.into(),
+            (0b0100000, 0b000) => RV64IInstruction::Subw.into(),
+            (0b0100000, 0b101) => RV64IInstruction::Sraw.into(),
+            (0b0000100, 0b000) => RVBInstruction::AddUw.into(),
+            (0b0000100, 0b100) if rs2 == 0 && xlen == Xlen::Rv64 => RVBInstruction::ZextH.into(),
+            (0b0110000, 0b001) => RVBInstruction::Rolw.into(),
+            (0b0110000, 0b101) => RVBInstruction::Rorw.into(),
+            (0b0010000, 0b010) => RVBInstruction::Sh1addUw.into(),
+            (0b0010000, 0b100) => RVBInstruction::Sh2addUw.into(),
+            (0b0010000, 0b110) => RVBInstruction::Sh3addUw.into(),
+            _ => return None,
+        }),
+        // register-immediate, the unary bit manipulations keep their function
+        // code in the immediate
+        0b0010011 => match (funct3, funct12) {
+            (0b000, _) => i(Addi.into(), i_imm),
+            (0b010, _) => i(Slti.into(), i_imm),
+            (0b011, _) => i(Sltiu.into(), i_imm),
+            (0b100, _) => i(Xori.into(), i_imm),
+            (0b110, _) => i(Ori.into(), i_imm),
+            (0b111, _) => i(Andi.into(), i_imm),
+            (0b001, 0x600) => r(RVBInstruction::Clz.into()),
+            (0b001, 0x601) => r(RVBInstruction::Ctz.into()),
+            (0b001, 0x602) => r(RVBInstruction::Cpop.into()),
+            (0b001, 0x604) => r(RVBInstruction::SextB.into()),
+            (0b001, 0x605) => r(RVBInstruction::SextH.into()),
+            (0b101, 0x287) => r(RVBInstruction::OrcB.into()),
+            (0b101, 0x698) if xlen == Xlen::Rv32 => r(RVBInstruction::Rev8.into()),
+            (0b101, 0x6b8) if xlen == Xlen::Rv64 => r(RVBInstruction::Rev8.into()),
+            (0b001, _) => i(
+                match funct6 {
+                    0b000000 => Slli.into(),
+                    0b010010 => RVBInstruction::Bclri.into(),
+                    0b011010 => RVBInstruction::Binvi.into(),
+                    0b001010 => RVBInstruction::Bseti.into(),
+                    _ => return None,
+                },
+                shamt?,
+            ),
+            (0b101, _) => i(
+                match funct6 {
+                    0b000000 => Srli.into(),
+                    0b010000 => Srai.into(),
+                    0b010010 => RVBInstruction::Bexti.into(),
+                    0b011000 => RVBInstruction::Rori.into(),
+                    _ => return None,
+                },
+                shamt?,
+            ),
+            _ => None,
+        },
+        0b0011011 => match (funct3, funct12) {
+            (0b000, _) => i(RV64IInstruction::Addiw.into(), i_imm),
+            (0b001, 0x600) => r(RVBInstruction::Clzw.into()),
+            (0b001, 0x601) => r(RVBInstruction::Ctzw.into()),
+            (0b001, 0x602) => r(RVBInstruction::Cpopw.into()),
+            (0b001, _) if funct6 == 0b000010 => i(RVBInstruction::SlliUw.into(), shamt?),
+            (0b001, _) if funct7 == 0b0000000 => {
+                i(RV64IInstruction::Slliw.into(), rs2 as RISCVImmediate)
+            }
+            (0b101, _) => i(
+                match funct7 {
+                    0b0000000 => RV64IInstruction::Srliw.into(),
+                    0b0100000 => RV64IInstruction::Sraiw.into(),
+                    0b0110000 => RVBInstruction::Roriw.into(),
+                    _ => return None,
+                },
+                rs2 as RISCVImmediate,
+            ),
+            _ => None,
+        },
+        0b0000011 => i(
+            match funct3 {
+                0b000 => Lb.into(),
+                0b001 => Lh.into(),
+                0b010 => Lw.into(),
+                0b011 => RV64IInstruction::Ld.into(),
+                0b100 => Lbu.into(),
+                0b101 => Lhu.into(),
+                0b110 => RV64IInstruction::Lwu.into(),
+                _ => return None,
+            },
+            i_imm,
+        ),
+        0b0100011 => Some((
+            match funct3 {
+                0b000 => Sb.into(),
+                0b001 => Sh.into(),
+                0b010 => Sw.into(),
+                0b011 => RV64IInstruction::Sd.into(),
+                _ => return None,
+            },
+            Fields {
+                rd: 0,
+                rs1,
+                rs2,
+                imm: sext(funct7 << 5 | rd, 12),
+            },
+        )),
+        0b1100011 => Some((
+            match funct3 {
+                0b000 => Beq.into(),
+                0b001 => Bne.into(),
+                0b100 => Blt.into(),
+                0b101 => Bge.into(),
+                0b110 => Bltu.into(),
+                0b111 => Bgeu.into(),
+                _ => return None,
+            },
+            Fields {
+                rd: 0,
+                rs1,
+                rs2,
+                imm: sext(
+                    bits(code, 31, 31) << 12
+                        | bits(code, 7, 7) << 11
+                        | bits(code, 30, 25) << 5
+                        | bits(code, 11, 8) << 1,
+                    13,
+                ),
+            },
+        )),
+        0b1101111 => i(
+            Jal.into(),
+            sext(
+                bits(code, 31, 31) << 20
+                    | bits(code, 19, 12) << 12
+                    | bits(code, 20, 20) << 11
+                    | bits(code, 30, 21) << 1,
+                21,
+            ),
+        ),
+        0b1100111 if funct3 == 0 => i(Jalr.into(), i_imm),
+        // the assembler keeps the upper immediates unshifted
+        0b0110111 => i(Lui.into(), (code >> 12) as RISCVImmediate),
+        0b0010111 => i(Auipc.into(), (code >> 12) as RISCVImmediate),
+        0b0001111 => match funct3 {
+            0b000 => Some((Fence.into(), Fields::default())),
+            0b001 => Some((FenceI.into(), Fields::default())),
+            _ => None,
+        },
+        0b1110011 => match funct3 {
+            0b000 => match code {
+                0x00000073 => Some((Ecall.into(), Fields::default())),
+                0x00100073 => Some((Ebreak.into(), Fields::default())),
+                _ => None,
+            },
+            // the source of the immediate forms is a 5-bit value in rs1
+            _ => i(
+                match funct3 {
+                    0b001 => Csrrw.into(),
+                    0b010 => Csrrs.into(),
+                    0b011 => Csrrc.into(),
+                    0b101 => Csrrwi.into(),
+                    0b110 => Csrrsi.into(),
+                    0b111 => Csrrci.into(),
+                    _ => return None,
+                },
+                funct12 as RISCVImmediate,
+            ),
+        },
+        0b0101111 if funct3 == 0b010 => {
+            use RV32AInstruction::*;
+
+            let op = match bits(code, 31, 27) {
+                0b00000 => AmoaddW,
+                0b01100 => AmoandW,
+                0b10100 => AmomaxW,
+                0b11100 => AmomaxuW,
+                0b10000 => AmominW,
+                0b11000 => AmominuW,
+                0b01000 => AmoorW,
+                0b00001 => AmoswapW,
+                0b00100 => AmoxorW,
+                0b00010 if rs2 == 0 => LrW,
+                0b00011 => ScW,
+                _ => return None,
+            };
+            Some((
+                op.into(),
+                Fields {
+                    rd,
+                    rs1,
+                    rs2,
+                    imm: bits(code, 26, 25) as RISCVImmediate,
+                },
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The fields are the ones of the base instruction, as the assembler keeps
+/// them.
+fn decode_compressed(code: u32, xlen: Xlen) -> Option<(RV32CInstruction, Fields)> {
+    use RV32CInstruction::*;
+
+    // x8-x15 of the 3-bit register fields
+    let rd_c = bits(code, 4, 2) + 8;
+    let rs1_c = bits(code, 9, 7) + 8;
+    let rd = bits(code, 11, 7);
+    let rs2 = bits(code, 6, 2);
+    let imm6 = bits(code, 12, 12) << 5 | bits(code, 6, 2);
+    let jump = sext(
+        bits(code, 12, 12) << 11
+            | bits(code, 11, 11) << 4
+            | bits(code, 10, 9) << 8
+            | bits(code, 8, 8) << 10
+            | bits(code, 7, 7) << 6
+            | bits(code, 6, 6) << 7
+            | bits(code, 5, 3) << 1
+            | bits(code, 2, 2) << 5,
+        12,
+    );
+    let fields = |rd, rs1, rs2, imm| Fields { rd, rs1, rs2, imm };
+    let res = match (code & 0b11, bits(code, 15, 13)) {
+        (0b00, 0b000) => {
+            let imm = bits(code, 12, 11) << 4
+                | bits(code, 10, 7) << 6
+                | bits(code, 6, 6) << 2
+                | bits(code, 5, 5) << 3;
+            if imm == 0 {
+                return None;
+            }
+            (CAddi4spn, fields(rd_c, 2, 0, imm as RISCVImmediate))
+        }
+        (0b00, 0b010 | 0b110) => {
+            let imm = bits(code, 12, 10) << 3 | bits(code, 6, 6) << 2 | bits(code, 5, 5) << 6;
+            match bits(code, 15, 13) {
+                0b010 => (CLw, fields(rd_c, rs1_c, 0, imm as RISCVImmediate)),
+                _ => (CSw, fields(0, rs1_c, rd_c, imm as RISCVImmediate)),
+            }
+        }
+        // hints with rd = x0 do nothing either
+        (0b01, 0b000) if rd == 0 => (CNop, Fields::default()),
+        (0b01, 0b000) => (CAddi, fields(rd, rd, 0, sext(imm6, 6))),
+        // c.addiw in RV64
+        (0b01, 0b001) if xlen == Xlen::Rv32 => (CJal, fields(1, 0, 0, jump)),
+        (0b01, 0b010) => (CLi, fields(rd, 0, 0, sext(imm6, 6))),
+        (0b01, 0b011) if rd == 2 => {
+            let imm = sext(
+                bits(code, 12, 12) << 9
+                    | bits(code, 6, 6) << 4
+                    | bits(code, 5, 5) << 6
+                    | bits(code, 4, 3) << 7
+                    | bits(code, 2, 2) << 5,
+                10,
+            );
+            if imm == 0 {
+                return None;
+            }
+            (CAddi16sp, fields(2, 2, 0, imm))
+        }
+        (0b01, 0b011) => {
+            if imm6 == 0 {
+                return None;
+            }
+            // the 20-bit upper immediate of `lui`
+            let imm = match imm6 & 0x20 {
+                0 => imm6,
+                _ => imm6 | 0xfffc0,
+            };
+            (CLui, fields(rd, 0, 0, imm as RISCVImmediate))
+        }
+        (0b01, 0b100) => match bits(code, 11, 10) {
+            0b00 | 0b01 if xlen == Xlen::Rv32 && imm6 >= 32 => return None,
+            0b00 => (CSrli, fields(rs1_c, rs1_c, 0, imm6 as RISCVImmediate)),
+            0b01 => (CSrai, fields(rs1_c, rs1_c, 0, imm6 as RISCVImmediate)),
+            0b10 => (CAndi, fields(rs1_c, rs1_c, 0, sext(imm6, 6))),
+            // c.subw and c.addw in RV64
+            _ if bits(code, 12, 12) == 1 => return None,
+            _ => {
+                let op = match bits(code, 6, 5) {
+                    0b00 => CSub,
+                    0b01 => CXor,
+                    0b10 => COr,
+                    _ => CAnd,
+                };
+                (op, fields(rs1_c, rs1_c, rd_c, 0))
+            }
+        },
+        (0b01, 0b101) => (CJ, fields(0, 0, 0, jump)),
+        (0b01, 0b110 | 0b111) => {
+            let imm = sext(
+                bits(code, 12, 12) << 8
+                    | bits(code, 11, 10) << 3
+                    | bits(code, 6, 5) << 6
+                    | bits(code, 4, 3) << 1
+                    | bits(code, 2, 2) << 5,
+                9,
+            );
+            match bits(code, 15, 13) {
+                0b110 => (CBeqz, fields(0, rs1_c, 0, imm)),
+                _ => (CBnez, fields(0, rs1_c, 0, imm)),
+            }
+        }
+        (0b10, 0b000) => {
+            if xlen == Xlen::Rv32 && imm6 >= 32 {
+                return None;
+            }
+            (CSlli, fields(rd, rd, 0, imm6 as RISCVImmediate))
+        }
+        (0b10, 0b010) if rd != 0 => {
+            let imm = bits(code, 12, 12) << 5 | bits(code, 6, 4) << 2 | bits(code, 3, 2) << 6;
+            (CLwsp, fields(rd, 2, 0, imm as RISCVImmediate))
+        }
+        (0b10, 0b100) => match (bits(code, 12, 12), rd, rs2) {
+            (0, 0, 0) => return None,
+            (0, _, 0) => (CJr, fields(0, rd, 0, 0)),
+            (0, _, _) => (CMv, fields(rd, 0, rs2, 0)),
+            (_, 0, 0) => (CEbreak, Fields::default()),
+            (_, _, 0) => (CJalr, fields(1, rd, 0, 0)),
+            (_, _, _) => (CAdd, fields(rd, rd, rs2, 0)),
+        },
+        (0b10, 0b110) => {
+            let imm = bits(code, 12, 9) << 2 | bits(code, 8, 7) << 6;
+            (CSwsp, fields(0, 2, rs2, imm as RISCVImmediate))
+        }
+        _ => return None,
+    };
+    Some(res)
+}
+
+/// Instructions fetched from memory, in self-modifying mode the text lives in
+/// the memory and every instruction is decoded when first fetched. Stores
+/// make the lines they overlap stale and `fence.i` all of them, a stale line
+/// is decoded again when fetched.
+pub(super) struct DecodeCache {
+    // pc index of the first address that isn't an instruction of the text
+    base: usize,
+    extra: Vec<u32>,
+    extra_idx: HashMap<u32, usize>,
+    // by address, stale lines are kept for the reports of the instructions
+    // that executed them
+    lines: HashMap<u32, (InstructionSet<RISCV>, bool)>,
+}
+
+impl DecodeCache {
+    /// Pc indices from `base` on are given to addresses outside the text
+    /// layout.
+    pub fn new(base: usize) -> Self {
+        DecodeCache {
+            base,
+            extra: Vec::new(),
+            extra_idx: HashMap::new(),
+            lines: HashMap::new(),
+        }
+    }
+
+    /// Pc index of `addr`, which isn't the address of an instruction of the
+    /// text.
+    pub fn idx(&mut self, addr: u32) -> usize {
+        let extra = &mut self.extra;
+        let base = self.base;
+        *self.extra_idx.entry(addr).or_insert_with(|| {
+            extra.push(addr);
+            base + extra.len() - 1
+        })
+    }
+
+    /// Address of an index given by `idx`.
+    pub fn addr(&self, idx: usize) -> u32 {
+        self.extra[idx - self.base]
+    }
+
+    /// The line decoded at `addr`, which may be stale.
+    pub fn get(&self, addr: u32) -> Option<&InstructionSet<RISCV>> {
+        self.lines.get(&addr).map(|(line, _)| line)
+    }
+
+    pub fn is_fresh(&self, addr: u32) -> bool {
+        self.lines.get(&addr).map_or(false, |&(_, fresh)| fresh)
+    }
+
+    pub fn insert(&mut self, line: InstructionSet<RISCV>) {
+        self.lines.insert(line.address, (line, true));
+    }
+
+    /// Makes the lines overlapping `[addr, addr + len)` stale.
+    pub fn invalidate(&mut self, addr: u32, len: u32) {
+        let end = addr as u64 + len as u64;
+        // instructions are 2-byte aligned and at most 4 bytes long
+        let first = addr.saturating_sub(3) & !1;
+        for start in (first as u64..end).step_by(2) {
+            if let Some((line, fresh)) = self.lines.get_mut(&(start as u32)) {
+                if start + size(line.instruction.operation) as u64 > addr as u64 {
+                    *fresh = false;
+                }
+            }
+        }
+    }
+
+    pub fn flush(&mut self) {
+        for (_, fresh) in self.lines.values_mut() {
+            *fresh = false;
+        }
+    }
+}
@@ -0,0 +1,1224 @@
+use std::ops::Index;
+
+use once_cell::sync::Lazy;
+use strum::VariantArray;
+use RV32AInstruction::*;
+use RV32IInstruction::*;
+use RV64IInstruction::*;
+use RVBInstruction::*;
+
+use super::{branch::BranchKind, decode, simulator::*};
+use crate::{
+    interface::assembler::Operand,
+    modules::riscv::{
+        basic::interface::parser::RISCV,
+        rv32a::constants::RV32AInstruction,
+        rv32i::constants::*,
+        rv64i::constants::RV64IInstruction,
+        rvb::constants::RVBInstruction,
+    },
+    types::middleware_types::MemoryFaultKind,
+    utility::{enum_map::EnumMap, ptr::Ptr},
+};
+
+pub(super) type InstHandler = fn(InstHandlerArg) -> Result<SimulatorStatus, String>;
+pub(super) struct InstHandlerArg<'a> {
+    pub sim: Ptr<RISCVSimulator>,
+    pub args: &'a Vec<Operand<RISCV>>,
+    pub history: Ptr<History>,
+}
+
+pub(super) static INST_HANDLER_MAP: Lazy<EnumMap<RV32IInstruction, InstHandler>> =
+    Lazy::new(|| {
+        EnumMap::new(
+            &[
+                (Add, add_handler as InstHandler),
+                (Addi, addi_handler as InstHandler),
+                (And, and_handler as InstHandler),
+                (Andi, andi_handler as InstHandler),
+                (Auipc, auipc_handler as InstHandler),
+                (Beq, beq_handler as InstHandler),
+                (Bge, bge_handler as InstHandler),
+                (Bgeu, bgeu_handler as InstHandler),
+                (Blt, blt_handler as InstHandler),
+                (Bltu, bltu_handler as InstHandler),
+                (Bne, bne_handler as InstHandler),
+                (Csrrc, csrrc_handler as InstHandler),
+                (Csrrci, csrrci_handler as InstHandler),
+                (Csrrs, csrrs_handler as InstHandler),
+                (Csrrsi, csrrsi_handler as InstHandler),
+                (Csrrs, csrrs_handler as InstHandler),
+                (Csrrsi, csrrsi_handler as InstHandler),
+                (Csrrw, csrrw_handler as InstHandler),
+                (Csrrwi, csrrwi_handler as InstHandler),
+                (Ebreak, ebreak_handler as InstHandler),
+                (Ecall, ecall_handler as InstHandler),
+                (Fence, fence_handler as InstHandler),
+                (FenceI, fence_i_handler as InstHandler),
+                (Jal, jal_handler as InstHandler),
+                (Jalr, jalr_handler as InstHandler),
+                (Lb, lb_handler as InstHandler),
+                (Lbu, lbu_handler as InstHandler),
+                (Lh, lh_handler as InstHandler),
+                (Lhu, lhu_handler as InstHandler),
+                (Lui, lui_handler as InstHandler),
+                (Lw, lw_handler as InstHandler),
+                (Or, or_handler as InstHandler),
+                (Ori, ori_handler as InstHandler),
+                (Sb, sb_handler as InstHandler),
+                (Sh, sh_handler as InstHandler),
+                (Sll, sll_handler as InstHandler),
+                (Slli, slli_handler as InstHandler),
+                (Slt, slt_handler as InstHandler),
+                (Slti, slti_handler as InstHandler),
+                (Sltiu, sltiu_handler as InstHandler),
+                (Sltu, sltu_handler as InstHandler),
+                (Sra, sra_handler as InstHandler),
+                (Srai, srai_handler as InstHandler),
+                (Srl, srl_handler as InstHandler),
+                (Srli, srli_handler as InstHandler),
+                (Sub, sub_handler as InstHandler),
+                (Sw, sw_handler as InstHandler),
+                (Xor, xor_handler as InstHandler),
+                (Xori, xori_handler as InstHandler),
+            ],
+            |ele| (ele.0, ele.1),
+        )
+    });
+
+pub(super) static RV32A_INST_HANDLER_MAP: Lazy<EnumMap<RV32AInstruction, InstHandler>> =
+    Lazy::new(|| {
+        EnumMap::new(
+            &[
+                (AmoaddW, amoadd_w_handler as InstHandler),
+                (AmoandW, amoand_w_handler as InstHandler),
+                (AmomaxW, amomax_w_handler as InstHandler),
+                (AmomaxuW, amomaxu_w_handler as InstHandler),
+                (AmominW, amomin_w_handler as InstHandler),
+                (AmominuW, amominu_w_handler as InstHandler),
+                (AmoorW, amoor_w_handler as InstHandler),
+                (AmoswapW, amoswap_w_handler as InstHandler),
+                (AmoxorW, amoxor_w_handler as InstHandler),
+                (LrW, lr_w_handler as InstHandler),
+                (ScW, sc_w_handler as InstHandler),
+            ],
+            |ele| (ele.0, ele.1),
+        )
+    });
+
+pub(super) static RV64I_INST_HANDLER_MAP: Lazy<EnumMap<RV64IInstruction, InstHandler>> =
+    Lazy::new(|| {
+        EnumMap::new(
+            &[
+                (Addiw, addiw_handler as InstHandler),
+                (Addw, addw_handler as InstHandler),
+                (Ld, ld_handler as InstHandler),
+                (Lwu, lwu_handler as InstHandler),
+                (Sd, sd_handler as InstHandler),
+                (Slliw, slliw_handler as InstHandler),
+                (Sllw, sllw_handler as InstHandler),
+                (Sraiw, sraiw_handler as InstHandler),
+                (Sraw, sraw_handler as InstHandler),
+                (Srliw, srliw_handler as InstHandler),
+                (Srlw, srlw_handler as InstHandler),
+                (Subw, subw_handler as InstHandler),
+            ],
+            |ele| (ele.0, ele.1),
+        )
+    });
+
+pub(super) static RVB_INST_HANDLER_MAP: Lazy<EnumMap<RVBInstruction, InstHandler>> =
+    Lazy::new(|| {
+        EnumMap::new(
+            &[
+                (AddUw, add_uw_handler as InstHandler),
+                (Andn, andn_handler as InstHandler),
+                (Bclr, bclr_handler as InstHandler),
+                (Bclri, bclri_handler as InstHandler),
+                (Bext, bext_handler as InstHandler),
+                (Bexti, bexti_handler as InstHandler),
+                (Binv, binv_handler as InstHandler),
+                (Binvi, binvi_handler as InstHandler),
+                (Bset, bset_handler as InstHandler),
+                (Bseti, bseti_handler as InstHandler),
+                (Clz, clz_handler as InstHandler),
+                (Clzw, clzw_handler as InstHandler),
+                (Cpop, cpop_handler as InstHandler),
+                (Cpopw, cpopw_handler as InstHandler),
+                (Ctz, ctz_handler as InstHandler),
+                (Ctzw, ctzw_handler as InstHandler),
+                (Max, max_handler as InstHandler),
+                (Maxu, maxu_handler as InstHandler),
+                (Min, min_handler as InstHandler),
+                (Minu, minu_handler as InstHandler),
+                (OrcB, orc_b_handler as InstHandler),
+                (Orn, orn_handler as InstHandler),
+                (Rev8, rev8_handler as InstHandler),
+                (Rol, rol_handler as InstHandler),
+                (Rolw, rolw_handler as InstHandler),
+                (Ror, ror_handler as InstHandler),
+                (Rori, rori_handler as InstHandler),
+                (Roriw, roriw_handler as InstHandler),
+                (Rorw, rorw_handler as InstHandler),
+                (SextB, sext_b_handler as InstHandler),
+                (SextH, sext_h_handler as InstHandler),
+                (Sh1add, sh1add_handler as InstHandler),
+                (Sh1addUw, sh1add_uw_handler as InstHandler),
+                (Sh2add, sh2add_handler as InstHandler),
+                (Sh2addUw, sh2add_uw_handler as InstHandler),
+                (Sh3add, sh3add_handler as InstHandler),
+                (Sh3addUw, sh3add_uw_handler as InstHandler),
+                (SlliUw, slli_uw_handler as InstHandler),
+                (Xnor, xnor_handler as InstHandler),
+                (ZextH, zext_h_handler as InstHandler),
+            ],
+            |ele| (ele.0, ele.1),
+        )
+    });
+
+static FAKE_ZERO: Lazy<u64> = Lazy::new(|| 0);
+static FAKE_ZERO_PTR: Lazy<Ptr<u64>> = Lazy::new(|| Ptr::new(&FAKE_ZERO));
+
+macro_rules! load_helper {
+    ($arg:expr, $size:expr, $t:ty) => {{
+        let addr = $arg.mem_addr($size, false)?;
+        let sim = $arg.sim.as_ref();
+        if sim.in_memory(addr, $size) {
+            let mut buf = [0u8; $size];
+            for i in 0..$size {
+                buf[i] = sim.mem[addr + i as u32];
+            }
+            sim.data_order(&mut buf);
+            $arg.sim.as_mut().access_memory(addr, $size, false);
+            $arg.set_reg($arg[0], <$t>::from_le_bytes(buf) as u64);
+            $arg.pc_step();
+            Ok(SimulatorStatus::Running)
+        } else {
+            Err($arg.fault(MemoryFaultKind::OutOfRange, addr as u64, $size, false))
+        }
+    }};
+}
+
+macro_rules! store_helper {
+    ($arg:expr, $size:expr, $t:ty) => {{
+        let addr = $arg.mem_addr($size, true)?;
+        let sim = $arg.sim.as_mut();
+        if sim.in_read_only_section(addr, $size).is_some() {
+            Err($arg.fault(MemoryFaultKind::ReadOnly, addr as u64, $size, true))
+        } else if sim.in_memory(addr, $size) {
+            let history = $arg.history.as_mut();
+            history.mem_addr = addr;
+            history.mem_len = $size;
+            let mut buf = ($arg.reg($arg[0]) as $t).to_le_bytes();
+            sim.data_order(&mut buf);
+            for i in 0..$size {
+                history.mem[i] = sim.mem[addr + i as u32];
+                sim.mem[addr + i as u32] = buf[i];
+            }
+            $arg.invalidate_reservation(addr, $size);
+            sim.access_memory(addr, $size, true);
+            $arg.pc_step();
+            Ok(SimulatorStatus::Running)
+        } else {
+            Err($arg.fault(MemoryFaultKind::OutOfRange, addr as u64, $size, true))
+        }
+    }};
+}
+
+pub(super) fn add_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]).wrapping_add(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn addi_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]).wrapping_add(arg.imm(2) as u64));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn and_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) & arg.reg(arg[2]));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn andi_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) & arg.imm(2) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn auipc_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.pc() as u64).wrapping_add(arg.upper_imm(1)));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn beq_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    branch_helper(&arg, arg.reg(arg[0]) == arg.reg(arg[1]))
+}
+
+pub(super) fn bge_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    branch_helper(&arg, arg.sreg(arg[0]) >= arg.sreg(arg[1]))
+}
+
+pub(super) fn bgeu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    branch_helper(&arg, arg.reg(arg[0]) >= arg.reg(arg[1]))
+}
+
+pub(super) fn blt_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    branch_helper(&arg, arg.sreg(arg[0]) < arg.sreg(arg[1]))
+}
+
+pub(super) fn bltu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    branch_helper(&arg, arg.reg(arg[0]) < arg.reg(arg[1]))
+}
+
+pub(super) fn bne_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    branch_helper(&arg, arg.reg(arg[0]) != arg.reg(arg[1]))
+}
+
+pub(super) fn csrrc_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    csr_helper(&arg, arg.reg(arg[2]), arg[2] != 0, |old, src| old & !src)
+}
+
+pub(super) fn csrrci_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    csr_helper(&arg, arg[2] as u64, arg[2] != 0, |old, src| old & !src)
+}
+
+pub(super) fn csrrs_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    csr_helper(&arg, arg.reg(arg[2]), arg[2] != 0, |old, src| old | src)
+}
+
+pub(super) fn csrrsi_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    csr_helper(&arg, arg[2] as u64, arg[2] != 0, |old, src| old | src)
+}
+
+pub(super) fn csrrw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    csr_helper(&arg, arg.reg(arg[2]), true, |_, src| src)
+}
+
+pub(super) fn csrrwi_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    csr_helper(&arg, arg[2] as u64, true, |_, src| src)
+}
+
+pub(super) fn ebreak_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.sim.as_mut().hart_mut().reservation = None;
+    arg.pc_step();
+    Ok(SimulatorStatus::Paused)
+}
+
+pub(super) fn ecall_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.sim.as_mut().hart_mut().reservation = None;
+    match arg.reg(RV32IRegister::A7 as Operand<RISCV>) {
+        1 => {
+            arg.output(&arg.sreg(RV32IRegister::A0 as Operand<RISCV>).to_string())?;
+            arg.pc_step();
+            Ok(SimulatorStatus::Running)
+        }
+        4 => {
+            let addr = arg.reg(RV32IRegister::A0 as Operand<RISCV>) as u32;
+            let sim = arg.sim.as_ref();
+            let mut buf = Vec::new();
+            for i in 0.. {
+                let byte = sim.mem[addr + i as u32];
+                if byte == 0 {
+                    break;
+                }
+                buf.push(byte);
+            }
+            let s = String::from_utf8(buf).unwrap();
+            arg.output(&s)?;
+            arg.pc_step();
+            Ok(SimulatorStatus::Running)
+        }
+        5 => {
+            arg.request_input(WaitStatus::Int)?;
+            Ok(SimulatorStatus::Paused)
+        }
+        8 => {
+            arg.request_input(WaitStatus::String)?;
+            Ok(SimulatorStatus::Paused)
+        }
+        10 => Ok(SimulatorStatus::Stopped),
+        11 => {
+            arg.output(
+                &((arg.reg(RV32IRegister::A0 as Operand<RISCV>) as u8 as char).to_string()),
+            )?;
+            arg.pc_step();
+            Ok(SimulatorStatus::Running)
+        }
+        12 => {
+            arg.request_input(WaitStatus::Char)?;
+            Ok(SimulatorStatus::Paused)
+        }
+        34 => {
+            let digits = arg.sim.as_ref().xlen_bits() as usize / 4;
+            arg.output(&format!(
+                "0x{:0digits$x}",
+                arg.reg(RV32IRegister::A0 as Operand<RISCV>)
+            ))?;
+            arg.pc_step();
+            Ok(SimulatorStatus::Running)
+        }
+        35 => {
+            let digits = arg.sim.as_ref().xlen_bits() as usize;
+            arg.output(&format!(
+                "0b{:0digits$b}",
+                arg.reg(RV32IRegister::A0 as Operand<RISCV>)
+            ))?;
+            arg.pc_step();
+            Ok(SimulatorStatus::Running)
+        }
+        36 => {
+            arg.output(&((arg.reg(RV32IRegister::A0 as Operand<RISCV>)).to_string()))?;
+            arg.pc_step();
+            Ok(SimulatorStatus::Running)
+        }
+        _ => Err("Invalid ecall number".to_string()),
+    }
+}
+
+pub(super) fn fence_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn fence_i_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    // instructions fetched from memory are decoded again
+    if let Some(decoded) = &mut arg.sim.as_mut().decoded {
+        decoded.flush();
+    }
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn jal_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    let target = arg.pc().wrapping_add(arg.imm(1) as u32);
+    let link = arg.next_pc();
+    arg.predict_branch(BranchKind::Jal, true, target);
+    jump_helper(&arg, target)?;
+    arg.set_reg(arg[0], link as u64);
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn jalr_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    let target = arg
+        .sim
+        .as_ref()
+        .truncate(arg.reg(arg[1]).wrapping_add(arg.imm(2) as u64) & !1);
+    let target = u32::try_from(target).map_err(|_| "Invalid aim pc")?;
+    arg.set_reg(arg[0], arg.next_pc() as u64);
+    arg.predict_branch(BranchKind::Jalr, true, target);
+    jump_helper(&arg, target)?;
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn lb_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    load_helper!(arg, 1, i8)
+}
+
+pub(super) fn lbu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    load_helper!(arg, 1, u8)
+}
+
+pub(super) fn lh_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    load_helper!(arg, 2, i16)
+}
+
+pub(super) fn lhu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    load_helper!(arg, 2, u16)
+}
+
+pub(super) fn lui_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.upper_imm(1));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn lw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    load_helper!(arg, 4, i32)
+}
+
+pub(super) fn or_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) | arg.reg(arg[2]));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn ori_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) | arg.imm(2) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sb_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    store_helper!(arg, 1, u8)
+}
+
+pub(super) fn sh_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    store_helper!(arg, 2, u16)
+}
+
+pub(super) fn sll_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) << arg.shamt(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn slli_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) << arg.shamt(arg[2] as u64));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn slt_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.sreg(arg[1]) < arg.sreg(arg[2])) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn slti_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.sreg(arg[1]) < arg.imm(2)) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sltiu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    let imm = arg.sim.as_ref().truncate(arg.imm(2) as u64);
+    arg.set_reg(arg[0], (arg.reg(arg[1]) < imm) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sltu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) < arg.reg(arg[2])) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sra_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(
+        arg[0],
+        (arg.sreg(arg[1]) >> arg.shamt(arg.reg(arg[2]))) as u64,
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn srai_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(
+        arg[0],
+        (arg.sreg(arg[1]) >> arg.shamt(arg[2] as u64)) as u64,
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn srl_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) >> arg.shamt(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn srli_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) >> arg.shamt(arg[2] as u64));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sub_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]).wrapping_sub(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    store_helper!(arg, 4, u32)
+}
+
+pub(super) fn xor_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) ^ arg.reg(arg[2]));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn xori_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) ^ arg.imm(2) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn amoadd_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |old, val| old.wrapping_add(val))
+}
+
+pub(super) fn amoand_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |old, val| old & val)
+}
+
+pub(super) fn amomax_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |old, val| old.max(val))
+}
+
+pub(super) fn amomaxu_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |old, val| (old as u32).max(val as u32) as i32)
+}
+
+pub(super) fn amomin_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |old, val| old.min(val))
+}
+
+pub(super) fn amominu_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |old, val| (old as u32).min(val as u32) as i32)
+}
+
+pub(super) fn amoor_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |old, val| old | val)
+}
+
+pub(super) fn amoswap_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |_, val| val)
+}
+
+pub(super) fn amoxor_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    amo_helper(&arg, |old, val| old ^ val)
+}
+
+pub(super) fn lr_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    let addr = arg.atomic_addr(false)?;
+    let val = arg.load_word(addr)?;
+    arg.set_reg(arg[0], val as i64 as u64);
+    arg.sim.as_mut().hart_mut().reservation = Some(addr);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sc_w_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    let addr = arg.atomic_addr(true)?;
+    if arg.sim.as_ref().hart().reservation == Some(addr) {
+        arg.store_word(addr, arg.reg(arg[2]) as i32)?;
+        arg.set_reg(arg[0], 0);
+    } else {
+        arg.set_reg(arg[0], 1);
+    }
+    // the reservation is dropped whether or not the store succeeded
+    arg.sim.as_mut().hart_mut().reservation = None;
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn addiw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(arg[0], (arg.reg(arg[1]) as i32).wrapping_add(arg[2] as i32));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn addw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(
+        arg[0],
+        (arg.reg(arg[1]) as i32).wrapping_add(arg.reg(arg[2]) as i32),
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn ld_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    load_helper!(arg, 8, u64)
+}
+
+pub(super) fn lwu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    load_helper!(arg, 4, u32)
+}
+
+pub(super) fn sd_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    store_helper!(arg, 8, u64)
+}
+
+pub(super) fn slliw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(arg[0], (arg.reg(arg[1]) as i32) << (arg[2] & 0x1f));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sllw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(arg[0], (arg.reg(arg[1]) as i32) << (arg.reg(arg[2]) & 0x1f));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sraiw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(arg[0], (arg.reg(arg[1]) as i32) >> (arg[2] & 0x1f));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sraw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(arg[0], (arg.reg(arg[1]) as i32) >> (arg.reg(arg[2]) & 0x1f));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn srliw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(arg[0], ((arg.reg(arg[1]) as u32) >> (arg[2] & 0x1f)) as i32);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn srlw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(
+        arg[0],
+        ((arg.reg(arg[1]) as u32) >> (arg.reg(arg[2]) & 0x1f)) as i32,
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn subw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(
+        arg[0],
+        (arg.reg(arg[1]) as i32).wrapping_sub(arg.reg(arg[2]) as i32),
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn add_uw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(
+        arg[0],
+        (arg.reg(arg[1]) as u32 as u64).wrapping_add(arg.reg(arg[2])),
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn andn_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) & !arg.reg(arg[2]));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn bclr_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) & !(1 << arg.shamt(arg.reg(arg[2]))));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn bclri_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) & !(1 << arg.shamt(arg[2] as u64)));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn bext_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) >> arg.shamt(arg.reg(arg[2]))) & 1);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn bexti_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) >> arg.shamt(arg[2] as u64)) & 1);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn binv_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) ^ (1 << arg.shamt(arg.reg(arg[2]))));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn binvi_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) ^ (1 << arg.shamt(arg[2] as u64)));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn bset_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) | (1 << arg.shamt(arg.reg(arg[2]))));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn bseti_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) | (1 << arg.shamt(arg[2] as u64)));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn clz_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    // registers hold XLEN bits, the bits above them are always zero
+    let unused = 64 - arg.sim.as_ref().xlen_bits();
+    arg.set_reg(arg[0], (arg.reg(arg[1]).leading_zeros() - unused) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn clzw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) as u32).leading_zeros() as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn cpop_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]).count_ones() as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn cpopw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) as u32).count_ones() as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn ctz_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    let xlen = arg.sim.as_ref().xlen_bits();
+    arg.set_reg(arg[0], arg.reg(arg[1]).trailing_zeros().min(xlen) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn ctzw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) as u32).trailing_zeros() as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn max_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.sreg(arg[1]).max(arg.sreg(arg[2])) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn maxu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]).max(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn min_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.sreg(arg[1]).min(arg.sreg(arg[2])) as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn minu_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]).min(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn orc_b_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    let bytes = arg
+        .reg(arg[1])
+        .to_le_bytes()
+        .map(|b| if b == 0 { 0 } else { 0xff });
+    arg.set_reg(arg[0], u64::from_le_bytes(bytes));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn orn_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) | !arg.reg(arg[2]));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn rev8_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    let unused = 64 - arg.sim.as_ref().xlen_bits();
+    arg.set_reg(arg[0], arg.reg(arg[1]).swap_bytes() >> unused);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn rol_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.rotate_left(arg.reg(arg[1]), arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn rolw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(
+        arg[0],
+        (arg.reg(arg[1]) as u32).rotate_left(arg.reg(arg[2]) as u32 & 0x1f) as i32,
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn ror_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.rotate_right(arg.reg(arg[1]), arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn rori_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.rotate_right(arg.reg(arg[1]), arg[2] as u64));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn roriw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(
+        arg[0],
+        (arg.reg(arg[1]) as u32).rotate_right(arg[2] as u32 & 0x1f) as i32,
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn rorw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg_word(
+        arg[0],
+        (arg.reg(arg[1]) as u32).rotate_right(arg.reg(arg[2]) as u32 & 0x1f) as i32,
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sext_b_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) as i8 as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sext_h_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) as i16 as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sh1add_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) << 1).wrapping_add(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sh1add_uw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(
+        arg[0],
+        ((arg.reg(arg[1]) as u32 as u64) << 1).wrapping_add(arg.reg(arg[2])),
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sh2add_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) << 2).wrapping_add(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sh2add_uw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(
+        arg[0],
+        ((arg.reg(arg[1]) as u32 as u64) << 2).wrapping_add(arg.reg(arg[2])),
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sh3add_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], (arg.reg(arg[1]) << 3).wrapping_add(arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn sh3add_uw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(
+        arg[0],
+        ((arg.reg(arg[1]) as u32 as u64) << 3).wrapping_add(arg.reg(arg[2])),
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn slli_uw_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(
+        arg[0],
+        (arg.reg(arg[1]) as u32 as u64) << arg.shamt(arg[2] as u64),
+    );
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn xnor_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], !(arg.reg(arg[1]) ^ arg.reg(arg[2])));
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+pub(super) fn zext_h_handler(arg: InstHandlerArg) -> Result<SimulatorStatus, String> {
+    arg.set_reg(arg[0], arg.reg(arg[1]) as u16 as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+/// Writes `op(old, src)` to the CSR `arg[1]` if `write`, `rd` receives the
+/// old value.
+fn csr_helper(
+    arg: &InstHandlerArg,
+    src: u64,
+    write: bool,
+    op: impl Fn(u64, u64) -> u64,
+) -> Result<SimulatorStatus, String> {
+    let old = arg.csr(arg[1])?;
+    if write {
+        arg.set_csr(arg[1], op(old, src))?;
+    }
+    arg.set_reg(arg[0], old);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+/// Atomically replaces the word at `rs1` by `op(old, rs2)`, `rd` receives the
+/// old value.
+fn amo_helper(
+    arg: &InstHandlerArg,
+    op: impl Fn(i32, i32) -> i32,
+) -> Result<SimulatorStatus, String> {
+    let addr = arg.atomic_addr(true)?;
+    let old = arg.load_word(addr)?;
+    arg.store_word(addr, op(old, arg.reg(arg[2]) as i32))?;
+    arg.set_reg(arg[0], old as i64 as u64);
+    arg.pc_step();
+    Ok(SimulatorStatus::Running)
+}
+
+fn csr_of(num: Operand<RISCV>) -> Result<RV32ICsr, String> {
+    RV32ICsr::VARIANTS
+        .iter()
+        .copied()
+        .find(|csr| csr.number() as Operand<RISCV> == num)
+        .ok_or_else(|| format!("Unsupported CSR 0x{:03x}", num))
+}
+
+fn jump_helper(arg: &InstHandlerArg, addr: u32) -> Result<(), String> {
+    if arg.set_pc(addr) {
+        Ok(())
+    } else {
+        Err("Invalid aim pc".to_string())
+    }
+}
+
+fn branch_helper(arg: &InstHandlerArg, taken: bool) -> Result<SimulatorStatus, String> {
+    let target = arg.pc().wrapping_add(arg.imm(2) as u32);
+    arg.predict_branch(BranchKind::Conditional, taken, target);
+    if taken {
+        jump_helper(arg, target)?;
+    } else {
+        arg.pc_step();
+    }
+    Ok(SimulatorStatus::Running)
+}
+
+impl<'a> Index<usize> for InstHandlerArg<'a> {
+    type Output = Operand<RISCV>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.args[index]
+    }
+}
+
+impl<'a> InstHandlerArg<'a> {
+    fn reg(&self, index: Operand<RISCV>) -> u64 {
+        let sim = self.sim.as_mut();
+        sim.read_register(index as usize);
+        sim.hart().reg[index as usize]
+    }
+
+    /// Register value sign extended from XLEN bits.
+    fn sreg(&self, index: Operand<RISCV>) -> i64 {
+        self.sim.as_ref().sext(self.reg(index))
+    }
+
+    fn reg_mut(&self, index: Operand<RISCV>) -> &mut u64 {
+        if index == 0 {
+            return FAKE_ZERO_PTR.as_mut();
+        }
+        let hart = self.sim.as_mut().hart_mut();
+        let history = self.history.as_mut();
+        history.reg_idx = index as i32;
+        history.reg_val = hart.reg[index as usize];
+        &mut hart.reg[index as usize]
+    }
+
+    /// Writes the low XLEN bits of `val` to the register.
+    fn set_reg(&self, index: Operand<RISCV>, val: u64) {
+        *self.reg_mut(index) = self.sim.as_ref().truncate(val);
+    }
+
+    /// Writes the result of a `*w` instruction, sign extended to 64 bits.
+    fn set_reg_word(&self, index: Operand<RISCV>, val: i32) {
+        self.set_reg(index, val as i64 as u64);
+    }
+
+    /// Immediate operand `idx`, immediates are sign extended from XLEN bits as
+    /// a 32-bit operand may hold an unsigned value.
+    fn imm(&self, idx: usize) -> i64 {
+        self.sim.as_ref().sext(self[idx] as u64)
+    }
+
+    /// The 20-bit immediate of `lui` and `auipc` shifted to its place, bit 31
+    /// is sign extended.
+    fn upper_imm(&self, idx: usize) -> u64 {
+        ((self[idx] as u32) << 12) as i32 as i64 as u64
+    }
+
+    fn shamt(&self, amount: u64) -> u32 {
+        (amount & (self.sim.as_ref().xlen_bits() as u64 - 1)) as u32
+    }
+
+    /// `val` rotated right by `amount` within XLEN bits.
+    fn rotate_right(&self, val: u64, amount: u64) -> u64 {
+        let xlen = self.sim.as_ref().xlen_bits();
+        let amount = self.shamt(amount);
+        (val >> amount) | (val << ((xlen - amount) % xlen))
+    }
+
+    fn rotate_left(&self, val: u64, amount: u64) -> u64 {
+        let xlen = self.sim.as_ref().xlen_bits();
+        self.rotate_right(val, (xlen - self.shamt(amount)) as u64)
+    }
+
+    /// Records a memory fault of the instruction, returns its error.
+    fn fault(&self, kind: MemoryFaultKind, addr: u64, len: u32, write: bool) -> String {
+        self.sim.as_mut().memory_fault(kind, addr, len, write)
+    }
+
+    /// Address of a `len` byte load or store with operands `[data, offset,
+    /// base]`.
+    fn mem_addr(&self, len: u32, write: bool) -> Result<u32, String> {
+        let addr = self
+            .sim
+            .as_ref()
+            .truncate(self.reg(self[2]).wrapping_add(self.imm(1) as u64));
+        u32::try_from(addr).map_err(|_| self.fault(MemoryFaultKind::OutOfRange, addr, len, write))
+    }
+
+    /// Address in `rs1` of an atomic instruction, which must be word aligned.
+    fn atomic_addr(&self, write: bool) -> Result<u32, String> {
+        let addr = self.sim.as_ref().truncate(self.reg(self[1]));
+        let addr = u32::try_from(addr)
+            .map_err(|_| self.fault(MemoryFaultKind::OutOfRange, addr, 4, write))?;
+        if addr % 4 != 0 {
+            return Err(self.fault(MemoryFaultKind::Misaligned, addr as u64, 4, write));
+        }
+        Ok(addr)
+    }
+
+    fn load_word(&self, addr: u32) -> Result<i32, String> {
+        let sim = self.sim.as_ref();
+        if sim.in_memory(addr, 4) {
+            let mut buf = [0u8; 4];
+            for i in 0..4 {
+                buf[i] = sim.mem[addr + i as u32];
+            }
+            sim.data_order(&mut buf);
+            self.sim.as_mut().access_memory(addr, 4, false);
+            Ok(i32::from_le_bytes(buf))
+        } else {
+            Err(self.fault(MemoryFaultKind::OutOfRange, addr as u64, 4, false))
+        }
+    }
+
+    /// Stores a word and records the overwritten bytes for undo.
+    fn store_word(&self, addr: u32, val: i32) -> Result<(), String> {
+        let sim = self.sim.as_mut();
+        if sim.in_read_only_section(addr, 4).is_some() {
+            return Err(self.fault(MemoryFaultKind::ReadOnly, addr as u64, 4, true));
+        }
+        if !sim.in_memory(addr, 4) {
+            return Err(self.fault(MemoryFaultKind::OutOfRange, addr as u64, 4, true));
+        }
+        let history = self.history.as_mut();
+        history.mem_addr = addr;
+        history.mem_len = 4;
+        let mut buf = val.to_le_bytes();
+        sim.data_order(&mut buf);
+        for (i, byte) in buf.into_iter().enumerate() {
+            history.mem[i] = sim.mem[addr + i as u32];
+            sim.mem[addr + i as u32] = byte;
+        }
+        self.invalidate_reservation(addr, 4);
+        sim.access_memory(addr, 4, true);
+        Ok(())
+    }
+
+    /// Drops the reservations overlapping stored bytes, the ones of other
+    /// harts are recorded for undo.
+    fn invalidate_reservation(&self, addr: u32, len: u32) {
+        let sim = self.sim.as_mut();
+        let current = sim.sched.hart;
+        let history = self.history.as_mut();
+        for (idx, hart) in sim.harts.iter_mut().enumerate() {
+            if hart.reserves(addr, len) {
+                if idx != current {
+                    history
+                        .dropped_reservations
+                        .push((idx, hart.reservation.unwrap()));
+                }
+                hart.reservation = None;
+            }
+        }
+    }
+
+    /// Value of the CSR numbered `num`.
+    fn csr(&self, num: Operand<RISCV>) -> Result<u64, String> {
+        match csr_of(num)? {
+            RV32ICsr::Mhartid => Ok(self.sim.as_ref().sched.hart as u64),
+        }
+    }
+
+    fn set_csr(&self, num: Operand<RISCV>, _val: u64) -> Result<(), String> {
+        match csr_of(num)? {
+            RV32ICsr::Mhartid => Err("Write to read-only CSR mhartid".to_string()),
+        }
+    }
+
+    fn pc(&self) -> u32 {
+        self.sim
+            .as_ref()
+            .to_text_addr(self.sim.as_ref().hart().pc_idx)
+    }
+
+    /// Address of the instruction following the current one, which may be 2
+    /// or 4 bytes long.
+    fn next_pc(&self) -> u32 {
+        let sim = self.sim.as_ref();
+        let pc_idx = sim.hart().pc_idx;
+        sim.to_text_addr(pc_idx) + decode::size(sim.line(pc_idx).instruction.operation)
+    }
+
+    fn set_pc(&self, addr: u32) -> bool {
+        let sim = self.sim.as_mut();
+        match sim.jump_idx(addr) {
+            Some(idx) => {
+                sim.hart_mut().pc_idx = idx;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Feeds the branch predictor with the current control transfer, before
+    /// the pc moves.
+    fn predict_branch(&self, kind: BranchKind, taken: bool, target: u32) {
+        let sim = self.sim.as_mut();
+        sim.predict_branch(sim.hart().pc_idx, kind, taken, target);
+    }
+
+    fn pc_step(&self) {
+        self.sim.as_mut().step_pc();
+    }
+
+    fn request_input(&self, wait_status: WaitStatus) -> Result<(), String> {
+        self.sim.as_mut().request_input(wait_status)
+    }
+
+    fn output(&self, output: &str) -> Result<(), String> {
+        self.sim.as_mut().output(output)
+    }
+}
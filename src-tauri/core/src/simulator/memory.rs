@@ -122,6 +122,20 @@ impl Memory {
         }
     }
 
+    /// Every allocated page with its address, in address order.
+    pub fn pages(&self) -> Vec<(u32, &Page)> {
+        let mut pages = Vec::new();
+        for (first, table) in self.page_table.iter().enumerate() {
+            for (second, page) in table.iter().flat_map(|table| table.iter().enumerate()) {
+                if let Some(page) = page {
+                    let addr = (first << FIRST_PAGE_SHIFT) | (second << SECOND_PAGE_SHIFT);
+                    pages.push((addr as u32, page.as_ref()));
+                }
+            }
+        }
+        pages
+    }
+
     fn build_page_table(&mut self, index: (usize, usize, usize)) {
         let first_page_table = &mut self.page_table;
         if first_page_table[index.0].is_none() {
@@ -0,0 +1,17 @@
+mod abi;
+#[allow(arithmetic_overflow)]
+mod branch;
+mod cache;
+mod changes;
+mod decode;
+mod instruction;
+mod memory;
+mod pacer;
+mod pipeline;
+mod profiler;
+mod shadow;
+pub mod simulator;
+#[cfg(test)]
+mod test;
+mod trace;
+mod watch;
@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+/// Interval of the progress updates of an animated run.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+/// Longest sleep, a stop request ends the wait after at most this.
+const MAX_SLEEP: Duration = Duration::from_millis(10);
+
+/// Paces a run at a rate which may change at any time, 0 runs flat out.
+pub(super) struct Pacer {
+    rate: u64,
+    // steps since `anchor`, reset when the rate changes
+    anchor: Instant,
+    steps: u64,
+    last_progress: Instant,
+}
+
+impl Pacer {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Pacer {
+            rate: 0,
+            anchor: now,
+            steps: 0,
+            last_progress: now,
+        }
+    }
+
+    /// Counts a step at `rate` steps per second.
+    pub fn step(&mut self, rate: u64) {
+        if rate != self.rate {
+            self.rate = rate;
+            self.anchor = Instant::now();
+            self.steps = 0;
+        }
+        self.steps += 1;
+    }
+
+    /// Time until the next step is due, at most `MAX_SLEEP`.
+    pub fn delay(&self) -> Duration {
+        if self.rate == 0 {
+            return Duration::ZERO;
+        }
+        let due = self.anchor
+            + Duration::from_nanos((self.steps as u128 * 1_000_000_000 / self.rate as u128) as u64);
+        due.saturating_duration_since(Instant::now()).min(MAX_SLEEP)
+    }
+
+    /// Whether a progress update is due, only animated runs send them.
+    pub fn progress_due(&mut self) -> bool {
+        if self.rate == 0 || self.last_progress.elapsed() < PROGRESS_INTERVAL {
+            return false;
+        }
+        self.last_progress = Instant::now();
+        true
+    }
+}
@@ -0,0 +1,313 @@
+use super::simulator::{RISCVSimulator, SimulatorStatus};
+use crate::{
+    interface::assembler::Operand,
+    modules::riscv::{
+        basic::interface::parser::{ParserRISCVInstOp, RV32IRegister, RISCV},
+        rv32i::constants::RV32IInstruction,
+        rv64i::constants::RV64IInstruction,
+        rvb::constants::RVBInstruction,
+    },
+    types::middleware_types::{PipelineData, StaticPrediction},
+};
+
+const IF: usize = 0;
+const ID: usize = 1;
+const EX: usize = 2;
+const MEM: usize = 3;
+const WB: usize = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Control {
+    None,
+    Branch,
+    Jal,
+    Jalr,
+}
+
+/// Registers an instruction reads and writes, `x0` stands for none.
+#[derive(Clone, Copy)]
+pub(super) struct Deps {
+    pub rd: Operand<RISCV>,
+    pub rs: [Operand<RISCV>; 2],
+    // the result is only available after MEM
+    pub load: bool,
+    pub control: Control,
+}
+
+#[derive(Clone, Copy)]
+struct Slot {
+    idx: usize,
+    deps: Deps,
+    // fetched down a mispredicted path, never executed
+    wrong_path: bool,
+    // the instruction fetched after it is on the wrong path
+    mispredicted: bool,
+}
+
+/// Timing model of a classic 5-stage pipeline. Instructions execute on the
+/// functional simulator when fetched on the correct path, so the pipeline
+/// only decides when they flow through the stages.
+#[derive(Clone)]
+pub(super) struct Pipeline {
+    stages: [Option<Slot>; 5],
+    forwarding: bool,
+    prediction: StaticPrediction,
+    // next instruction on the wrong path, the correct path follows the pc
+    fetch_idx: usize,
+    wrong_path: bool,
+    // nothing is fetched after the program exits
+    halted: bool,
+    cycles: u64,
+    instructions: u64,
+    stalls: u64,
+    flushes: u64,
+}
+
+impl Pipeline {
+    pub fn new(forwarding: bool, prediction: StaticPrediction) -> Self {
+        Pipeline {
+            stages: [None; 5],
+            forwarding,
+            prediction,
+            fetch_idx: 0,
+            wrong_path: false,
+            halted: false,
+            cycles: 0,
+            instructions: 0,
+            stalls: 0,
+            flushes: 0,
+        }
+    }
+
+    /// Whether the program has ended and only WB may still be busy.
+    pub fn finished(&self, sim: &RISCVSimulator) -> bool {
+        self.stages[..WB].iter().all(Option::is_none)
+            && (self.halted || sim.hart().pc_idx == sim.inst.as_ref().unwrap().instruction.len())
+    }
+
+    /// Advances one cycle, the status is the one of the instruction fetched.
+    pub fn cycle(&mut self, sim: &mut RISCVSimulator) -> Result<SimulatorStatus, String> {
+        self.cycles += 1;
+        let [fetched, decoded, executed, memory, _] = self.stages;
+        // a mispredicted control transfer leaves EX, the younger two are
+        // squashed
+        let flush = executed.map_or(false, |slot| slot.mispredicted);
+        let stall =
+            !flush && decoded.map_or(false, |slot| self.hazard(&slot.deps, executed, memory));
+        self.stages[WB] = memory;
+        self.stages[MEM] = executed;
+        // wrong path instructions are squashed before reaching EX
+        if memory.is_some() {
+            self.instructions += 1;
+        }
+        let mut status = SimulatorStatus::Running;
+        if stall {
+            self.stalls += 1;
+            self.stages[EX] = None;
+        } else {
+            if flush {
+                self.flushes += 1;
+                self.wrong_path = false;
+                self.stages[EX] = None;
+                self.stages[ID] = None;
+            } else {
+                self.stages[EX] = decoded;
+                self.stages[ID] = fetched;
+            }
+            let (slot, res) = self.fetch(sim)?;
+            self.stages[IF] = slot;
+            status = res;
+        }
+        Ok(status)
+    }
+
+    /// Fetches from the pc again after the debugger moved it, the
+    /// instructions in flight have executed already and keep their effects.
+    pub fn redirect(&mut self) {
+        self.wrong_path = false;
+        self.halted = false;
+    }
+
+    pub fn data(&self) -> PipelineData {
+        PipelineData {
+            stages: self
+                .stages
+                .iter()
+                .map(|slot| slot.map(|slot| slot.idx as u64))
+                .collect(),
+            cycles: self.cycles,
+            instructions: self.instructions,
+            stalls: self.stalls,
+            flushes: self.flushes,
+            cpi: if self.instructions == 0 {
+                0.0
+            } else {
+                self.cycles as f64 / self.instructions as f64
+            },
+        }
+    }
+
+    /// Whether the instruction in ID has to wait for a result, the register
+    /// file is written before it is read within a cycle.
+    fn hazard(&self, deps: &Deps, executed: Option<Slot>, memory: Option<Slot>) -> bool {
+        let reads = |slot: Option<Slot>| {
+            slot.map_or(false, |slot| {
+                slot.deps.rd != 0 && deps.rs.contains(&slot.deps.rd)
+            })
+        };
+        if self.forwarding {
+            // only a load in EX can't forward in time
+            reads(executed) && executed.unwrap().deps.load
+        } else {
+            reads(executed) || reads(memory)
+        }
+    }
+
+    fn fetch(
+        &mut self,
+        sim: &mut RISCVSimulator,
+    ) -> Result<(Option<Slot>, SimulatorStatus), String> {
+        let end = sim.inst.as_ref().unwrap().instruction.len();
+        let idx = if self.wrong_path {
+            self.fetch_idx
+        } else {
+            sim.hart().pc_idx
+        };
+        if self.halted || idx >= end {
+            return Ok((None, SimulatorStatus::Running));
+        }
+        let inst = &sim.inst.as_ref().unwrap().instruction[idx].instruction;
+        let deps = deps(inst.operation, &inst.operands);
+        let predicted = self.predict(sim, idx, &deps, &inst.operands);
+        let mut slot = Slot {
+            idx,
+            deps,
+            wrong_path: self.wrong_path,
+            mispredicted: false,
+        };
+        let mut status = SimulatorStatus::Running;
+        if self.wrong_path {
+            self.fetch_idx = predicted;
+        } else {
+            status = sim._step()?;
+            if status == SimulatorStatus::Stopped {
+                self.halted = true;
+                status = SimulatorStatus::Running;
+            }
+            if deps.control != Control::None && sim.hart().pc_idx != predicted {
+                slot.mispredicted = true;
+                self.wrong_path = true;
+                self.fetch_idx = predicted;
+            }
+        }
+        Ok((Some(slot), status))
+    }
+
+    /// Index of the instruction fetched after the one at `idx`.
+    fn predict(
+        &self,
+        sim: &RISCVSimulator,
+        idx: usize,
+        deps: &Deps,
+        opds: &[Operand<RISCV>],
+    ) -> usize {
+        let target = |offset: Operand<RISCV>| {
+            let addr = sim.to_text_addr(idx).wrapping_add(offset as u32);
+            sim.to_text_idx(addr)
+                .unwrap_or(sim.inst.as_ref().unwrap().instruction.len())
+        };
+        match (self.prediction, deps.control) {
+            (StaticPrediction::Btfn, Control::Jal) => target(opds[1]),
+            (StaticPrediction::Btfn, Control::Branch) if opds[2] < 0 => target(opds[2]),
+            _ => idx + 1,
+        }
+    }
+}
+
+/// The registers `op` reads and writes given its operands as laid out by the
+/// assembler.
+pub(super) fn deps(op: ParserRISCVInstOp, opds: &[Operand<RISCV>]) -> Deps {
+    use RV32IInstruction::*;
+
+    let mut deps = Deps {
+        rd: 0,
+        rs: [0; 2],
+        load: false,
+        control: Control::None,
+    };
+    let op = match op {
+        ParserRISCVInstOp::RV32C(op) => ParserRISCVInstOp::RV32I(op.base()),
+        op => op,
+    };
+    match op {
+        ParserRISCVInstOp::RV32I(Lb | Lbu | Lh | Lhu | Lw)
+        | ParserRISCVInstOp::RV64I(RV64IInstruction::Ld | RV64IInstruction::Lwu) => {
+            deps.rd = opds[0];
+            deps.rs[0] = opds[2];
+            deps.load = true;
+        }
+        ParserRISCVInstOp::RV32I(Sb | Sh | Sw) | ParserRISCVInstOp::RV64I(RV64IInstruction::Sd) => {
+            deps.rs = [opds[0], opds[2]];
+        }
+        ParserRISCVInstOp::RV32I(Beq | Bge | Bgeu | Blt | Bltu | Bne) => {
+            deps.rs = [opds[0], opds[1]];
+            deps.control = Control::Branch;
+        }
+        ParserRISCVInstOp::RV32I(Jal) => {
+            deps.rd = opds[0];
+            deps.control = Control::Jal;
+        }
+        ParserRISCVInstOp::RV32I(Jalr) => {
+            deps.rd = opds[0];
+            deps.rs[0] = opds[1];
+            deps.control = Control::Jalr;
+        }
+        ParserRISCVInstOp::RV32I(Lui | Auipc) => deps.rd = opds[0],
+        ParserRISCVInstOp::RV32I(Csrrc | Csrrs | Csrrw) => {
+            deps.rd = opds[0];
+            deps.rs[0] = opds[2];
+        }
+        ParserRISCVInstOp::RV32I(Csrrci | Csrrsi | Csrrwi) => deps.rd = opds[0],
+        ParserRISCVInstOp::RV32I(Ecall) => {
+            deps.rd = RV32IRegister::A0 as Operand<RISCV>;
+            deps.rs = [
+                RV32IRegister::A7 as Operand<RISCV>,
+                RV32IRegister::A0 as Operand<RISCV>,
+            ];
+        }
+        ParserRISCVInstOp::RV32I(Ebreak | Fence | FenceI) => {}
+        ParserRISCVInstOp::RV32I(Addi | Andi | Ori | Slli | Slti | Sltiu | Srai | Srli | Xori)
+        | ParserRISCVInstOp::RV64I(
+            RV64IInstruction::Addiw
+            | RV64IInstruction::Slliw
+            | RV64IInstruction::Sraiw
+            | RV64IInstruction::Srliw,
+        )
+        | ParserRISCVInstOp::RVB(
+            RVBInstruction::Bclri
+            | RVBInstruction::Bexti
+            | RVBInstruction::Binvi
+            | RVBInstruction::Bseti
+            | RVBInstruction::Rori
+            | RVBInstruction::Roriw
+            | RVBInstruction::SlliUw,
+        ) => {
+            deps.rd = opds[0];
+            deps.rs[0] = opds[1];
+        }
+        // atomics read and write memory, their result comes from MEM
+        ParserRISCVInstOp::RV32A(_) => {
+            deps.rd = opds[0];
+            deps.rs = [opds[1], opds[2]];
+            deps.load = true;
+        }
+        // register-register operations, unary bit manipulations have one
+        // source
+        _ => {
+            deps.rd = opds.first().copied().unwrap_or(0);
+            deps.rs[0] = opds.get(1).copied().unwrap_or(0);
+            deps.rs[1] = opds.get(2).copied().unwrap_or(0);
+        }
+    }
+    deps
+}
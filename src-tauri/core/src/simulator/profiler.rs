@@ -0,0 +1,99 @@
+use crate::{
+    interface::assembler::InstructionSet,
+    modules::riscv::{
+        basic::interface::parser::{ParserRISCVInstOp, RISCV},
+        rv32a::constants::RV32AInstruction,
+        rv32i::constants::RV32IInstruction,
+        rv64i::constants::RV64IInstruction,
+    },
+    types::middleware_types::{InstructionMix, InstructionProfile, LineProfile, ProfileReport},
+};
+
+/// Execution counts of the loaded program, undo takes instructions back.
+pub(super) struct Profiler {
+    // by instruction index
+    counts: Vec<u64>,
+    // also counts instructions fetched outside the text
+    retired: u64,
+    mix: InstructionMix,
+}
+
+impl Profiler {
+    pub fn new(len: usize) -> Self {
+        Profiler {
+            counts: vec![0; len],
+            retired: 0,
+            mix: InstructionMix::default(),
+        }
+    }
+
+    /// Instructions fetched outside the text have no count of their own.
+    pub fn retire(&mut self, idx: usize, op: ParserRISCVInstOp) {
+        if let Some(count) = self.counts.get_mut(idx) {
+            *count += 1;
+        }
+        self.retired += 1;
+        *class(&mut self.mix, op) += 1;
+    }
+
+    pub fn unretire(&mut self, idx: usize, op: ParserRISCVInstOp) {
+        if let Some(count) = self.counts.get_mut(idx) {
+            *count -= 1;
+        }
+        self.retired -= 1;
+        *class(&mut self.mix, op) -= 1;
+    }
+
+    pub fn report(&self, inst: &[InstructionSet<RISCV>], text_addr: &[u32]) -> ProfileReport {
+        let instructions: Vec<InstructionProfile> = inst
+            .iter()
+            .zip(text_addr)
+            .zip(&self.counts)
+            .map(|((inst, &address), &count)| InstructionProfile {
+                address,
+                line: inst.line_number,
+                count,
+            })
+            .collect();
+        let mut lines: Vec<LineProfile> = Vec::new();
+        for inst in instructions.iter().filter(|inst| inst.count != 0) {
+            // pseudo instructions expand to consecutive instructions
+            match lines.iter_mut().find(|line| line.line == inst.line) {
+                Some(line) => line.count += inst.count,
+                None => lines.push(LineProfile {
+                    line: inst.line,
+                    count: inst.count,
+                }),
+            }
+        }
+        lines.sort_by(|a, b| b.count.cmp(&a.count).then(a.line.cmp(&b.line)));
+        ProfileReport {
+            retired: self.retired,
+            mix: self.mix,
+            lines,
+            instructions,
+        }
+    }
+}
+
+/// The counter of the class `op` belongs to.
+fn class(mix: &mut InstructionMix, op: ParserRISCVInstOp) -> &mut u64 {
+    use RV32IInstruction::*;
+
+    let op = match op {
+        ParserRISCVInstOp::RV32C(op) => ParserRISCVInstOp::RV32I(op.base()),
+        op => op,
+    };
+    match op {
+        ParserRISCVInstOp::RV32I(Lb | Lbu | Lh | Lhu | Lw)
+        | ParserRISCVInstOp::RV64I(RV64IInstruction::Ld | RV64IInstruction::Lwu)
+        | ParserRISCVInstOp::RV32A(RV32AInstruction::LrW) => &mut mix.load,
+        ParserRISCVInstOp::RV32I(Sb | Sh | Sw)
+        | ParserRISCVInstOp::RV64I(RV64IInstruction::Sd)
+        | ParserRISCVInstOp::RV32A(_) => &mut mix.store,
+        ParserRISCVInstOp::RV32I(Beq | Bge | Bgeu | Blt | Bltu | Bne) => &mut mix.branch,
+        ParserRISCVInstOp::RV32I(Jal | Jalr) => &mut mix.jump,
+        ParserRISCVInstOp::RV32I(Ecall | Ebreak) => &mut mix.ecall,
+        _ => &mut mix.alu,
+    }
+}
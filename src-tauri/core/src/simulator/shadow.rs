@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use super::memory::PAGE_SIZE;
+use crate::{
+    modules::riscv::basic::interface::parser::RV32IRegister,
+    types::middleware_types::UninitRead,
+};
+
+type ShadowPage = [u64; PAGE_SIZE / 64];
+
+/// What a step changed, undo puts it back.
+pub(super) struct ShadowUndo {
+    hart: usize,
+    regs: u32,
+    marked: Vec<u32>,
+    reads: usize,
+}
+
+/// Initialised bits of every register and memory byte, like the shadow
+/// memory of Valgrind. Bytes the program is loaded with are initialised, the
+/// rest of the memory and every register but `zero` and `sp` are not until
+/// written.
+pub(super) struct Shadow {
+    // by hart, bit `n` is `xn`
+    regs: Vec<u32>,
+    // by page address, bit `n` is the byte at offset `n`
+    pages: HashMap<u32, Box<ShadowPage>>,
+    // bytes initialised since `begin`
+    marked: Vec<u32>,
+    reads: Vec<UninitRead>,
+}
+
+impl Shadow {
+    pub fn new(harts: usize) -> Self {
+        Shadow {
+            regs: vec![1 | 1 << RV32IRegister::Sp as usize; harts],
+            pages: HashMap::new(),
+            marked: Vec::new(),
+            reads: Vec::new(),
+        }
+    }
+
+    pub fn reg(&self, hart: usize, idx: usize) -> bool {
+        self.regs[hart] & (1 << idx) != 0
+    }
+
+    pub fn init_reg(&mut self, hart: usize, idx: usize) {
+        self.regs[hart] |= 1 << idx;
+    }
+
+    pub fn init_regs(&mut self) {
+        self.regs.fill(u32::MAX);
+    }
+
+    /// Address of the first uninitialised byte of `[addr, addr + len)`.
+    pub fn uninit(&self, addr: u32, len: u32) -> Option<u32> {
+        (addr..=addr.saturating_add(len - 1)).find(|&addr| {
+            self.pages
+                .get(&(addr & !(PAGE_SIZE as u32 - 1)))
+                .map_or(true, |page| {
+                    let offset = addr as usize % PAGE_SIZE;
+                    page[offset / 64] & (1 << (offset % 64)) == 0
+                })
+        })
+    }
+
+    pub fn init_mem(&mut self, addr: u32, len: u32) {
+        for addr in (addr as u64..addr as u64 + len as u64).map(|addr| addr as u32) {
+            let page = self
+                .pages
+                .entry(addr & !(PAGE_SIZE as u32 - 1))
+                .or_insert_with(|| Box::new([0; PAGE_SIZE / 64]));
+            let offset = addr as usize % PAGE_SIZE;
+            if page[offset / 64] & (1 << (offset % 64)) == 0 {
+                page[offset / 64] |= 1 << (offset % 64);
+                self.marked.push(addr);
+            }
+        }
+    }
+
+    pub fn report(&mut self, read: UninitRead) {
+        self.reads.push(read);
+    }
+
+    pub fn reads(&self) -> &[UninitRead] {
+        &self.reads
+    }
+
+    /// Reads reported since `undo` was begun.
+    pub fn reads_since(&self, undo: &ShadowUndo) -> &[UninitRead] {
+        &self.reads[undo.reads..]
+    }
+
+    /// Starts recording a step of `hart` for undo.
+    pub fn begin(&mut self, hart: usize) -> ShadowUndo {
+        self.marked.clear();
+        ShadowUndo {
+            hart,
+            regs: self.regs[hart],
+            marked: Vec::new(),
+            reads: self.reads.len(),
+        }
+    }
+
+    pub fn end(&mut self, undo: &mut ShadowUndo) {
+        undo.marked = std::mem::take(&mut self.marked);
+    }
+
+    pub fn undo(&mut self, undo: ShadowUndo) {
+        self.regs[undo.hart] = undo.regs;
+        for addr in undo.marked {
+            let page = self
+                .pages
+                .get_mut(&(addr & !(PAGE_SIZE as u32 - 1)))
+                .unwrap();
+            let offset = addr as usize % PAGE_SIZE;
+            page[offset / 64] &= !(1 << (offset % 64));
+        }
+        self.reads.truncate(undo.reads);
+    }
+}
@@ -0,0 +1,2157 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, AtomicU8},
+        Arc,
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use strum::VariantArray;
+
+use super::{
+    abi::{AbiChecker, AbiUndo},
+    branch::{BranchKind, BranchPredictor},
+    cache::Cache,
+    changes::ChangeTracker,
+    decode::{self, DecodeCache},
+    instruction::{
+        InstHandler,
+        InstHandlerArg,
+        INST_HANDLER_MAP,
+        RV32A_INST_HANDLER_MAP,
+        RV64I_INST_HANDLER_MAP,
+        RVB_INST_HANDLER_MAP,
+    },
+    memory::{Memory, PAGE_SIZE},
+    pacer::Pacer,
+    pipeline::{self, Pipeline},
+    profiler::Profiler,
+    shadow::{Shadow, ShadowUndo},
+    trace::Trace,
+    watch::{self, Machine},
+};
+use crate::{
+    interface::{
+        assembler::{AssembleResult, AssembleSection, InstructionSet},
+        simulator::{Simulator, SimulatorEventSink},
+    },
+    modules::riscv::basic::interface::parser::{ParserRISCVInstOp, RV32IRegister, RISCV},
+    types::middleware_types::{
+        AbiWarning,
+        AssemblerConfig,
+        BranchPredictorData,
+        BreakpointSnapshot,
+        CacheData,
+        DisplayFormat,
+        ElementWidth,
+        Endianness,
+        ExecutionMode,
+        HartData,
+        HartScheduler,
+        HartSnapshot,
+        InputKind,
+        MemoryDelta,
+        MemoryFault,
+        MemoryFaultKind,
+        MemoryReturnRange,
+        MemoryView,
+        MemoryViewData,
+        Optional,
+        PageSnapshot,
+        PipelineData,
+        ProfileReport,
+        Register,
+        RunLimit,
+        SimulatorDelta,
+        Snapshot,
+        TraceEntry,
+        TraceFormat,
+        TraceMemAccess,
+        TraceRegWrite,
+        UninitCheck,
+        UninitRead,
+        UninitSource,
+        WatchData,
+        WatchValue,
+        Xlen,
+        SNAPSHOT_VERSION,
+    },
+    utility::ptr::Ptr,
+};
+
+pub const MAX_HISTORY_SIZE: usize = 100;
+pub const MAX_HART_COUNT: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq, VariantArray)]
+pub(super) enum SimulatorStatus {
+    Unloaded = 0,
+    Loading = 1,
+    Running = 2,
+    Paused = 3,
+    Stopped = 4,
+    Stopping = 5,
+    Undo = 6,
+    Edit = 7,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum WaitStatus {
+    Not,
+    Int,
+    String,
+    Char,
+}
+
+/// State private to a hart, the memory is shared by all harts.
+pub(super) struct Hart {
+    // values are kept truncated to XLEN bits
+    pub reg: [u64; 32],
+    pub pc_idx: usize,
+    // address reserved by `lr.w`, stores to it from any hart and traps drop
+    // the reservation
+    pub reservation: Option<u32>,
+}
+
+/// Which hart runs next, restored by undo to keep the interleaving
+/// deterministic.
+#[derive(Clone, Copy)]
+pub(super) struct Schedule {
+    // the hart executing instructions, its id is `mhartid`
+    pub hart: usize,
+    // instructions left in the quantum of `hart`
+    pub left: u64,
+    pub rng: u64,
+}
+
+pub struct RISCVSimulator {
+    pub(super) harts: Vec<Hart>,
+    pub(super) sched: Schedule,
+    // timing model in pipeline mode
+    pipeline: Option<Pipeline>,
+    pub(super) mem: Memory,
+    icache: Option<Cache>,
+    dcache: Option<Cache>,
+    branch_predictor: Option<BranchPredictor>,
+    profiler: Option<Profiler>,
+    trace: Option<Trace>,
+    abi: Option<AbiChecker>,
+    shadow: Option<Shadow>,
+    // instructions fetched from memory in self-modifying mode
+    pub(super) decoded: Option<DecodeCache>,
+    // message of the next pause, the default is "paused"
+    pause_reason: Option<String>,
+    // instructions retired and time spent running since the last reset,
+    // without the sleeps of animated runs
+    retired: u64,
+    run_time: Duration,
+    exceeded_limit: Option<RunLimit>,
+    fault: Option<MemoryFault>,
+    // instructions per second of `run`, `debug` and `resume`, 0 is flat out
+    run_rate: AtomicU64,
+    changes: Mutex<ChangeTracker>,
+    pub(super) conf: AssemblerConfig,
+    pub(super) inst: Option<AssembleResult<RISCV>>,
+    // address of every instruction, with the end of the text at the end
+    text_addr: Vec<u32>,
+    pub(super) file: String,
+    pub(super) wait_input: WaitStatus,
+    // bit `n` is set if hart `n` breaks at the instruction
+    breakpoints: Vec<u64>,
+    debug: bool,
+    thread: Option<std::thread::JoinHandle<()>>,
+    status: AtomicU8,
+    history: VecDeque<History>,
+    mem_range: MemoryReturnRange,
+    views: Vec<MemoryView>,
+    watches: Vec<(String, Result<watch::Expr, String>)>,
+    event_sink: Option<Arc<dyn SimulatorEventSink>>,
+}
+
+pub(super) struct History {
+    pub sched: Schedule,
+    pub reg_idx: i32,
+    pub reg_val: u64,
+    pub pc_idx: usize,
+    pub mem_addr: u32,
+    pub mem: [u8; 8],
+    pub mem_len: u8,
+    pub reservation: Option<u32>,
+    // reservations of other harts dropped by a store
+    pub dropped_reservations: Vec<(usize, u32)>,
+    // pipeline before the cycle fetching the instruction, undo rewinds to it
+    pub pipeline: Option<Pipeline>,
+    // the handler succeeded, the profiler and the trace count it
+    pub retired: bool,
+    pub abi: Option<AbiUndo>,
+    pub shadow: Option<ShadowUndo>,
+}
+
+impl Hart {
+    fn new() -> Self {
+        Hart {
+            reg: [0; 32],
+            pc_idx: 0,
+            reservation: None,
+        }
+    }
+
+    /// Whether the reservation overlaps `[addr, addr + len)`.
+    pub(super) fn reserves(&self, addr: u32, len: u32) -> bool {
+        self.reservation.map_or(false, |reserved| {
+            addr < reserved.saturating_add(4) && reserved < addr.saturating_add(len)
+        })
+    }
+}
+
+impl RISCVSimulator {
+    pub fn new(file: &str) -> Self {
+        RISCVSimulator {
+            harts: vec![Hart::new()],
+            sched: Schedule {
+                hart: 0,
+                left: 0,
+                rng: 0,
+            },
+            pipeline: None,
+            mem: Memory::new(),
+            icache: None,
+            dcache: None,
+            branch_predictor: None,
+            profiler: None,
+            trace: None,
+            abi: None,
+            shadow: None,
+            decoded: None,
+            pause_reason: None,
+            retired: 0,
+            run_time: Duration::ZERO,
+            exceeded_limit: None,
+            fault: None,
+            run_rate: AtomicU64::new(0),
+            changes: Mutex::new(ChangeTracker::new()),
+            conf: Default::default(),
+            inst: None,
+            text_addr: Vec::new(),
+            breakpoints: Vec::new(),
+            debug: false,
+            wait_input: WaitStatus::Not,
+            thread: None,
+            status: AtomicU8::new(0),
+            file: file.to_string(),
+            history: VecDeque::with_capacity(MAX_HISTORY_SIZE),
+            mem_range: Default::default(),
+            views: Vec::new(),
+            watches: Vec::new(),
+            event_sink: None,
+        }
+    }
+
+    pub(super) fn xlen_bits(&self) -> u32 {
+        match self.conf.xlen {
+            Xlen::Rv32 => 32,
+            Xlen::Rv64 => 64,
+        }
+    }
+
+    /// The low XLEN bits of `val`.
+    pub(super) fn truncate(&self, val: u64) -> u64 {
+        match self.conf.xlen {
+            Xlen::Rv32 => val as u32 as u64,
+            Xlen::Rv64 => val,
+        }
+    }
+
+    /// `val` sign extended from XLEN bits.
+    pub(super) fn sext(&self, val: u64) -> i64 {
+        match self.conf.xlen {
+            Xlen::Rv32 => val as i32 as i64,
+            Xlen::Rv64 => val as i64,
+        }
+    }
+
+    /// The hart executing instructions.
+    pub(super) fn hart(&self) -> &Hart {
+        &self.harts[self.sched.hart]
+    }
+
+    pub(super) fn hart_mut(&mut self) -> &mut Hart {
+        &mut self.harts[self.sched.hart]
+    }
+
+    /// Drops the reservation of every hart overlapping `[addr, addr + len)`.
+    pub(super) fn invalidate_reservation(&mut self, addr: u32, len: u32) {
+        for hart in &mut self.harts {
+            if hart.reserves(addr, len) {
+                hart.reservation = None;
+            }
+        }
+    }
+
+    /// Applies a debugger edit to a paused or stopped simulator, undo reverts
+    /// it like an instruction which did not retire.
+    fn edit(
+        &mut self,
+        message: &str,
+        f: impl FnOnce(&mut Self, &mut History) -> Result<(), String>,
+    ) -> Result<(), String> {
+        if self.wait_input != WaitStatus::Not {
+            return Err("Waiting for input".to_string());
+        }
+        let status = if self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Edit) {
+            SimulatorStatus::Stopped
+        } else if self.cas_status(SimulatorStatus::Paused, SimulatorStatus::Edit) {
+            SimulatorStatus::Paused
+        } else {
+            return Err("Invalid operation".to_string());
+        };
+        let mut history = History {
+            sched: self.sched,
+            reg_idx: -1,
+            reg_val: 0,
+            pc_idx: self.hart().pc_idx,
+            mem_addr: 0,
+            mem: [0; 8],
+            mem_len: 0,
+            reservation: self.hart().reservation,
+            dropped_reservations: Vec::new(),
+            pipeline: None,
+            retired: false,
+            abi: None,
+            shadow: None,
+        };
+        if let Err(e) = f(self, &mut history) {
+            self.set_status(status);
+            return Err(e);
+        }
+        if self.history.len() == MAX_HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back(history);
+        self.set_status(SimulatorStatus::Paused);
+        self.update(Optional {
+            success: true,
+            message: message.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Swaps little-endian `bytes` of a value into the data byte order, or
+    /// back.
+    pub(super) fn data_order(&self, bytes: &mut [u8]) {
+        if self.conf.data_endianness == Endianness::Big {
+            bytes.reverse();
+        }
+    }
+
+    /// Index of the instruction at `addr`, the end of the text is the index
+    /// of a finished hart.
+    fn pc_idx_at(&self, addr: u32) -> Option<usize> {
+        self.to_text_idx(addr).or_else(|| {
+            let end = self.text_addr.len().checked_sub(1)?;
+            (self.text_addr[end] == addr).then(|| end)
+        })
+    }
+
+    fn decode_page(page: &PageSnapshot) -> Option<Vec<u8>> {
+        if page.address as usize % PAGE_SIZE != 0
+            || page.data.len() != PAGE_SIZE * 2
+            || !page.data.is_ascii()
+        {
+            return None;
+        }
+        (0..page.data.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&page.data[i..i + 2], 16).ok())
+            .collect()
+    }
+
+    /// Parses `snapshot` and checks it against the program and the config.
+    fn parse_snapshot(&self, snapshot: &str) -> Result<Snapshot, String> {
+        let value: serde_json::Value = serde_json::from_str(snapshot).map_err(|e| e.to_string())?;
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or("Not a snapshot")?;
+        if version > SNAPSHOT_VERSION as u64 {
+            return Err(format!(
+                "Snapshot version {} is newer than the supported version {}",
+                version, SNAPSHOT_VERSION
+            ));
+        }
+        let snapshot: Snapshot = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        if snapshot.xlen != self.conf.xlen {
+            return Err("The snapshot was taken with a different XLEN".to_string());
+        }
+        let inst = &self.inst.as_ref().unwrap().instruction;
+        if snapshot.text.len() != inst.len()
+            || snapshot
+                .text
+                .iter()
+                .zip(inst)
+                .any(|(&code, line)| code != line.code)
+        {
+            return Err("The snapshot belongs to a different program".to_string());
+        }
+        if snapshot.harts.len() != self.harts.len() {
+            return Err(format!(
+                "The snapshot has {} harts, the config {}",
+                snapshot.harts.len(),
+                self.harts.len()
+            ));
+        }
+        if snapshot.current_hart as usize >= self.harts.len() {
+            return Err("Invalid hart index".to_string());
+        }
+        for hart in &snapshot.harts {
+            if hart.registers.len() != 32 {
+                return Err("Invalid register count".to_string());
+            }
+            // any instruction can be fetched from memory in self-modifying mode
+            if self.pc_idx_at(hart.pc).is_none() && (self.decoded.is_none() || hart.pc % 2 != 0) {
+                return Err(format!("No instruction at 0x{:08x}", hart.pc));
+            }
+        }
+        for page in &snapshot.memory {
+            if Self::decode_page(page).is_none() {
+                return Err(format!("Invalid memory page at 0x{:08x}", page.address));
+            }
+        }
+        for breakpoint in &snapshot.breakpoints {
+            if self.to_text_idx(breakpoint.pc).is_none() {
+                return Err(format!("No instruction at 0x{:08x}", breakpoint.pc));
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// Bytes of `[start, start + len)`, with the encodings of the
+    /// instructions in the text.
+    fn memory_bytes(&self, start: u32, len: u32) -> Vec<u8> {
+        let end = start as u64 + len as u64;
+
+        let mut data = self.mem.get_range(start, len);
+        if self.decoded.is_some() {
+            return data;
+        }
+        // instructions are not stored in memory, overlay their encodings
+        let text = &self.inst.as_ref().unwrap().instruction;
+        let first = self.text_addr[..text.len()]
+            .partition_point(|&addr| addr < start)
+            .saturating_sub(1);
+        for (line, &addr) in text[first..].iter().zip(&self.text_addr[first..]) {
+            if addr as u64 >= end {
+                break;
+            }
+            let size = decode::size(line.instruction.operation) as usize;
+            for (i, byte) in line.code.to_le_bytes()[..size].iter().enumerate() {
+                let byte_addr = addr as u64 + i as u64;
+                if byte_addr >= start as u64 && byte_addr < end {
+                    data[(byte_addr - start as u64) as usize] = *byte;
+                }
+            }
+        }
+        data
+    }
+
+    /// Words of `[start, start + len)` in the data byte order, words in
+    /// the text hold instructions which are always little-endian.
+    fn memory_words(&self, start: u32, len: u32) -> Vec<u32> {
+        let (text_start, text_len) = self.text_range();
+        let text_end = text_start as u64 + text_len as u64;
+        self.memory_bytes(start, len)
+            .chunks(4)
+            .enumerate()
+            .map(|(i, data)| {
+                let addr = start as u64 + i as u64 * 4;
+                let mut word = [data[0], data[1], data[2], data[3]];
+                if addr + 4 <= text_start as u64 || addr >= text_end {
+                    self.data_order(&mut word);
+                }
+                u32::from_le_bytes(word)
+            })
+            .collect()
+    }
+
+    fn view_data(&self, view: &MemoryView) -> MemoryViewData {
+        MemoryViewData {
+            name: view.name.clone(),
+            start: view.start,
+            width: view.width,
+            format: view.format,
+            endianness: view.endianness,
+            data: self
+                .memory_bytes(view.start as u32, view.len as u32)
+                .chunks(view.width.bytes() as usize)
+                .map(|data| match view.endianness {
+                    Endianness::Little => data
+                        .iter()
+                        .rev()
+                        .fold(0, |value, &byte| value << 8 | byte as u32),
+                    Endianness::Big => data.iter().fold(0, |value, &byte| value << 8 | byte as u32),
+                })
+                .collect(),
+        }
+    }
+
+    /// Forgets the changes after a memory window changed and shows it if the
+    /// simulator is idle.
+    fn windows_changed(&mut self, message: &str) {
+        // words entering a window are unknown to the front end
+        self.changes.lock().unwrap().invalidate();
+        if self.get_status() == SimulatorStatus::Stopped
+            || self.get_status() == SimulatorStatus::Paused
+        {
+            self.update(Optional {
+                success: true,
+                message: message.to_string(),
+            });
+        }
+    }
+
+    /// Records a load or store of `[addr, addr + len)` in the data cache, the
+    /// trace and the changes, after the memory is accessed.
+    pub(super) fn access_memory(&mut self, addr: u32, len: u32, write: bool) {
+        if write {
+            self.changes.lock().unwrap().mem(addr, len);
+            self.code_written(addr, len);
+        }
+        if let Some(dcache) = &mut self.dcache {
+            dcache.access(addr, len, write);
+        }
+        if let Some(trace) = &mut self.trace {
+            // the value loaded or stored
+            let mut data = self.mem.get_range(addr, len);
+            if self.conf.data_endianness == Endianness::Big {
+                data.reverse();
+            }
+            trace.mem_access(addr, &data, write);
+        }
+        let uninit = match &mut self.shadow {
+            Some(shadow) => {
+                let uninit = shadow.uninit(addr, len).filter(|_| !write);
+                // reported once
+                shadow.init_mem(addr, len);
+                uninit
+            }
+            None => None,
+        };
+        if let Some(addr) = uninit {
+            self.report_uninit(UninitSource::Memory(addr));
+        }
+    }
+
+    /// Records a read of register `idx` of the current hart, it is reported
+    /// if never written.
+    pub(super) fn read_register(&mut self, idx: usize) {
+        let hart = self.sched.hart;
+        match &mut self.shadow {
+            Some(shadow) if !shadow.reg(hart, idx) => {
+                // reported once
+                shadow.init_reg(hart, idx);
+                let name = RV32IRegister::VARIANTS[idx].to_string();
+                self.report_uninit(UninitSource::Register(name));
+            }
+            _ => {}
+        }
+    }
+
+    fn report_uninit(&mut self, source: UninitSource) {
+        let pc_idx = self.hart().pc_idx;
+        let line = self.line(pc_idx);
+        let what = match &source {
+            UninitSource::Register(name) => name.clone(),
+            UninitSource::Memory(addr) => format!("memory at 0x{:08x}", addr),
+        };
+        let read = UninitRead {
+            hart: self.sched.hart as u64,
+            pc: self.to_text_addr(pc_idx),
+            instruction: line.basic.clone(),
+            line: line.line_number,
+            source,
+            message: format!(
+                "`{}` on line {} reads uninitialised {}",
+                line.basic,
+                line.line_number + 1,
+                what
+            ),
+        };
+        self.shadow.as_mut().unwrap().report(read);
+    }
+
+    /// Records the outcome of the control transfer at `idx` in the branch
+    /// predictor.
+    pub(super) fn predict_branch(
+        &mut self,
+        idx: usize,
+        kind: BranchKind,
+        taken: bool,
+        target: u32,
+    ) {
+        let pc = self.to_text_addr(idx);
+        if let Some(predictor) = &mut self.branch_predictor {
+            predictor.update(idx, pc, kind, taken, target);
+        }
+    }
+
+    pub(super) fn in_data_segment(&self, addr: u32, len: u32) -> bool {
+        let data_start = self.conf.dot_data_base_address as u32;
+        let data_end = self.conf.data_segment_limit_address as u32;
+        addr >= data_start && addr <= data_end && addr + (len - 1) <= data_end
+    }
+
+    pub(super) fn in_stack_segment(&self, addr: u32, len: u32) -> bool {
+        let stack_start = self.conf.stack_base_address as u32;
+        let stack_end = self.conf.stack_limit_address as u32;
+        addr <= stack_start && addr >= stack_end && addr - (len - 1) >= stack_end
+    }
+
+    pub(super) fn in_section(&self, addr: u32, len: u32) -> bool {
+        self.inst.as_ref().unwrap().sections.iter().any(|sec| {
+            addr >= sec.address && (addr - sec.address) as u64 + len as u64 <= sec.data.len() as u64
+        })
+    }
+
+    /// Whether `[addr, addr + len)` can be loaded and stored, the text can
+    /// only in self-modifying mode.
+    pub(super) fn in_memory(&self, addr: u32, len: u32) -> bool {
+        let (text_start, text_len) = self.text_range();
+        self.in_data_segment(addr, len)
+            || self.in_stack_segment(addr, len)
+            || self.in_section(addr, len)
+            || (self.decoded.is_some()
+                && addr >= text_start
+                && (addr - text_start) as u64 + len as u64 <= text_len as u64)
+    }
+
+    /// Records the access of `len` bytes at `addr` the current instruction
+    /// faulted at, the report's message is the error of the instruction.
+    pub(super) fn memory_fault(
+        &mut self,
+        kind: MemoryFaultKind,
+        addr: u64,
+        len: u32,
+        write: bool,
+    ) -> String {
+        let (text_start, text_len) = self.text_range();
+        // sections first, they win ties with the segments holding them
+        let mut segments: Vec<_> = self
+            .inst
+            .as_ref()
+            .unwrap()
+            .sections
+            .iter()
+            .map(|sec| {
+                (
+                    sec.name.clone(),
+                    sec.address as u64,
+                    sec.address as u64 + sec.data.len() as u64,
+                )
+            })
+            .collect();
+        segments.extend([
+            (
+                ".text".to_string(),
+                text_start as u64,
+                (text_start + text_len) as u64,
+            ),
+            (
+                ".data".to_string(),
+                self.conf.dot_data_base_address,
+                self.conf.data_segment_limit_address + 1,
+            ),
+            (
+                "stack".to_string(),
+                self.conf.stack_limit_address,
+                self.conf.stack_base_address + 1,
+            ),
+        ]);
+        let end = addr.saturating_add(len as u64);
+        let (segment, segment_start, segment_end) = segments
+            .into_iter()
+            .min_by_key(|(_, start, seg_end)| {
+                start.saturating_sub(addr).max(end.saturating_sub(*seg_end))
+            })
+            .unwrap();
+
+        let pc_idx = self.hart().pc_idx;
+        let pc = self.to_text_addr(pc_idx);
+        let line = self.line(pc_idx);
+        let sp = self.hart().reg[RV32IRegister::Sp as usize];
+        let stack_overflow = sp < self.conf.stack_limit_address;
+        let access = format!(
+            "{}-byte {} at 0x{:08x}",
+            len,
+            if write { "store" } else { "load" },
+            addr
+        );
+        let mut message = match kind {
+            MemoryFaultKind::OutOfRange => {
+                let place = if addr < segment_start {
+                    format!("0x{:x} bytes below {}", segment_start - addr, segment)
+                } else if end > segment_end {
+                    format!(
+                        "0x{:x} bytes past the end of {}",
+                        end - segment_end,
+                        segment
+                    )
+                } else {
+                    format!("inside {}, which can't be accessed", segment)
+                };
+                format!("Invalid memory access: {}, {}", access, place)
+            }
+            MemoryFaultKind::ReadOnly => {
+                format!("Write to read-only section {}: {}", segment, access)
+            }
+            MemoryFaultKind::Misaligned => format!("Misaligned atomic memory access: {}", access),
+        };
+        message += &format!(
+            " (0x{:08x}-0x{:08x}) by `{}` on line {}",
+            segment_start,
+            segment_end,
+            line.basic,
+            line.line_number + 1
+        );
+        if stack_overflow {
+            message += &format!(
+                ", sp = 0x{:08x} is below the stack limit 0x{:08x}, the stack overflowed",
+                sp, self.conf.stack_limit_address
+            );
+        }
+        self.fault = Some(MemoryFault {
+            kind,
+            address: addr,
+            width: len,
+            write,
+            segment,
+            segment_start,
+            segment_end,
+            pc,
+            instruction: line.basic.clone(),
+            line: line.line_number,
+            stack_overflow,
+            message: message.clone(),
+        });
+        message
+    }
+
+    /// Returns the read-only section overlapping `[addr, addr + len)` if any.
+    pub(super) fn in_read_only_section(&self, addr: u32, len: u32) -> Option<&AssembleSection> {
+        self.inst.as_ref().unwrap().sections.iter().find(|sec| {
+            sec.read_only
+                && (addr as u64) < sec.address as u64 + sec.data.len() as u64
+                && (sec.address as u64) < addr as u64 + len as u64
+        })
+    }
+
+    // (start, len)
+    pub(super) fn text_range(&self) -> (u32, u32) {
+        let text_start = self.conf.dot_text_base_address as u32;
+        (
+            text_start,
+            self.text_addr.last().map_or(0, |end| end - text_start),
+        )
+    }
+
+    pub(super) fn to_text_idx(&self, addr: u32) -> Option<usize> {
+        let len = self.text_addr.len().saturating_sub(1);
+        self.text_addr[..len].binary_search(&addr).ok()
+    }
+
+    /// Indices past the end of the text belong to instructions fetched
+    /// outside of it.
+    pub(super) fn to_text_addr(&self, idx: usize) -> u32 {
+        match self.text_addr.get(idx) {
+            Some(&addr) => addr,
+            None => self.decoded.as_ref().unwrap().addr(idx),
+        }
+    }
+
+    /// Index of the instruction a jump to `addr` lands on. In self-modifying
+    /// mode every even address has one, the end of the text included.
+    pub(super) fn jump_idx(&mut self, addr: u32) -> Option<usize> {
+        if self.decoded.is_none() {
+            return self.to_text_idx(addr);
+        }
+        if let Some(idx) = self.pc_idx_at(addr) {
+            return Some(idx);
+        }
+        (addr % 2 == 0).then(|| self.decoded.as_mut().unwrap().idx(addr))
+    }
+
+    /// The instruction at `idx`, as last fetched in self-modifying mode.
+    pub(super) fn line(&self, idx: usize) -> &InstructionSet<RISCV> {
+        self.decoded
+            .as_ref()
+            .and_then(|decoded| decoded.get(self.to_text_addr(idx)))
+            .unwrap_or_else(|| &self.inst.as_ref().unwrap().instruction[idx])
+    }
+
+    /// Moves the pc of the current hart past its instruction.
+    pub(super) fn step_pc(&mut self) {
+        let pc_idx = self.hart().pc_idx;
+        if self.decoded.is_none() {
+            self.hart_mut().pc_idx += 1;
+            return;
+        }
+        let next =
+            self.to_text_addr(pc_idx) + decode::size(self.line(pc_idx).instruction.operation);
+        self.hart_mut().pc_idx = self.jump_idx(next).unwrap();
+    }
+
+    /// Makes the instructions decoded from `[addr, addr + len)` stale, they
+    /// are decoded again when fetched.
+    fn code_written(&mut self, addr: u32, len: u32) {
+        if let Some(decoded) = &mut self.decoded {
+            decoded.invalidate(addr, len);
+        }
+    }
+
+    /// Decodes the instruction at the pc of the current hart from memory,
+    /// unless it is cached. An unmodified instruction of the text is the
+    /// assembled one.
+    fn fetch(&mut self) -> Result<(), String> {
+        let pc_idx = self.hart().pc_idx;
+        let addr = self.to_text_addr(pc_idx);
+        if self.decoded.as_ref().unwrap().is_fresh(addr) {
+            return Ok(());
+        }
+        let size = if self.mem[addr] & 0b11 == 0b11 { 4 } else { 2 };
+        if !self.in_memory(addr, size) {
+            return Err(format!("Invalid instruction fetch at 0x{:08x}", addr));
+        }
+        let code = self
+            .mem
+            .get_range(addr, size)
+            .iter()
+            .rev()
+            .fold(0, |code, &byte| code << 8 | byte as u32);
+        let text = self.inst.as_ref().unwrap().instruction.get(pc_idx);
+        let mut line = match text {
+            Some(line) if line.code == code => line.clone(),
+            _ => {
+                let mut line = decode::decode(code, self.conf.xlen).ok_or_else(|| {
+                    format!(
+                        "Illegal instruction 0x{:0width$x} at 0x{:08x}",
+                        code,
+                        addr,
+                        width = size as usize * 2
+                    )
+                })?;
+                // code outside the text has no source line
+                line.line_number = text.map_or(0, |line| line.line_number);
+                line
+            }
+        };
+        line.address = addr;
+        self.decoded.as_mut().unwrap().insert(line);
+        Ok(())
+    }
+
+    fn layout_text(&mut self) {
+        let mut addr = self.conf.dot_text_base_address as u32;
+        self.text_addr.clear();
+        for line in &self.inst.as_ref().unwrap().instruction {
+            self.text_addr.push(addr);
+            addr += decode::size(line.instruction.operation);
+        }
+        self.text_addr.push(addr);
+    }
+
+    /// Stores the encodings of the text in memory, in self-modifying mode.
+    fn load_text(&mut self) {
+        if self.decoded.is_none() {
+            return;
+        }
+        let text = &self.inst.as_ref().unwrap().instruction;
+        for (line, &addr) in text.iter().zip(&self.text_addr) {
+            let size = decode::size(line.instruction.operation) as usize;
+            self.mem.set_range(addr, &line.code.to_le_bytes()[..size]);
+        }
+    }
+
+    pub(super) fn request_input(&mut self, wait_status: WaitStatus) -> Result<(), String> {
+        self.wait_input = wait_status;
+        match &self.event_sink {
+            Some(sink) => sink.request_input(&self.file),
+            None => Err("No event sink to request input from".to_string()),
+        }
+    }
+
+    /// Completes the trace entry of the input ecall with what the input
+    /// wrote to `a0` or to memory.
+    fn trace_input(&mut self, input: &str, a0: Option<u64>, mem: Option<(u32, u32)>) {
+        let data = mem.map(|(addr, len)| (addr, self.mem.get_range(addr, len)));
+        if let Some(entry) = self.trace.as_mut().and_then(Trace::last_mut) {
+            entry.input = Some(input.to_string());
+            if let Some(value) = a0 {
+                entry.reg_writes.push(TraceRegWrite {
+                    reg: RV32IRegister::A0 as u8,
+                    value,
+                });
+            }
+            // long strings don't fit an access, keep them byte by byte
+            if let Some((addr, data)) = &data {
+                for (i, &byte) in data.iter().enumerate() {
+                    entry.mem_writes.push(TraceMemAccess {
+                        address: addr + i as u32,
+                        size: 1,
+                        value: byte as u64,
+                    });
+                }
+            }
+        }
+    }
+
+    pub(super) fn output(&mut self, msg: &str) -> Result<(), String> {
+        if let Some(entry) = self.trace.as_mut().and_then(Trace::pending_mut) {
+            entry.output += msg;
+        }
+        match &self.event_sink {
+            Some(sink) => sink.output(&self.file, msg),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Machine for RISCVSimulator {
+    fn reg(&self, idx: usize) -> u64 {
+        self.hart().reg[idx]
+    }
+
+    fn pc(&self) -> u64 {
+        let pc_idx = self.hart().pc_idx;
+        match (self.text_addr.get(pc_idx), &self.decoded) {
+            (Some(&addr), _) => addr as u64,
+            (None, Some(decoded)) => decoded.addr(pc_idx) as u64,
+            (None, None) => 0,
+        }
+    }
+
+    fn label(&self, name: &str) -> Option<u64> {
+        let labels = &self.inst.as_ref()?.labels;
+        labels.get(name).map(|&addr| addr as u64)
+    }
+
+    fn read(&self, addr: u32, len: u32) -> Vec<u8> {
+        self.memory_bytes(addr, len)
+    }
+
+    fn truncate(&self, val: u64) -> u64 {
+        RISCVSimulator::truncate(self, val)
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.conf.data_endianness
+    }
+}
+
+impl Simulator for RISCVSimulator {
+    fn load_inst(&mut self, inst: AssembleResult<RISCV>) -> Result<(), String> {
+        if !self.cas_status(SimulatorStatus::Unloaded, SimulatorStatus::Loading)
+            && !self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Loading)
+        {
+            return Err("Simulator is still running".to_string());
+        }
+        self.breakpoints = vec![0; inst.instruction.len()];
+        self.inst = Some(inst);
+        self._reset();
+        self.update(Optional {
+            success: true,
+            message: "instruction loaded".to_string(),
+        });
+        self.set_status(SimulatorStatus::Stopped);
+        Ok(())
+    }
+
+    fn get_raw_inst(&self) -> &Option<AssembleResult<RISCV>> {
+        &self.inst
+    }
+
+    fn update_config(&mut self, config: &AssemblerConfig) -> Result<(), String> {
+        let old_status;
+        if self.cas_status(SimulatorStatus::Unloaded, SimulatorStatus::Loading) {
+            old_status = SimulatorStatus::Unloaded;
+        } else if self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Loading) {
+            old_status = SimulatorStatus::Stopped;
+        } else {
+            return Err("Simulator is still running".to_string());
+        }
+        if config.dot_text_base_address % 4 != 0 {
+            self.set_status(old_status);
+            return Err("Invalid text base address".to_string());
+        }
+        if config.hart_count == 0 || config.hart_count > MAX_HART_COUNT as u64 {
+            self.set_status(old_status);
+            return Err("Invalid hart count".to_string());
+        }
+        if config.execution_mode == ExecutionMode::Pipeline && config.hart_count != 1 {
+            self.set_status(old_status);
+            return Err("The pipeline only supports a single hart".to_string());
+        }
+        if config.execution_mode == ExecutionMode::Pipeline && config.self_modifying_code {
+            self.set_status(old_status);
+            return Err("The pipeline doesn't support self-modifying code".to_string());
+        }
+        if !Cache::valid_config(&config.l1_icache) {
+            self.set_status(old_status);
+            return Err("Invalid L1 instruction cache config".to_string());
+        }
+        if !Cache::valid_config(&config.l1_dcache) {
+            self.set_status(old_status);
+            return Err("Invalid L1 data cache config".to_string());
+        }
+        if !BranchPredictor::valid_config(&config.branch_predictor) {
+            self.set_status(old_status);
+            return Err("Invalid branch predictor config".to_string());
+        }
+        self.conf = config.clone();
+        if old_status == SimulatorStatus::Stopped {
+            self._reset();
+            self.update(Optional {
+                success: true,
+                message: "config updated".to_string(),
+            });
+        }
+        self.set_status(old_status);
+        Ok(())
+    }
+
+    fn run(&mut self) -> Result<(), String> {
+        if !self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Running) {
+            return Err("Invalid operation".to_string());
+        }
+        self.debug = false;
+        self._reset();
+        self._start(None);
+        Ok(())
+    }
+
+    fn debug(&mut self) -> Result<(), String> {
+        if !self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Running) {
+            return Err("Invalid operation".to_string());
+        }
+        self.debug = true;
+        self._reset();
+        self._start(None);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), String> {
+        if !self.cas_status(SimulatorStatus::Running, SimulatorStatus::Stopping) {
+            return Err("Simulator not running".to_string());
+        }
+        if let Some(t) = self.thread.take() {
+            t.join().unwrap();
+        }
+        self.set_status(SimulatorStatus::Stopped);
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<(), String> {
+        if self.wait_input != WaitStatus::Not {
+            return Err("Waiting for input".to_string());
+        }
+        if !self.cas_status(SimulatorStatus::Paused, SimulatorStatus::Running) {
+            return Err("Simulator not paused".to_string());
+        }
+        self._start(None);
+        Ok(())
+    }
+
+    fn step(&mut self) -> Result<(), String> {
+        if !self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Running) {
+            if !self.cas_status(SimulatorStatus::Paused, SimulatorStatus::Running) {
+                return Err("Invalid operation".to_string());
+            } else {
+                if self.wait_input != WaitStatus::Not {
+                    self.set_status(SimulatorStatus::Paused);
+                    return Err("Waiting for input".to_string());
+                }
+            }
+        }
+        self._start(Some(1));
+        Ok(())
+    }
+
+    fn step_hart(&mut self, hart: usize) -> Result<(), String> {
+        if hart >= self.conf.hart_count as usize {
+            return Err("Invalid hart index".to_string());
+        }
+        if self.get_status() == SimulatorStatus::Paused && self.wait_input != WaitStatus::Not {
+            return Err("Waiting for input".to_string());
+        }
+        let status = if self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Running) {
+            SimulatorStatus::Stopped
+        } else if self.cas_status(SimulatorStatus::Paused, SimulatorStatus::Running) {
+            SimulatorStatus::Paused
+        } else {
+            return Err("Invalid operation".to_string());
+        };
+        if self.harts[hart].pc_idx == self.inst.as_ref().unwrap().instruction.len() {
+            self.set_status(status);
+            return Err(format!("Hart {} has finished", hart));
+        }
+        // the hart gets a new quantum, the scheduler carries on from it
+        self.sched.hart = hart;
+        self.sched.left = self.conf.hart_quantum.max(1);
+        self._start(Some(1));
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), String> {
+        if !self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Stopping)
+            && !self.cas_status(SimulatorStatus::Paused, SimulatorStatus::Stopping)
+        {
+            return Err("Invalid operation".to_string());
+        }
+        self._reset();
+        self.set_status(SimulatorStatus::Stopped);
+        self.update(Optional {
+            success: true,
+            message: "reset".to_string(),
+        });
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if self.history.is_empty() {
+            return Err("No history".to_string());
+        }
+        if self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Undo) {
+            if self.history.is_empty() {
+                self.set_status(SimulatorStatus::Stopped);
+                return Err("No history".to_string());
+            }
+        } else if self.cas_status(SimulatorStatus::Paused, SimulatorStatus::Undo) {
+            if self.history.is_empty() {
+                self.set_status(SimulatorStatus::Paused);
+                return Err("No history".to_string());
+            }
+        } else {
+            return Err("Invalid operation".to_string());
+        }
+        let h = self.history.pop_back().unwrap();
+        self.sched = h.sched;
+        let mut changes = self.changes.lock().unwrap();
+        if h.reg_idx != -1 {
+            changes.reg(self.sched.hart, h.reg_idx as usize);
+        }
+        if h.mem_len != 0 {
+            changes.mem(h.mem_addr, h.mem_len as u32);
+        }
+        drop(changes);
+        let hart = self.hart_mut();
+        if h.reg_idx != -1 {
+            hart.reg[h.reg_idx as usize] = h.reg_val;
+        }
+        hart.pc_idx = h.pc_idx;
+        hart.reservation = h.reservation;
+        if h.mem_len != 0 {
+            self.mem.set_range(h.mem_addr, &h.mem[..h.mem_len as usize]);
+            self.code_written(h.mem_addr, h.mem_len as u32);
+        }
+        for (idx, addr) in h.dropped_reservations {
+            self.harts[idx].reservation = Some(addr);
+        }
+        if h.pipeline.is_some() {
+            self.pipeline = h.pipeline;
+        }
+        if h.retired {
+            self.retired -= 1;
+        }
+        let op = h.retired.then(|| self.line(h.pc_idx).instruction.operation);
+        if let (Some(op), Some(profiler)) = (op, &mut self.profiler) {
+            profiler.unretire(h.pc_idx, op);
+        }
+        if let (true, Some(trace)) = (h.retired, &mut self.trace) {
+            trace.pop();
+        }
+        if let (Some(abi), Some(undo)) = (&mut self.abi, h.abi) {
+            abi.undo(undo);
+        }
+        if let (Some(shadow), Some(undo)) = (&mut self.shadow, h.shadow) {
+            shadow.undo(undo);
+        }
+        // only the last instruction can have faulted
+        self.fault = None;
+        self.set_status(SimulatorStatus::Paused);
+        self.update(Optional {
+            success: true,
+            message: "undo".to_string(),
+        });
+        Ok(())
+    }
+
+    fn set_breakpoint(&mut self, idx: usize, hart: Option<usize>) -> Result<(), String> {
+        let mask = Self::hart_mask(hart)?;
+        if idx >= self.breakpoints.len() {
+            return Err("Invalid breakpoint index".to_string());
+        }
+        self.breakpoints[idx] |= mask;
+        Ok(())
+    }
+
+    fn remove_breakpoint(&mut self, idx: usize, hart: Option<usize>) -> Result<(), String> {
+        let mask = Self::hart_mask(hart)?;
+        if idx >= self.breakpoints.len() {
+            return Err("Invalid breakpoint index".to_string());
+        }
+        self.breakpoints[idx] &= !mask;
+        Ok(())
+    }
+
+    fn syscall_input(&mut self, input: &str) -> Result<(), String> {
+        match self.wait_input {
+            WaitStatus::Not => Err("No input required".to_string()),
+            WaitStatus::Int => {
+                let val = match self.conf.xlen {
+                    Xlen::Rv32 => input.parse::<u32>().map(u64::from).ok(),
+                    Xlen::Rv64 => input.parse::<i64>().map(|val| val as u64).ok(),
+                };
+                if let Some(val) = val {
+                    self.hart_mut().reg[RV32IRegister::A0 as usize] = val;
+                    if let Some(shadow) = &mut self.shadow {
+                        shadow.init_reg(self.sched.hart, RV32IRegister::A0 as usize);
+                    }
+                    self.changes
+                        .lock()
+                        .unwrap()
+                        .reg(self.sched.hart, RV32IRegister::A0 as usize);
+                    self.trace_input(input, Some(val), None);
+                    self.wait_input = WaitStatus::Not;
+                    self.step_pc();
+                    self.resume()
+                } else {
+                    Err("Invalid input".to_string())
+                }
+            }
+            WaitStatus::String => {
+                let addr = self.hart().reg[RV32IRegister::A0 as usize] as u32;
+                let len = self.hart().reg[RV32IRegister::A1 as usize] as u32;
+                if !self.in_data_segment(addr, len) && !self.in_section(addr, len) {
+                    return Err("Invalid memory access".to_string());
+                }
+                if let Some(sec) = self.in_read_only_section(addr, len) {
+                    return Err(format!("Write to read-only section {}", sec.name));
+                }
+                let data = input.as_bytes();
+                self.mem.set_range(addr, &data[..len as usize]);
+                self.changes.lock().unwrap().mem(addr, len);
+                self.code_written(addr, len);
+                if let Some(shadow) = &mut self.shadow {
+                    shadow.init_mem(addr, len);
+                }
+                self.trace_input(input, None, Some((addr, len)));
+                self.invalidate_reservation(addr, len);
+                self.wait_input = WaitStatus::Not;
+                self.step_pc();
+                self.resume()
+            }
+            WaitStatus::Char => {
+                self.hart_mut().reg[RV32IRegister::A0 as usize] = input.as_bytes()[0] as u64;
+                if let Some(shadow) = &mut self.shadow {
+                    shadow.init_reg(self.sched.hart, RV32IRegister::A0 as usize);
+                }
+                self.changes
+                    .lock()
+                    .unwrap()
+                    .reg(self.sched.hart, RV32IRegister::A0 as usize);
+                self.trace_input(input, Some(input.as_bytes()[0] as u64), None);
+                self.wait_input = WaitStatus::Not;
+                self.step_pc();
+                self.resume()
+            }
+        }
+    }
+
+    fn set_register(&mut self, idx: usize, value: u64) -> Result<(), String> {
+        if idx == 0 || idx >= 32 {
+            return Err("Invalid register".to_string());
+        }
+        self.edit("register updated", |sim, history| {
+            let value = sim.truncate(value);
+            let hart = sim.sched.hart;
+            history.reg_idx = idx as i32;
+            history.reg_val = sim.harts[hart].reg[idx];
+            sim.harts[hart].reg[idx] = value;
+            sim.changes.lock().unwrap().reg(hart, idx);
+            if let Some(shadow) = &mut sim.shadow {
+                shadow.init_reg(hart, idx);
+            }
+            Ok(())
+        })
+    }
+
+    fn set_memory(&mut self, addr: u32, data: &[u8]) -> Result<(), String> {
+        let len = data.len() as u32;
+        if len == 0 || len > 8 {
+            return Err("Invalid length".to_string());
+        }
+        self.edit("memory updated", |sim, history| {
+            if let Some(sec) = sim.in_read_only_section(addr, len) {
+                return Err(format!(
+                    "Write to read-only section {} at 0x{:08x}",
+                    sec.name, addr
+                ));
+            }
+            if !sim.in_memory(addr, len) {
+                return Err("Invalid memory access".to_string());
+            }
+            history.mem_addr = addr;
+            history.mem_len = len as u8;
+            history.mem[..data.len()].copy_from_slice(&sim.mem.get_range(addr, len));
+            sim.mem.set_range(addr, data);
+            sim.code_written(addr, len);
+            if let Some(shadow) = &mut sim.shadow {
+                shadow.init_mem(addr, len);
+            }
+            let current = sim.sched.hart;
+            for (idx, hart) in sim.harts.iter_mut().enumerate() {
+                if hart.reserves(addr, len) {
+                    if idx != current {
+                        history
+                            .dropped_reservations
+                            .push((idx, hart.reservation.unwrap()));
+                    }
+                    hart.reservation = None;
+                }
+            }
+            sim.changes.lock().unwrap().mem(addr, len);
+            Ok(())
+        })
+    }
+
+    fn set_pc(&mut self, addr: u32) -> Result<(), String> {
+        let idx = self
+            .jump_idx(addr)
+            .ok_or(format!("No instruction at 0x{:08x}", addr))?;
+        self.edit("pc updated", |sim, history| {
+            sim.hart_mut().pc_idx = idx;
+            if let Some(pipeline) = &mut sim.pipeline {
+                history.pipeline = Some(pipeline.clone());
+                pipeline.redirect();
+            }
+            Ok(())
+        })
+    }
+
+    fn save_snapshot(&self) -> Result<String, String> {
+        let status = self.get_status();
+        if status != SimulatorStatus::Stopped && status != SimulatorStatus::Paused {
+            return Err("Invalid operation".to_string());
+        }
+        let snapshot = Snapshot {
+            version: SNAPSHOT_VERSION,
+            xlen: self.conf.xlen,
+            text: self
+                .inst
+                .as_ref()
+                .unwrap()
+                .instruction
+                .iter()
+                .map(|line| line.code)
+                .collect(),
+            harts: self
+                .harts
+                .iter()
+                .map(|hart| HartSnapshot {
+                    registers: hart.reg.to_vec(),
+                    pc: self.to_text_addr(hart.pc_idx),
+                    reservation: hart.reservation,
+                })
+                .collect(),
+            current_hart: self.sched.hart as u64,
+            quantum_left: self.sched.left,
+            scheduler_seed: self.sched.rng,
+            memory: self
+                .mem
+                .pages()
+                .into_iter()
+                .filter(|(_, page)| page.iter().any(|&byte| byte != 0))
+                .map(|(address, page)| PageSnapshot {
+                    address,
+                    data: page.iter().map(|byte| format!("{:02x}", byte)).collect(),
+                })
+                .collect(),
+            breakpoints: self
+                .breakpoints
+                .iter()
+                .enumerate()
+                .filter(|(_, &harts)| harts != 0)
+                .map(|(idx, &harts)| BreakpointSnapshot {
+                    pc: self.text_addr[idx],
+                    harts,
+                })
+                .collect(),
+            input: match self.wait_input {
+                WaitStatus::Not => None,
+                WaitStatus::Int => Some(InputKind::Int),
+                WaitStatus::String => Some(InputKind::String),
+                WaitStatus::Char => Some(InputKind::Char),
+            },
+        };
+        Ok(serde_json::to_string_pretty(&snapshot).unwrap())
+    }
+
+    fn restore_snapshot(&mut self, snapshot: &str) -> Result<(), String> {
+        let old_status = if self.cas_status(SimulatorStatus::Stopped, SimulatorStatus::Loading) {
+            SimulatorStatus::Stopped
+        } else if self.cas_status(SimulatorStatus::Paused, SimulatorStatus::Loading) {
+            SimulatorStatus::Paused
+        } else {
+            return Err("Invalid operation".to_string());
+        };
+        let snapshot = match self.parse_snapshot(snapshot) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                self.set_status(old_status);
+                return Err(e);
+            }
+        };
+        self._reset();
+        for (hart, saved) in self.harts.iter_mut().zip(&snapshot.harts) {
+            hart.reg.copy_from_slice(&saved.registers);
+            hart.reservation = saved.reservation;
+        }
+        for (idx, saved) in snapshot.harts.iter().enumerate() {
+            self.harts[idx].pc_idx = match self.pc_idx_at(saved.pc) {
+                Some(pc_idx) => pc_idx,
+                None => self.jump_idx(saved.pc).unwrap(),
+            };
+        }
+        self.sched = Schedule {
+            hart: snapshot.current_hart as usize,
+            left: snapshot.quantum_left,
+            rng: snapshot.scheduler_seed,
+        };
+        self.mem.reset();
+        // the text of a snapshot taken without self-modifying code isn't in
+        // its memory
+        self.load_text();
+        for page in &snapshot.memory {
+            self.mem
+                .set_range(page.address, &Self::decode_page(page).unwrap());
+        }
+        // shadow bits aren't saved, everything restored counts as written
+        if let Some(shadow) = &mut self.shadow {
+            shadow.init_regs();
+            for page in &snapshot.memory {
+                shadow.init_mem(page.address, PAGE_SIZE as u32);
+            }
+        }
+        self.breakpoints.fill(0);
+        for breakpoint in &snapshot.breakpoints {
+            let idx = self.to_text_idx(breakpoint.pc).unwrap();
+            self.breakpoints[idx] = breakpoint.harts;
+        }
+        self.set_status(SimulatorStatus::Paused);
+        // the host asks for the input before the update, as after the ecall
+        let res = match snapshot.input {
+            Some(InputKind::Int) => self.request_input(WaitStatus::Int),
+            Some(InputKind::String) => self.request_input(WaitStatus::String),
+            Some(InputKind::Char) => self.request_input(WaitStatus::Char),
+            None => Ok(()),
+        };
+        self.update(Optional {
+            success: true,
+            message: "snapshot restored".to_string(),
+        });
+        res
+    }
+
+    fn get_register(&self) -> &[u64] {
+        &self.hart().reg
+    }
+
+    fn get_hart_register(&self, hart: usize) -> &[u64] {
+        &self.harts[hart].reg
+    }
+
+    fn get_memory(&self) -> Vec<u32> {
+        self.memory_words(self.mem_range.start as u32, self.mem_range.len as u32)
+    }
+
+    fn take_delta(&self, res: Optional, paused: bool) -> SimulatorDelta {
+        let changes = self.changes.lock().unwrap().take();
+        let mut delta = SimulatorDelta {
+            filepath: self.file.clone(),
+            seq: changes.seq,
+            resync: changes.lost,
+            success: res.success,
+            paused,
+            message: res.message,
+            current_hart: self.sched.hart as u64,
+            harts: Vec::new(),
+            memory: Vec::new(),
+            watches: self.get_watches(),
+        };
+        if changes.lost {
+            return delta;
+        }
+        delta.harts = (0..self.harts.len())
+            .map(|hart| {
+                let dirty = changes.regs.get(hart).copied().unwrap_or(0);
+                HartData {
+                    id: hart as u64,
+                    has_current_text: self.get_hart_pc_idx(hart).is_some(),
+                    current_text: self.get_hart_pc_idx(hart).unwrap_or(0) as u64,
+                    registers: (0..32)
+                        .filter(|i| dirty & (1 << i) != 0)
+                        .map(|i| Register {
+                            name: RV32IRegister::VARIANTS[i].to_string(),
+                            number: i.to_string(),
+                            value: self.harts[hart].reg[i],
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        let windows = std::iter::once((self.mem_range.start, self.mem_range.len))
+            .chain(self.views.iter().map(|view| (view.start, view.len)));
+        let mut ranges = Vec::new();
+        for (window_start, window_len) in windows {
+            for &(start, end) in &changes.mem {
+                // whole words, views may not be aligned by 4
+                let start = start.max(window_start) & !3;
+                let end = (end.min(window_start + window_len) + 3) & !3;
+                if start < end {
+                    ranges.push((start, end));
+                }
+            }
+        }
+        // windows may overlap, send every word once
+        ranges.sort_unstable();
+        let mut merged: Vec<(u64, u64)> = Vec::new();
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        delta.memory = merged
+            .into_iter()
+            .map(|(start, end)| MemoryDelta {
+                start,
+                data: self.memory_words(start as u32, (end - start) as u32),
+            })
+            .collect();
+        delta
+    }
+
+    fn resync(&self) -> u64 {
+        self.changes.lock().unwrap().take().seq
+    }
+
+    fn get_pc_idx(&self) -> Option<usize> {
+        self.get_hart_pc_idx(self.sched.hart)
+    }
+
+    fn get_hart_pc_idx(&self, hart: usize) -> Option<usize> {
+        let pc_idx = self.harts[hart].pc_idx;
+        if pc_idx < self.inst.as_ref().unwrap().instruction.len() {
+            Some(pc_idx)
+        } else {
+            None
+        }
+    }
+
+    fn get_hart_count(&self) -> usize {
+        self.harts.len()
+    }
+
+    fn get_current_hart(&self) -> usize {
+        self.sched.hart
+    }
+
+    fn get_pipeline(&self) -> Option<PipelineData> {
+        self.pipeline.as_ref().map(Pipeline::data)
+    }
+
+    fn get_icache(&self) -> Option<CacheData> {
+        self.icache.as_ref().map(Cache::data)
+    }
+
+    fn get_dcache(&self) -> Option<CacheData> {
+        self.dcache.as_ref().map(Cache::data)
+    }
+
+    fn get_abi_warnings(&self) -> Option<Vec<AbiWarning>> {
+        self.abi.as_ref().map(AbiChecker::warnings)
+    }
+
+    fn get_uninit_reads(&self) -> Option<Vec<UninitRead>> {
+        self.shadow.as_ref().map(|shadow| shadow.reads().to_vec())
+    }
+
+    fn get_profile(&self) -> Option<ProfileReport> {
+        let inst = &self.inst.as_ref()?.instruction;
+        self.profiler
+            .as_ref()
+            .map(|profiler| profiler.report(inst, &self.text_addr))
+    }
+
+    fn export_trace(&self, format: TraceFormat) -> Result<String, String> {
+        let trace = self.trace.as_ref().ok_or("Trace is disabled")?;
+        Ok(match format {
+            TraceFormat::Jsonl => trace.jsonl(),
+            TraceFormat::Spike => trace.spike(self.xlen_bits()),
+        })
+    }
+
+    fn get_branch_predictor(&self) -> Option<BranchPredictorData> {
+        self.inst.as_ref()?;
+        self.branch_predictor
+            .as_ref()
+            .map(|predictor| predictor.data(|idx| self.line(idx).line_number))
+    }
+
+    fn get_exceeded_limit(&self) -> Option<RunLimit> {
+        self.exceeded_limit
+    }
+
+    fn get_memory_fault(&self) -> Option<MemoryFault> {
+        self.fault.clone()
+    }
+
+    fn set_run_rate(&self, rate: u64) {
+        self.run_rate
+            .store(rate, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn get_filepath(&self) -> &str {
+        &self.file
+    }
+
+    fn get_memory_return_range(&self) -> MemoryReturnRange {
+        self.mem_range
+    }
+
+    fn set_memory_return_range(&mut self, range: MemoryReturnRange) -> Result<(), String> {
+        if range.start % 4 != 0
+            || range.len % 4 != 0
+            || range.start > u32::MAX as u64
+            || u32::MAX as u64 - range.start < range.len
+        {
+            Err("Invalid range".to_string())
+        } else {
+            self.mem_range = range;
+            self.windows_changed("memory return range updated");
+            Ok(())
+        }
+    }
+
+    fn get_memory_views(&self) -> Vec<MemoryViewData> {
+        self.views.iter().map(|view| self.view_data(view)).collect()
+    }
+
+    fn set_memory_view(&mut self, view: MemoryView) -> Result<(), String> {
+        let width = view.width.bytes();
+        if view.name.is_empty()
+            || view.start % width != 0
+            || view.len % width != 0
+            || view.start > u32::MAX as u64
+            || u32::MAX as u64 - view.start < view.len
+        {
+            return Err("Invalid memory view".to_string());
+        }
+        if view.format == DisplayFormat::Float && view.width != ElementWidth::Word {
+            return Err("Float views need word elements".to_string());
+        }
+        match self.views.iter_mut().find(|old| old.name == view.name) {
+            Some(old) => *old = view,
+            None => self.views.push(view),
+        }
+        self.windows_changed("memory view updated");
+        Ok(())
+    }
+
+    fn remove_memory_view(&mut self, name: &str) -> Result<(), String> {
+        let idx = self
+            .views
+            .iter()
+            .position(|view| view.name == name)
+            .ok_or(format!("No memory view named {}", name))?;
+        self.views.remove(idx);
+        self.windows_changed("memory view removed");
+        Ok(())
+    }
+
+    fn get_watches(&self) -> Vec<WatchData> {
+        self.watches
+            .iter()
+            .map(|(expression, expr)| WatchData {
+                expression: expression.clone(),
+                value: match (expr, &self.inst) {
+                    (Err(e), _) => WatchValue::Error(e.clone()),
+                    (Ok(_), None) => WatchValue::Error("No program loaded".to_string()),
+                    (Ok(expr), Some(_)) => expr.eval(self),
+                },
+            })
+            .collect()
+    }
+
+    fn set_watches(&mut self, expressions: Vec<String>) {
+        self.watches = expressions
+            .into_iter()
+            .map(|expression| {
+                let expr = watch::parse(&expression);
+                (expression, expr)
+            })
+            .collect();
+        if self.get_status() == SimulatorStatus::Stopped
+            || self.get_status() == SimulatorStatus::Paused
+        {
+            self.update(Optional {
+                success: true,
+                message: "watches updated".to_string(),
+            });
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.get_status() == SimulatorStatus::Paused
+    }
+
+    fn is_waiting_input(&self) -> bool {
+        self.wait_input != WaitStatus::Not
+    }
+
+    fn set_event_sink(&mut self, sink: Option<Arc<dyn SimulatorEventSink>>) {
+        self.event_sink = sink;
+    }
+}
+
+impl RISCVSimulator {
+    fn get_status(&self) -> SimulatorStatus {
+        SimulatorStatus::VARIANTS[self.status.load(std::sync::atomic::Ordering::Acquire) as usize]
+    }
+
+    fn set_status(&self, status: SimulatorStatus) {
+        self.status
+            .store(status as u8, std::sync::atomic::Ordering::Release);
+    }
+
+    fn cas_status(&self, current: SimulatorStatus, new: SimulatorStatus) -> bool {
+        self.status
+            .compare_exchange(
+                current as u8,
+                new as u8,
+                std::sync::atomic::Ordering::AcqRel,
+                std::sync::atomic::Ordering::Acquire,
+            )
+            .is_ok()
+    }
+
+    /// Bit mask of `hart`, all harts if `None`.
+    fn hart_mask(hart: Option<usize>) -> Result<u64, String> {
+        match hart {
+            None => Ok(u64::MAX),
+            Some(hart) if hart < MAX_HART_COUNT => Ok(1 << hart),
+            Some(_) => Err("Invalid hart index".to_string()),
+        }
+    }
+
+    /// Picks the hart executing the next instruction, returns `false` once
+    /// every hart has run to the end of the text.
+    fn schedule(&mut self) -> bool {
+        let end = self.inst.as_ref().unwrap().instruction.len();
+        let count = self.harts.len();
+        if self.sched.left > 0 && self.hart().pc_idx != end {
+            return true;
+        }
+        // candidates in round robin order, the current hart comes last
+        let running = (1..=count)
+            .map(|i| (self.sched.hart + i) % count)
+            .filter(|&hart| self.harts[hart].pc_idx != end);
+        let next = match self.conf.hart_scheduler {
+            HartScheduler::RoundRobin => running.clone().next(),
+            HartScheduler::Random => {
+                let nth = next_random(&mut self.sched.rng) % running.clone().count().max(1) as u64;
+                running.clone().nth(nth as usize)
+            }
+        };
+        match next {
+            Some(hart) => {
+                self.sched.hart = hart;
+                self.sched.left = self.conf.hart_quantum.max(1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(super) fn _step(&mut self) -> Result<SimulatorStatus, String> {
+        let hart = self.sched.hart;
+        let pc_idx = self.hart().pc_idx;
+        if self.decoded.is_some() {
+            self.fetch()?;
+        }
+        let addr = self.to_text_addr(pc_idx);
+        let line = match &self.decoded {
+            Some(decoded) => decoded.get(addr).unwrap(),
+            None => &self.inst.as_ref().unwrap().instruction[pc_idx],
+        };
+        let inst = &line.instruction;
+        let size = decode::size(inst.operation);
+        let handler: Option<InstHandler> = match inst.operation {
+            ParserRISCVInstOp::RV32I(op) => Some(*INST_HANDLER_MAP.get(op)),
+            ParserRISCVInstOp::RV32A(op) => Some(*RV32A_INST_HANDLER_MAP.get(op)),
+            // compressed instructions keep the operands of their base instruction
+            ParserRISCVInstOp::RV32C(op) => Some(*INST_HANDLER_MAP.get(op.base())),
+            ParserRISCVInstOp::RV64I(op) => match self.conf.xlen {
+                Xlen::Rv64 => Some(*RV64I_INST_HANDLER_MAP.get(op)),
+                Xlen::Rv32 => {
+                    return Err(format!(
+                        "{} is only available in RV64",
+                        Into::<&'static str>::into(op)
+                    ))
+                }
+            },
+            ParserRISCVInstOp::RVB(op) if op.rv64_only() && self.conf.xlen == Xlen::Rv32 => {
+                return Err(format!("{} is only available in RV64", op.name()))
+            }
+            ParserRISCVInstOp::RVB(op) => Some(*RVB_INST_HANDLER_MAP.get(op)),
+            _ => None,
+        };
+        if let Some(handler) = handler {
+            if let Some(icache) = &mut self.icache {
+                icache.access(addr, size, false);
+            }
+            if let Some(trace) = &mut self.trace {
+                trace.begin(TraceEntry {
+                    hart: hart as u64,
+                    pc: addr,
+                    code: line.code,
+                    size: size as u8,
+                    disasm: line.basic.clone(),
+                    ..Default::default()
+                });
+            }
+            let mut history = History {
+                sched: self.sched,
+                reg_idx: -1,
+                reg_val: 0,
+                pc_idx,
+                mem_addr: 0,
+                mem: [0; 8],
+                mem_len: 0,
+                reservation: self.hart().reservation,
+                dropped_reservations: Vec::new(),
+                pipeline: None,
+                retired: false,
+                abi: None,
+                shadow: None,
+            };
+            let mut shadow_step = self.shadow.as_mut().map(|shadow| shadow.begin(hart));
+            let mut abi_step = self.abi.as_mut().map(|abi| {
+                let line = line.line_number;
+                let deps = pipeline::deps(inst.operation, &inst.operands);
+                let undo = abi.before(hart, line, &deps, &self.harts[hart].reg);
+                (undo, line, deps)
+            });
+            let mut res = handler(InstHandlerArg {
+                sim: Ptr::new(self),
+                args: &inst.operands,
+                history: Ptr::new(&history),
+            });
+            history.retired = res.is_ok();
+            if let (true, Some(abi), Some((undo, line, deps))) =
+                (history.retired, &mut self.abi, &mut abi_step)
+            {
+                abi.retire(undo, *line, deps, &self.harts[hart].reg);
+            }
+            history.abi = abi_step.map(|(undo, ..)| undo);
+            if let (Some(shadow), Some(undo)) = (&mut self.shadow, &mut shadow_step) {
+                if history.reg_idx > 0 {
+                    shadow.init_reg(hart, history.reg_idx as usize);
+                }
+                shadow.end(undo);
+                let read = shadow.reads_since(undo).first();
+                if let (UninitCheck::Pause, Some(read), Ok(SimulatorStatus::Running)) =
+                    (self.conf.uninit_check, read, &res)
+                {
+                    self.pause_reason = Some(read.message.clone());
+                    res = Ok(SimulatorStatus::Paused);
+                }
+            }
+            history.shadow = shadow_step;
+            if history.reg_idx > 0 {
+                self.changes
+                    .lock()
+                    .unwrap()
+                    .reg(hart, history.reg_idx as usize);
+            }
+            if history.retired {
+                self.retired += 1;
+            }
+            if let (true, Some(profiler)) = (history.retired, &mut self.profiler) {
+                profiler.retire(pc_idx, inst.operation);
+            }
+            if let Some(trace) = &mut self.trace {
+                if let (true, Some(entry)) = (history.reg_idx > 0, trace.pending_mut()) {
+                    entry.reg_writes.push(TraceRegWrite {
+                        reg: history.reg_idx as u8,
+                        value: self.harts[hart].reg[history.reg_idx as usize],
+                    });
+                }
+                trace.end(history.retired);
+            }
+            if self.history.len() == MAX_HISTORY_SIZE {
+                self.history.pop_front();
+            }
+            self.history.push_back(history);
+            if res.is_err() {
+                self.hart_mut().reservation = None;
+            }
+            self.sched.left = self.sched.left.saturating_sub(1);
+            let pc_idx = self.hart().pc_idx;
+            if matches!(res, Ok(SimulatorStatus::Running))
+                && self.debug
+                && pc_idx < self.breakpoints.len()
+                && self.breakpoints[pc_idx] & (1 << hart) != 0
+            {
+                Ok(SimulatorStatus::Paused)
+            } else {
+                res
+            }
+        } else {
+            unimplemented!(
+                "想要更多拓展? https://github.com/sustech-cs304/team-project-24spring-0点亮☆谢谢喵~"
+            )
+        }
+    }
+
+    /// One cycle of the pipeline, the instruction fetched in it records the
+    /// pipeline before the cycle for undo.
+    fn _cycle(&mut self) -> Result<SimulatorStatus, String> {
+        let mut pipeline = self.pipeline.take().unwrap();
+        let before = pipeline.clone();
+        let res = pipeline.cycle(self);
+        // only the entry of an instruction fetched in this cycle lacks it
+        if let Some(h) = self.history.back_mut() {
+            if h.pipeline.is_none() {
+                h.pipeline = Some(before);
+            }
+        }
+        self.pipeline = Some(pipeline);
+        res
+    }
+
+    fn _reset(&mut self) {
+        self.harts = (0..self.conf.hart_count.max(1))
+            .map(|id| {
+                let mut hart = Hart::new();
+                hart.reg[RV32IRegister::Sp as usize] = self.truncate(
+                    self.conf
+                        .stack_pointer_sp
+                        .wrapping_sub(id * self.conf.hart_stack_size),
+                );
+                hart
+            })
+            .collect();
+        // the random scheduler also draws the first hart
+        self.sched = Schedule {
+            hart: 0,
+            left: match self.conf.hart_scheduler {
+                HartScheduler::RoundRobin => self.conf.hart_quantum.max(1),
+                HartScheduler::Random => 0,
+            },
+            rng: self.conf.hart_schedule_seed,
+        };
+        self.pipeline = match self.conf.execution_mode {
+            ExecutionMode::Functional => None,
+            ExecutionMode::Pipeline => Some(Pipeline::new(
+                self.conf.pipeline_forwarding,
+                self.conf.pipeline_prediction,
+            )),
+        };
+        self.icache = Cache::new(&self.conf.l1_icache);
+        self.dcache = Cache::new(&self.conf.l1_dcache);
+        self.branch_predictor = BranchPredictor::new(&self.conf.branch_predictor);
+        let len = self.inst.as_ref().unwrap().instruction.len();
+        self.profiler = self.conf.profiler.then(|| Profiler::new(len));
+        self.trace = self.conf.trace.then(Trace::new);
+        self.abi = self
+            .conf
+            .abi_check
+            .then(|| AbiChecker::new(self.harts.len()));
+        self.layout_text();
+        self.decoded = self
+            .conf
+            .self_modifying_code
+            .then(|| DecodeCache::new(self.text_addr.len()));
+        self.mem.reset();
+        self.load_text();
+        self.mem.set_range(
+            self.conf.dot_data_base_address as u32,
+            self.inst.as_ref().unwrap().data.as_slice(),
+        );
+        for sec in &self.inst.as_ref().unwrap().sections {
+            self.mem.set_range(sec.address, sec.data.as_slice());
+        }
+        self.shadow = (self.conf.uninit_check != UninitCheck::Off).then(|| {
+            let inst = self.inst.as_ref().unwrap();
+            let mut shadow = Shadow::new(self.harts.len());
+            shadow.init_mem(
+                self.conf.dot_data_base_address as u32,
+                inst.data.len() as u32,
+            );
+            for sec in &inst.sections {
+                shadow.init_mem(sec.address, sec.data.len() as u32);
+            }
+            if self.conf.self_modifying_code {
+                let (text_start, text_len) = self.text_range();
+                shadow.init_mem(text_start, text_len);
+            }
+            shadow
+        });
+        self.pause_reason = None;
+        if let Some(t) = self.thread.take() {
+            t.join().unwrap();
+        }
+        self.wait_input = WaitStatus::Not;
+        self.history.clear();
+        self.retired = 0;
+        self.run_time = Duration::ZERO;
+        self.exceeded_limit = None;
+        self.fault = None;
+        self.changes.lock().unwrap().invalidate();
+    }
+
+    /// The first limit of the run reached, `run_time` is up to date.
+    fn reached_limit(&self) -> Option<(RunLimit, String)> {
+        let limits = &self.conf.run_limits;
+        if limits.max_instructions != 0 && self.retired >= limits.max_instructions {
+            Some((
+                RunLimit::Instructions,
+                format!("Instruction limit of {} exceeded", limits.max_instructions),
+            ))
+        } else if limits.time_limit_ms != 0
+            && self.run_time >= Duration::from_millis(limits.time_limit_ms)
+        {
+            Some((
+                RunLimit::Time,
+                format!("Time limit of {} ms exceeded", limits.time_limit_ms),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn _start(&mut self, max_step: Option<usize>) {
+        let self_ptr = Ptr::new(self);
+        self.thread = Some(std::thread::spawn(move || {
+            let mut step = 0;
+            let _self = self_ptr.as_mut();
+            let (run_time, started) = (_self.run_time, Instant::now());
+            let mut pacer = Pacer::new();
+            // the time limit doesn't count the sleeps of an animated run
+            let mut paced = Duration::ZERO;
+            loop {
+                _self.run_time = run_time + started.elapsed() - paced;
+                if let Some(max_step) = max_step {
+                    if step >= max_step {
+                        _self.set_status(SimulatorStatus::Paused);
+                        _self.update(Optional {
+                            success: true,
+                            message: "finished running".to_string(),
+                        });
+                        break;
+                    } else {
+                        step += 1;
+                    }
+                }
+                let finished = match &_self.pipeline {
+                    Some(pipeline) => pipeline.finished(_self),
+                    None => !_self.schedule(),
+                };
+                if finished {
+                    _self.set_status(SimulatorStatus::Stopped);
+                    _self.update(Optional {
+                        success: true,
+                        message: "finished running".to_string(),
+                    });
+                    break;
+                }
+                if let Some((limit, message)) = _self.reached_limit() {
+                    _self.exceeded_limit = Some(limit);
+                    _self.set_status(SimulatorStatus::Stopped);
+                    _self.update(Optional {
+                        success: false,
+                        message,
+                    });
+                    break;
+                }
+                let res = match _self.pipeline {
+                    Some(_) => _self._cycle(),
+                    None => _self._step(),
+                };
+                match res {
+                    Ok(status) => {
+                        _self.set_status(status);
+                        if status == SimulatorStatus::Paused {
+                            let message = _self.pause_reason.take();
+                            _self.update(Optional {
+                                success: true,
+                                message: message.unwrap_or_else(|| "paused".to_string()),
+                            });
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        _self.set_status(SimulatorStatus::Stopped);
+                        _self.update(Optional {
+                            success: false,
+                            message: e,
+                        });
+                        break;
+                    }
+                }
+                // steps aren't animated, a stop request ends the wait
+                if max_step.is_none() {
+                    pacer.step(_self.run_rate.load(std::sync::atomic::Ordering::Relaxed));
+                    let (mut delay, slept) = (pacer.delay(), Instant::now());
+                    while !delay.is_zero() && _self.get_status() == SimulatorStatus::Running {
+                        std::thread::sleep(delay);
+                        delay = pacer.delay();
+                    }
+                    paced += slept.elapsed();
+                }
+                if _self.get_status() != SimulatorStatus::Running {
+                    _self.update(Optional {
+                        success: true,
+                        message: "stopped".to_string(),
+                    });
+                    break;
+                }
+                if let (true, Some(sink)) = (pacer.progress_due(), &_self.event_sink) {
+                    sink.delta(_self.take_delta(
+                        Optional {
+                            success: true,
+                            message: "running".to_string(),
+                        },
+                        false,
+                    ));
+                }
+            }
+        }));
+    }
+
+    fn update(&mut self, res: Optional) {
+        let paused = self.get_status() == SimulatorStatus::Paused;
+        if let Some(sink) = self.event_sink.clone() {
+            sink.update(self, res, paused);
+        }
+    }
+}
+
+/// splitmix64, the interleaving only depends on the seed.
+pub(super) fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,461 @@
+mod memory {
+    use super::super::memory::Memory;
+
+    #[test]
+    fn test() {
+        let mut mem = Memory::new();
+        mem[0] = 1;
+        assert_eq!(mem[0], 1);
+        assert_eq!(mem[1], 0);
+        assert_eq!(mem[0x54365453u32], 0);
+        let start = 0x95478806u32;
+        mem.set_range(start, &[1, 2]);
+        assert_eq!(mem.get_range(start - 2, 6), [0, 0, 1, 2, 0, 0]);
+        let start = 0x07637855u32;
+        const LEN: usize = 16666;
+        mem.set_range(start, &[1; LEN]);
+        assert_eq!(mem.get_range(start, LEN as u32), [1; 16666]);
+        mem.reset();
+    }
+}
+
+mod cache {
+    use super::super::cache::Cache;
+    use crate::types::middleware_types::{CacheConfig, CacheReplacement, CacheWritePolicy};
+
+    fn new_cache(replacement: CacheReplacement, write_policy: CacheWritePolicy) -> Cache {
+        // a single set of two 16 byte blocks
+        let conf = CacheConfig {
+            enabled: true,
+            size: 32,
+            associativity: 2,
+            block_size: 16,
+            replacement,
+            write_policy,
+        };
+        assert!(Cache::valid_config(&conf));
+        Cache::new(&conf).unwrap()
+    }
+
+    #[test]
+    fn test_replacement() {
+        for (replacement, hit) in [
+            (CacheReplacement::Lru, true),
+            (CacheReplacement::Fifo, false),
+        ] {
+            let mut cache = new_cache(replacement, CacheWritePolicy::WriteBack);
+            cache.access(0x00, 4, false);
+            cache.access(0x10, 4, false);
+            cache.access(0x00, 4, false);
+            // LRU evicts 0x10, FIFO evicts 0x00
+            cache.access(0x20, 4, false);
+            cache.access(0x00, 4, false);
+            assert_eq!(cache.data().last_access.unwrap().hit, hit);
+        }
+    }
+
+    #[test]
+    fn test_write_policy() {
+        let mut cache = new_cache(CacheReplacement::Lru, CacheWritePolicy::WriteBack);
+        cache.access(0x00, 4, true);
+        cache.access(0x10, 4, false);
+        cache.access(0x20, 4, false);
+        let data = cache.data();
+        assert_eq!((data.hits, data.misses, data.writebacks), (0, 3, 1));
+
+        // write misses don't allocate
+        let mut cache = new_cache(CacheReplacement::Lru, CacheWritePolicy::WriteThrough);
+        cache.access(0x00, 4, true);
+        cache.access(0x00, 4, false);
+        cache.access(0x00, 4, true);
+        let data = cache.data();
+        assert_eq!((data.hits, data.misses, data.writebacks), (1, 2, 0));
+        assert!(data.lines.iter().all(|line| !line.dirty));
+
+        // an unaligned access spans two blocks
+        cache.access(0x0e, 4, false);
+        assert_eq!(cache.data().accesses, 5);
+    }
+
+    #[test]
+    fn test_config() {
+        let conf = CacheConfig {
+            enabled: true,
+            size: 48,
+            ..Default::default()
+        };
+        assert!(!Cache::valid_config(&conf));
+        assert!(Cache::new(&CacheConfig::default()).is_none());
+
+        // the set size overflows
+        let conf = CacheConfig {
+            enabled: true,
+            block_size: 1 << 63,
+            associativity: 2,
+            ..Default::default()
+        };
+        assert!(!Cache::valid_config(&conf));
+        assert!(Cache::new(&conf).is_none());
+    }
+}
+
+mod branch {
+    use super::super::branch::{BranchKind, BranchPredictor};
+    use crate::types::middleware_types::{BranchPredictorConfig, BranchPredictorKind};
+
+    fn new_predictor(kind: BranchPredictorKind, btb_entries: u64) -> BranchPredictor {
+        let conf = BranchPredictorConfig {
+            enabled: true,
+            kind,
+            table_bits: 4,
+            history_bits: 2,
+            btb_entries,
+        };
+        assert!(BranchPredictor::valid_config(&conf));
+        BranchPredictor::new(&conf).unwrap()
+    }
+
+    fn mispredictions(kind: BranchPredictorKind, outcomes: &[bool]) -> u64 {
+        let mut predictor = new_predictor(kind, 0);
+        for &taken in outcomes {
+            predictor.update(0, 0x400000, BranchKind::Conditional, taken, 0x3ffff0);
+        }
+        predictor.data(|_| 0).mispredictions
+    }
+
+    #[test]
+    fn test_direction() {
+        // a loop branch running twice
+        let outcomes = [true, true, true, false, true, true, true, false];
+        assert_eq!(
+            mispredictions(BranchPredictorKind::StaticNotTaken, &outcomes),
+            6
+        );
+        assert_eq!(
+            mispredictions(BranchPredictorKind::StaticTaken, &outcomes),
+            2
+        );
+        assert_eq!(mispredictions(BranchPredictorKind::OneBit, &outcomes), 4);
+        assert_eq!(mispredictions(BranchPredictorKind::TwoBit, &outcomes), 3);
+
+        // the global history tells alternating outcomes apart
+        let outcomes: Vec<bool> = (0..32).map(|i| i % 2 == 0).collect();
+        assert!(
+            mispredictions(BranchPredictorKind::Gshare, &outcomes)
+                < mispredictions(BranchPredictorKind::TwoBit, &outcomes)
+        );
+    }
+
+    #[test]
+    fn test_btb() {
+        let mut predictor = new_predictor(BranchPredictorKind::StaticTaken, 0);
+        predictor.update(1, 0x400004, BranchKind::Jalr, true, 0x400100);
+        predictor.update(2, 0x400008, BranchKind::Jal, true, 0x400100);
+        let data = predictor.data(|idx| idx as u64 * 10);
+        assert_eq!(data.mispredictions, 1);
+        assert_eq!(data.sites[0].line, 10);
+        assert_eq!(data.sites[0].mispredictions, 1);
+
+        let mut predictor = new_predictor(BranchPredictorKind::StaticTaken, 4);
+        for _ in 0..2 {
+            predictor.update(1, 0x400004, BranchKind::Jalr, true, 0x400100);
+        }
+        // a different target misses
+        predictor.update(1, 0x400004, BranchKind::Jalr, true, 0x400200);
+        let data = predictor.data(|_| 0);
+        assert_eq!((data.btb_hits, data.btb_misses), (2, 1));
+        assert_eq!(data.mispredictions, 2);
+    }
+}
+
+mod changes {
+    use super::super::changes::ChangeTracker;
+
+    #[test]
+    fn test_ranges() {
+        let mut changes = ChangeTracker::new();
+        // starts lost, taking clears it
+        assert!(changes.take().lost);
+        changes.mem(8, 4);
+        changes.mem(0, 4);
+        changes.mem(4, 2);
+        changes.mem(20, 1);
+        changes.mem(6, 16);
+        changes.reg(1, 5);
+        let taken = changes.take();
+        assert!(!taken.lost);
+        assert_eq!(taken.mem, vec![(0, 22)]);
+        assert_eq!(taken.regs, vec![0, 1 << 5]);
+        assert!(changes.take().mem.is_empty());
+
+        // too many ranges to keep
+        for i in 0..1000 {
+            changes.mem(i * 8, 4);
+        }
+        let taken = changes.take();
+        assert!(taken.lost && taken.mem.is_empty());
+    }
+}
+
+mod watch {
+    use super::super::watch::{parse, Machine};
+    use crate::types::middleware_types::{Endianness, WatchValue};
+
+    struct FakeMachine {
+        mem: Vec<u8>,
+    }
+
+    impl Machine for FakeMachine {
+        fn reg(&self, idx: usize) -> u64 {
+            idx as u64 * 0x10
+        }
+
+        fn pc(&self) -> u64 {
+            0x400000
+        }
+
+        fn label(&self, name: &str) -> Option<u64> {
+            (name == "buf").then(|| 4)
+        }
+
+        fn read(&self, addr: u32, len: u32) -> Vec<u8> {
+            (addr..addr + len)
+                .map(|addr| self.mem.get(addr as usize).copied().unwrap_or(0))
+                .collect()
+        }
+
+        fn truncate(&self, val: u64) -> u64 {
+            val as u32 as u64
+        }
+
+        fn endianness(&self) -> Endianness {
+            Endianness::Little
+        }
+    }
+
+    #[test]
+    fn test_eval() {
+        let machine = FakeMachine {
+            mem: vec![0x78, 0x56, 0x34, 0x12, b'h', b'i', 0, 0],
+        };
+        let eval = |src: &str| parse(src).unwrap().eval(&machine);
+        assert_eq!(eval("1 + 2 * 3"), WatchValue::Int(7));
+        assert_eq!(eval("(1 + 2) * 3"), WatchValue::Int(9));
+        assert_eq!(eval("1 << 4 | 0x3 & 0b10"), WatchValue::Int(0x12));
+        assert_eq!(eval("-1"), WatchValue::Int(0xffffffff));
+        assert_eq!(eval("a0 + x1 - pc"), WatchValue::Int(0xffc000b0));
+        assert_eq!(eval("mem32[0]"), WatchValue::Int(0x12345678));
+        assert_eq!(eval("mem16[buf - 2]"), WatchValue::Int(0x1234));
+        assert_eq!(eval("*0"), WatchValue::Int(0x12345678));
+        assert_eq!(eval("mem8[&buf]"), WatchValue::Int(b'h' as u64));
+        assert_eq!(eval("str[buf]"), WatchValue::Str("hi".to_string()));
+        assert_eq!(eval("float(0x3fc00000)"), WatchValue::Float(1.5));
+
+        assert_eq!(
+            eval("1 / (a0 - 0xa0)"),
+            WatchValue::Error("Division by zero".to_string())
+        );
+        assert_eq!(
+            eval("nope"),
+            WatchValue::Error("Unknown label nope".to_string())
+        );
+        assert_eq!(
+            eval("str[0] + 1"),
+            WatchValue::Error("Expected an integer".to_string())
+        );
+        assert!(parse("f10").unwrap_err().contains("not simulated"));
+        assert!(parse("float(f10)").unwrap_err().contains("not simulated"));
+        assert!(parse("(1 + 2").is_err());
+        assert!(parse("1 2").is_err());
+        assert!(parse("mem32[0] $").is_err());
+        assert!(parse("&a0").is_err());
+    }
+}
+
+mod decode {
+    use super::super::decode::decode;
+    use crate::{
+        interface::{assembler::Assembler, parser::Parser},
+        modules::riscv::basic::{
+            assembler::assembler::RiscVAssembler,
+            interface::parser::{RISCVExtension, RISCVParser},
+        },
+        types::middleware_types::{AssemblerConfig, Xlen},
+    };
+
+    /// Every instruction of `code` decodes to what it was assembled from.
+    fn round_trip(code: &str, xlen: Xlen) {
+        let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(xlen));
+        let mut assembler = RiscVAssembler::new();
+        assembler.update_config(&AssemblerConfig {
+            xlen,
+            ..Default::default()
+        });
+        let inst = assembler
+            .assemble(parser.parse(&code.to_string()).unwrap())
+            .unwrap();
+        for line in &inst.instruction {
+            let decoded =
+                decode(line.code, xlen).unwrap_or_else(|| panic!("`{}` isn't decoded", line.basic));
+            assert!(
+                decoded.instruction.operation == line.instruction.operation,
+                "`{}`",
+                line.basic
+            );
+            assert_eq!(
+                decoded.instruction.operands, line.instruction.operands,
+                "`{}`",
+                line.basic
+            );
+            assert_eq!(decoded.basic, line.basic);
+            assert_eq!(decoded.code, line.code);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip(
+            "
+.text
+main:
+    add a0, a1, a2
+    sub t0, t1, t2
+    sll a0, a0, a1
+    slt a0, a1, a2
+    sltu a0, a1, a2
+    xor a0, a1, a2
+    srl a0, a1, a2
+    sra a0, a1, a2
+    or a0, a1, a2
+    and a0, a1, a2
+    addi a0, a1, -5
+    slti a0, a1, 7
+    sltiu a0, a1, 2047
+    xori a0, a1, -1
+    ori a0, a1, 0x7f
+    andi a0, a1, -2048
+    slli a0, a1, 31
+    srli a0, a1, 3
+    srai a0, a1, 17
+    lw a0, -4(sp)
+    sw a1, 8(sp)
+    sb a1, -1(a0)
+    sh a1, 2(a0)
+    beq a0, a1, main
+    bne a0, zero, end
+    blt a0, a1, main
+    bge a0, a1, end
+    bltu a0, a1, main
+    bgeu a0, a1, end
+    jal ra, main
+    jalr ra, 12(t0)
+    lui a0, 0xfffff
+    auipc a0, 0x12345
+    fence 3, 3
+    fence.i
+    ecall
+    ebreak
+    csrrs a0, mhartid, zero
+    csrrw a0, mhartid, a1
+    csrrci a0, mhartid, 5
+    lr.w a0, (a1)
+    sc.w.aqrl a0, a2, (a1)
+    amoadd.w a0, a2, (a1)
+    amoswap.w.aq a0, a2, (a1)
+    amomaxu.w a0, a2, (a1)
+    andn a0, a1, a2
+    orn a0, a1, a2
+    xnor a0, a1, a2
+    clz a0, a1
+    ctz a0, a1
+    cpop a0, a1
+    sext.b a0, a1
+    sext.h a0, a1
+    zext.h a0, a1
+    max a0, a1, a2
+    minu a0, a1, a2
+    rol a0, a1, a2
+    ror a0, a1, a2
+    rori a0, a1, 7
+    orc.b a0, a1
+    rev8 a0, a1
+    bclr a0, a1, a2
+    bexti a0, a1, 3
+    binvi a0, a1, 31
+    bset a0, a1, a2
+    sh2add a0, a1, a2
+back:
+    c.li a0, -1
+    c.addi a0, 1
+    c.nop
+    c.lui a0, 0x1f
+    c.lui a0, 0xfffff
+    c.addi16sp sp, -16
+    c.addi4spn s0, 8
+    c.mv a0, a1
+    c.add a0, a1
+    c.sub s0, s1
+    c.and s0, s1
+    c.or s0, s1
+    c.xor s0, s1
+    c.andi s0, -3
+    c.srli s0, 3
+    c.srai s0, 31
+    c.slli a0, 4
+    c.lw a0, 4(a1)
+    c.sw a0, 8(a1)
+    c.lwsp a0, 12(sp)
+    c.swsp a0, 16(sp)
+    c.beqz a0, back
+    c.bnez a0, end
+    c.j back
+    c.jal end
+    c.jr ra
+    c.jalr t0
+    c.ebreak
+end:
+    nop
+",
+            Xlen::Rv32,
+        );
+        round_trip(
+            "
+.text
+    ld a0, 8(sp)
+    lwu a0, -8(sp)
+    sd a1, 16(sp)
+    addiw a0, a1, -1
+    slliw a0, a1, 31
+    srliw a0, a1, 1
+    sraiw a0, a1, 2
+    slli a0, a1, 63
+    addw a0, a1, a2
+    subw a0, a1, a2
+    sllw a0, a1, a2
+    srlw a0, a1, a2
+    sraw a0, a1, a2
+    add.uw a0, a1, a2
+    zext.h a0, a1
+    rev8 a0, a1
+    clzw a0, a1
+    cpopw a0, a1
+    rolw a0, a1, a2
+    roriw a0, a1, 5
+    slli.uw a0, a1, 40
+    sh3add.uw a0, a1, a2
+",
+            Xlen::Rv64,
+        );
+    }
+
+    #[test]
+    fn test_illegal() {
+        assert!(decode(0x0000, Xlen::Rv32).is_none());
+        assert!(decode(0xffffffff, Xlen::Rv32).is_none());
+        // c.addi4spn with a zero immediate is reserved
+        assert!(decode(0x0004, Xlen::Rv32).is_none());
+        // the shift amount of slli has 5 bits in RV32
+        assert!(decode(0x02051513, Xlen::Rv32).is_none());
+        assert!(decode(0x02051513, Xlen::Rv64).is_some());
+    }
+}
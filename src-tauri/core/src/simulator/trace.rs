@@ -0,0 +1,102 @@
+use std::fmt::Write;
+
+use crate::types::middleware_types::{TraceEntry, TraceMemAccess};
+
+/// Retired instructions in order, undo drops the last one.
+pub(super) struct Trace {
+    entries: Vec<TraceEntry>,
+    // the instruction being executed
+    pending: Option<TraceEntry>,
+}
+
+impl Trace {
+    pub fn new() -> Self {
+        Trace {
+            entries: Vec::new(),
+            pending: None,
+        }
+    }
+
+    pub fn begin(&mut self, entry: TraceEntry) {
+        self.pending = Some(entry);
+    }
+
+    /// Keeps the pending entry if it retired.
+    pub fn end(&mut self, retired: bool) {
+        if let (true, Some(entry)) = (retired, self.pending.take()) {
+            self.entries.push(entry);
+        }
+    }
+
+    pub fn pending_mut(&mut self) -> Option<&mut TraceEntry> {
+        self.pending.as_mut()
+    }
+
+    /// Last retired entry, an input ecall completes it.
+    pub fn last_mut(&mut self) -> Option<&mut TraceEntry> {
+        self.entries.last_mut()
+    }
+
+    pub fn pop(&mut self) {
+        self.entries.pop();
+    }
+
+    pub fn mem_access(&mut self, address: u32, data: &[u8], write: bool) {
+        if let Some(entry) = &mut self.pending {
+            let mut buf = [0u8; 8];
+            buf[..data.len()].copy_from_slice(data);
+            let access = TraceMemAccess {
+                address,
+                size: data.len() as u8,
+                value: u64::from_le_bytes(buf),
+            };
+            if write {
+                entry.mem_writes.push(access);
+            } else {
+                entry.mem_reads.push(access);
+            }
+        }
+    }
+
+    pub fn jsonl(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            out += &serde_json::to_string(entry).unwrap();
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Commit log as printed by Spike in machine mode, writes to `x0` are
+    /// left out.
+    pub fn spike(&self, xlen_bits: u32) -> String {
+        let digits = xlen_bits as usize / 4;
+        let mut out = String::new();
+        for entry in &self.entries {
+            let code_digits = entry.size as usize * 2;
+            write!(
+                out,
+                "core {:>3}: 3 0x{:0digits$x} (0x{:0code_digits$x})",
+                entry.hart, entry.pc, entry.code
+            )
+            .unwrap();
+            for write in entry.reg_writes.iter().filter(|write| write.reg != 0) {
+                write!(out, " x{:<2} 0x{:0digits$x}", write.reg, write.value).unwrap();
+            }
+            for read in &entry.mem_reads {
+                write!(out, " mem 0x{:0digits$x}", read.address).unwrap();
+            }
+            for write in &entry.mem_writes {
+                let value_digits = write.size as usize * 2;
+                write!(
+                    out,
+                    " mem 0x{:0digits$x} 0x{:0value_digits$x}",
+                    write.address, write.value
+                )
+                .unwrap();
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
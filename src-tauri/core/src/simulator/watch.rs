@@ -0,0 +1,343 @@
+use std::str::FromStr;
+
+use crate::{
+    modules::riscv::{rv32f::constants::RV32FRegister, rv32i::constants::RV32IRegister},
+    types::middleware_types::{Endianness, WatchValue},
+};
+
+/// Longest string `str[...]` reads before giving up on the NUL.
+const MAX_STRING_LEN: u32 = 256;
+
+/// State a watch expression reads, the registers are those of the current
+/// hart.
+pub(super) trait Machine {
+    fn reg(&self, idx: usize) -> u64;
+    fn pc(&self) -> u64;
+    fn label(&self, name: &str) -> Option<u64>;
+    fn read(&self, addr: u32, len: u32) -> Vec<u8>;
+    /// The low XLEN bits of `val`.
+    fn truncate(&self, val: u64) -> u64;
+    /// Byte order of data in memory.
+    fn endianness(&self) -> Endianness;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    And,
+    Or,
+    Xor,
+    Shl,
+    Shr,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Expr {
+    Num(u64),
+    Reg(usize),
+    Pc,
+    Label(String),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    /// `len` bytes at the address, zero-extended
+    Load(u32, Box<Expr>),
+    Str(Box<Expr>),
+    Float(Box<Expr>),
+    Double(Box<Expr>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(u64),
+    Ident(String),
+    Punct(&'static str),
+}
+
+const PUNCTS: [&str; 15] = [
+    "<<", ">>", "+", "-", "*", "/", "%", "&", "|", "^", "~", "(", ")", "[", "]",
+];
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = src.trim_start();
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '.')
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            tokens.push(if c.is_ascii_digit() {
+                Token::Num(parse_num(word).ok_or(format!("Invalid number {}", word))?)
+            } else {
+                Token::Ident(word.to_string())
+            });
+            rest = &rest[end..];
+        } else {
+            let punct = PUNCTS
+                .iter()
+                .find(|punct| rest.starts_with(**punct))
+                .ok_or(format!("Unexpected character {}", c))?;
+            tokens.push(Token::Punct(punct));
+            rest = &rest[punct.len()..];
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+fn parse_num(word: &str) -> Option<u64> {
+    if let Some(hex) = word.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16).ok()
+    } else if let Some(bin) = word.strip_prefix("0b") {
+        u64::from_str_radix(bin, 2).ok()
+    } else {
+        word.parse().ok()
+    }
+}
+
+struct ExprParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+// binary operators from the loosest to the tightest binding
+const LEVELS: [&[(&str, BinOp)]; 6] = [
+    &[("|", BinOp::Or)],
+    &[("^", BinOp::Xor)],
+    &[("&", BinOp::And)],
+    &[("<<", BinOp::Shl), (">>", BinOp::Shr)],
+    &[("+", BinOp::Add), ("-", BinOp::Sub)],
+    &[("*", BinOp::Mul), ("/", BinOp::Div), ("%", BinOp::Rem)],
+];
+
+impl ExprParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), String> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(format!("Expected {}", punct))
+        }
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, String> {
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+        let mut lhs = self.binary(level + 1)?;
+        'outer: loop {
+            for &(punct, op) in LEVELS[level] {
+                if self.eat(punct) {
+                    let rhs = self.binary(level + 1)?;
+                    lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+                    continue 'outer;
+                }
+            }
+            return Ok(lhs);
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat("-") {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else if self.eat("~") {
+            Ok(Expr::Not(Box::new(self.unary()?)))
+        } else if self.eat("*") {
+            // a pointer is XLEN bits wide, the width is decided when loading
+            Ok(Expr::Load(0, Box::new(self.unary()?)))
+        } else if self.eat("&") {
+            match self.primary()? {
+                label @ Expr::Label(_) => Ok(label),
+                _ => Err("& needs a label".to_string()),
+            }
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let token = self.peek().cloned().ok_or("Unexpected end")?;
+        self.pos += 1;
+        match token {
+            Token::Num(val) => Ok(Expr::Num(val)),
+            Token::Punct("(") => {
+                let expr = self.binary(0)?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Token::Ident(name) => self.ident(name),
+            Token::Punct(punct) => Err(format!("Unexpected {}", punct)),
+        }
+    }
+
+    fn ident(&mut self, name: String) -> Result<Expr, String> {
+        let len = match name.as_str() {
+            "mem8" => Some(1),
+            "mem16" => Some(2),
+            "mem32" => Some(4),
+            "mem64" => Some(8),
+            _ => None,
+        };
+        if let Some(len) = len.filter(|_| self.eat("[")) {
+            let addr = self.binary(0)?;
+            self.expect("]")?;
+            return Ok(Expr::Load(len, Box::new(addr)));
+        }
+        if name == "str" && self.eat("[") {
+            let addr = self.binary(0)?;
+            self.expect("]")?;
+            return Ok(Expr::Str(Box::new(addr)));
+        }
+        if (name == "float" || name == "double") && self.eat("(") {
+            let bits = Box::new(self.binary(0)?);
+            self.expect(")")?;
+            return Ok(match name.as_str() {
+                "float" => Expr::Float(bits),
+                _ => Expr::Double(bits),
+            });
+        }
+        if name == "pc" {
+            return Ok(Expr::Pc);
+        }
+        if let Ok(reg) = RV32IRegister::from_str(&name) {
+            return Ok(Expr::Reg(reg as usize));
+        }
+        if RV32FRegister::from_str(&name).is_ok() {
+            return Err(format!("{} is not simulated", name));
+        }
+        Ok(Expr::Label(name))
+    }
+}
+
+/// Parses a watch expression.
+///
+/// - integers, `0x` hexadecimal and `0b` binary
+/// - `pc`, registers by number or ABI name, labels as their address, `&label`
+///   is the same
+/// - `+ - * / % & | ^ << >>` and unary `- ~`, with the precedence of C
+/// - `mem8[a]`, `mem16[a]`, `mem32[a]`, `mem64[a]` and `*a`, which reads XLEN
+///   bits
+/// - `str[a]`, the NUL-terminated string at `a`
+/// - `float(x)` and `double(x)`, the bits of `x` as a floating point number
+///
+/// The simulator has no F register file, so F registers are rejected and
+/// `float(f10)` can't be watched, `float(a0)` reads the bits of an integer
+/// register.
+pub(super) fn parse(src: &str) -> Result<Expr, String> {
+    let mut parser = ExprParser {
+        tokens: tokenize(src)?,
+        pos: 0,
+    };
+    let expr = parser.binary(0)?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(_) => Err("Unexpected token after the expression".to_string()),
+    }
+}
+
+impl Expr {
+    pub fn eval(&self, machine: &dyn Machine) -> WatchValue {
+        match self.value(machine) {
+            Ok(value) => value,
+            Err(e) => WatchValue::Error(e),
+        }
+    }
+
+    fn int(&self, machine: &dyn Machine) -> Result<u64, String> {
+        match self.value(machine)? {
+            WatchValue::Int(val) => Ok(val),
+            _ => Err("Expected an integer".to_string()),
+        }
+    }
+
+    fn addr(&self, machine: &dyn Machine) -> Result<u32, String> {
+        let addr = self.int(machine)?;
+        u32::try_from(addr).map_err(|_| format!("Invalid address 0x{:x}", addr))
+    }
+
+    fn value(&self, machine: &dyn Machine) -> Result<WatchValue, String> {
+        let int = |val| Ok(WatchValue::Int(machine.truncate(val)));
+        match self {
+            Expr::Num(val) => int(*val),
+            Expr::Reg(idx) => int(machine.reg(*idx)),
+            Expr::Pc => int(machine.pc()),
+            Expr::Label(name) => machine
+                .label(name)
+                .map(WatchValue::Int)
+                .ok_or(format!("Unknown label {}", name)),
+            Expr::Neg(expr) => int(expr.int(machine)?.wrapping_neg()),
+            Expr::Not(expr) => int(!expr.int(machine)?),
+            Expr::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.int(machine)?, rhs.int(machine)?);
+                int(match op {
+                    BinOp::Add => lhs.wrapping_add(rhs),
+                    BinOp::Sub => lhs.wrapping_sub(rhs),
+                    BinOp::Mul => lhs.wrapping_mul(rhs),
+                    BinOp::Div => lhs.checked_div(rhs).ok_or("Division by zero")?,
+                    BinOp::Rem => lhs.checked_rem(rhs).ok_or("Division by zero")?,
+                    BinOp::And => lhs & rhs,
+                    BinOp::Or => lhs | rhs,
+                    BinOp::Xor => lhs ^ rhs,
+                    BinOp::Shl => u32::try_from(rhs)
+                        .ok()
+                        .and_then(|rhs| lhs.checked_shl(rhs))
+                        .unwrap_or(0),
+                    BinOp::Shr => u32::try_from(rhs)
+                        .ok()
+                        .and_then(|rhs| lhs.checked_shr(rhs))
+                        .unwrap_or(0),
+                })
+            }
+            Expr::Load(len, addr) => {
+                let len = match len {
+                    0 => machine.truncate(u64::MAX).count_ones() / 8,
+                    len => *len,
+                };
+                let addr = addr.addr(machine)?;
+                if u32::MAX - addr < len - 1 {
+                    return Err(format!("Invalid address 0x{:x}", addr));
+                }
+                let mut bytes = machine.read(addr, len);
+                if machine.endianness() == Endianness::Little {
+                    bytes.reverse();
+                }
+                Ok(WatchValue::Int(
+                    bytes.iter().fold(0, |val, &byte| val << 8 | byte as u64),
+                ))
+            }
+            Expr::Str(addr) => {
+                let addr = addr.addr(machine)?;
+                let len = MAX_STRING_LEN.min(u32::MAX - addr);
+                let bytes = machine.read(addr, len);
+                let end = bytes
+                    .iter()
+                    .position(|&byte| byte == 0)
+                    .ok_or("Unterminated string")?;
+                Ok(WatchValue::Str(
+                    String::from_utf8_lossy(&bytes[..end]).into_owned(),
+                ))
+            }
+            Expr::Float(bits) => Ok(WatchValue::Float(
+                f32::from_bits(bits.int(machine)? as u32) as f64
+            )),
+            Expr::Double(bits) => Ok(WatchValue::Float(f64::from_bits(bits.int(machine)?))),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod rv32a;
+mod rv32c;
+mod rv32i;
+mod rv64i;
+mod rvb;
+mod sample;
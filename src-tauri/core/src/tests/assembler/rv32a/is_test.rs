@@ -0,0 +1,43 @@
+use crate::{
+    interface::assembler::Assembler,
+    modules::riscv::basic::{assembler::assembler::RiscVAssembler, interface::parser::*},
+    types::middleware_types::Xlen,
+};
+
+#[test]
+fn test_assemble() {
+    let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(Xlen::Rv32));
+    let table = [
+        ("lr.w a0, (a1)", 0x1005a52f, "lr.w x10,(x11)"),
+        ("sc.w a0, a2, (a1)", 0x18c5a52f, "sc.w x10,x12,(x11)"),
+        (
+            "amoadd.w a0, a2, (a1)",
+            0x00c5a52f,
+            "amoadd.w x10,x12,(x11)",
+        ),
+        (
+            "amoswap.w.aq a0, a2, (a1)",
+            0x0cc5a52f,
+            "amoswap.w.aq x10,x12,(x11)",
+        ),
+        (
+            "amoor.w.rl a0, a2, (a1)",
+            0x42c5a52f,
+            "amoor.w.rl x10,x12,(x11)",
+        ),
+        (
+            "amomaxu.w.aqrl a0, a2, (a1)",
+            0xe6c5a52f,
+            "amomaxu.w.aqrl x10,x12,(x11)",
+        ),
+    ];
+    for (code, expect, basic) in table {
+        let res = RiscVAssembler::new()
+            .assemble(parser.parse(&code.to_string()).unwrap())
+            .unwrap();
+        assert_eq!(res.instruction[0].code, expect, "{}", code);
+        assert_eq!(res.instruction[0].basic, basic);
+    }
+    assert!(parser.parse(&"lr.w a0, a1".to_string()).is_err());
+    assert!(parser.parse(&"amoadd.w a0, a2, 4(a1)".to_string()).is_err());
+}
@@ -0,0 +1,111 @@
+use crate::{
+    interface::assembler::Assembler,
+    modules::riscv::{
+        basic::{assembler::assembler::RiscVAssembler, interface::parser::*},
+        rv32c::assembler::rv32c::RV32C,
+    },
+    types::middleware_types::AssemblerConfig,
+};
+
+fn encode(op: RV32CInstruction, rd: u32, rs1: u32, rs2: u32, imm: RISCVImmediate) -> u32 {
+    RV32C::encode(op, rd, rs1, rs2, imm).unwrap().into()
+}
+
+#[test]
+fn test_encode() {
+    assert_eq!(0x0001, encode(RV32CInstruction::CNop, 0, 0, 0, 0));
+    assert_eq!(0x0505, encode(RV32CInstruction::CAddi, 10, 10, 0, 1));
+    assert_eq!(0x557d, encode(RV32CInstruction::CLi, 10, 0, 0, -1));
+    assert_eq!(0x717d, encode(RV32CInstruction::CAddi16sp, 2, 2, 0, -16));
+    assert_eq!(0x852e, encode(RV32CInstruction::CMv, 10, 0, 11, 0));
+    assert_eq!(0x952e, encode(RV32CInstruction::CAdd, 10, 10, 11, 0));
+    assert_eq!(0x8082, encode(RV32CInstruction::CJr, 0, 1, 0, 0));
+    assert_eq!(0x9002, encode(RV32CInstruction::CEbreak, 0, 0, 0, 0));
+    assert_eq!(0x4188, encode(RV32CInstruction::CLw, 10, 11, 0, 0));
+    assert_eq!(0xc1c8, encode(RV32CInstruction::CSw, 0, 11, 10, 4));
+    assert_eq!(0xa001, encode(RV32CInstruction::CJ, 0, 0, 0, 0));
+    assert_eq!(0xbffd, encode(RV32CInstruction::CJ, 0, 0, 0, -2));
+    assert_eq!(0xc101, encode(RV32CInstruction::CBeqz, 0, 10, 0, 0));
+}
+
+#[test]
+fn test_encode_error() {
+    // only x8-x15 fit in the 3-bit register fields
+    assert!(RV32C::encode(RV32CInstruction::CLw, 5, 11, 0, 0).is_err());
+    assert!(RV32C::encode(RV32CInstruction::CAddi, 10, 10, 0, 32).is_err());
+    assert!(RV32C::encode(RV32CInstruction::CLwsp, 10, 2, 0, 3).is_err());
+    assert!(RV32C::encode(RV32CInstruction::CBnez, 0, 10, 0, 256).is_err());
+}
+
+#[test]
+fn test_compress() {
+    assert_eq!(
+        RV32C::compress(RV32IInstruction::Addi, 10, 10, 0, 1),
+        Some(RV32CInstruction::CAddi)
+    );
+    assert_eq!(RV32C::compress(RV32IInstruction::Addi, 10, 11, 0, 1), None);
+    assert_eq!(
+        RV32C::compress(RV32IInstruction::Add, 10, 0, 11, 0),
+        Some(RV32CInstruction::CMv)
+    );
+    assert_eq!(
+        RV32C::compress(RV32IInstruction::Lw, 10, 2, 10, 8),
+        Some(RV32CInstruction::CLwsp)
+    );
+    assert_eq!(RV32C::compress(RV32IInstruction::Beq, 10, 0, 0, 0), None);
+}
+
+#[test]
+fn test_assemble() {
+    let mut parser = RISCVParser::new(&vec![RISCVExtension::RV32I, RISCVExtension::RV32C]);
+    let code = "
+main:
+    c.li a0, 1
+    addi a0, a0, 1
+    beq a0, zero, main
+    c.j main
+"
+    .to_string();
+    let mut assembler = RiscVAssembler::new();
+    let base = AssemblerConfig::default().dot_text_base_address as u32;
+
+    let res = assembler.assemble(parser.parse(&code).unwrap()).unwrap();
+    let address: Vec<_> = res
+        .instruction
+        .iter()
+        .map(|line| line.address - base)
+        .collect();
+    assert_eq!(address, vec![0, 2, 6, 10]);
+    assert_eq!(res.instruction[0].code, 0x4505);
+    assert_eq!(res.instruction[0].basic, "c.li x10,1");
+    assert_eq!(res.instruction[2].basic, "beq x10,x0,-6");
+    assert_eq!(res.instruction[3].basic, "c.j -10");
+    assert_eq!(
+        res.instruction[0].instruction.operation,
+        ParserRISCVInstOp::RV32C(RV32CInstruction::CLi)
+    );
+    assert_eq!(res.instruction[0].instruction.operands, vec![10, 0, 1]);
+
+    assembler.update_config(&AssemblerConfig {
+        compress_instructions: true,
+        ..Default::default()
+    });
+    let res = assembler.assemble(parser.parse(&code).unwrap()).unwrap();
+    let address: Vec<_> = res
+        .instruction
+        .iter()
+        .map(|line| line.address - base)
+        .collect();
+    assert_eq!(address, vec![0, 2, 4, 8]);
+    assert_eq!(res.instruction[1].code, 0x0505);
+    assert_eq!(res.instruction[1].basic, "c.addi x10,1");
+    assert_eq!(res.instruction[2].basic, "beq x10,x0,-4");
+    let dump = assembler.dump(parser.parse(&code).unwrap()).unwrap();
+    let width: Vec<_> = dump.text.lines().map(|line| line.len()).collect();
+    assert_eq!(width, vec![16, 16, 32, 16]);
+
+    // c.lw only encodes x8-x15
+    assert!(assembler
+        .assemble(parser.parse(&"c.lw a0, 0(t0)".to_string()).unwrap())
+        .is_err());
+}
@@ -1,4 +1,15 @@
-use crate::modules::riscv::{rv32f::assembler::rv32f::RV32F, rv32i::assembler::rv32i::RV32I};
+use crate::{
+    interface::{assembler::Assembler, parser::Parser},
+    modules::riscv::{
+        basic::{
+            assembler::assembler::RiscVAssembler,
+            interface::parser::{RISCVExtension, RISCVParser},
+        },
+        rv32f::assembler::rv32f::RV32F,
+        rv32i::assembler::rv32i::RV32I,
+    },
+    types::middleware_types::Xlen,
+};
 
 #[test]
 fn test_lui() {
@@ -79,3 +90,20 @@ fn test_flw() {
         Into::<u32>::into(RV32F::flw(0x0.into(), 0x8.into(), 0x8.into()))
     )
 }
+
+#[test]
+fn test_csr() {
+    let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(Xlen::Rv32));
+    let table = [
+        ("csrr a0, mhartid", 0xf1402573, "csrrs x10,3860,x0"),
+        ("csrrw a0, mhartid, a1", 0xf1459573, "csrrw x10,3860,x11"),
+        ("csrrsi zero, mhartid, 3", 0xf141e073, "csrrsi x0,3860,3"),
+    ];
+    for (code, expect, basic) in table {
+        let res = RiscVAssembler::new()
+            .assemble(parser.parse(&code.to_string()).unwrap())
+            .unwrap();
+        assert_eq!(res.instruction[0].code, expect, "{}", code);
+        assert_eq!(res.instruction[0].basic, basic);
+    }
+}
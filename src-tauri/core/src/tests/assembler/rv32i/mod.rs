@@ -0,0 +1 @@
+mod is_test;
@@ -0,0 +1,62 @@
+use crate::{
+    interface::assembler::Assembler,
+    modules::riscv::basic::{assembler::assembler::RiscVAssembler, interface::parser::*},
+    types::middleware_types::{AssemblerConfig, Xlen},
+};
+
+fn rv64_assembler() -> RiscVAssembler {
+    let mut assembler = RiscVAssembler::new();
+    assembler.update_config(&AssemblerConfig {
+        xlen: Xlen::Rv64,
+        ..Default::default()
+    });
+    assembler
+}
+
+fn assemble(code: &str) -> Vec<u32> {
+    let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(Xlen::Rv64));
+    rv64_assembler()
+        .assemble(parser.parse(&code.to_string()).unwrap())
+        .unwrap()
+        .instruction
+        .iter()
+        .map(|line| line.code)
+        .collect()
+}
+
+#[test]
+fn test_encode() {
+    assert_eq!(assemble("addiw a0, a1, -1"), vec![0xfff5851b]);
+    assert_eq!(assemble("addw a0, a1, a2"), vec![0x00c5853b]);
+    assert_eq!(assemble("subw a0, a1, a2"), vec![0x40c5853b]);
+    assert_eq!(assemble("sraiw a0, a0, 3"), vec![0x4035551b]);
+    assert_eq!(assemble("ld a0, 8(sp)"), vec![0x00813503]);
+    assert_eq!(assemble("lwu a0, 0(a1)"), vec![0x0005e503]);
+    assert_eq!(assemble("sd a0, 8(sp)"), vec![0x00a13423]);
+    // 6-bit shift amounts
+    assert_eq!(assemble("slli a0, a0, 40"), vec![0x02851513]);
+    assert_eq!(assemble("srai a0, a0, 33"), vec![0x42155513]);
+}
+
+#[test]
+fn test_li() {
+    assert_eq!(assemble("li a0, -1").len(), 1);
+    assert_eq!(assemble("li a0, 0x7fffffff").len(), 2);
+    assert_eq!(assemble("li a0, 0xffffffff").len(), 8);
+    assert_eq!(assemble("li a0, 0xffffffffffffffff").len(), 8);
+}
+
+#[test]
+fn test_xlen() {
+    let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(Xlen::Rv32));
+    assert!(parser.parse(&"ld a0, 0(sp)".to_string()).is_err());
+    assert!(parser.parse(&"slli a0, a0, 32".to_string()).is_err());
+    assert!(parser.parse(&"li a0, 0x100000000".to_string()).is_err());
+
+    // c.jal is c.addiw in RV64C
+    let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(Xlen::Rv64));
+    let code = "main:\n    c.jal main\n".to_string();
+    assert!(rv64_assembler()
+        .assemble(parser.parse(&code).unwrap())
+        .is_err());
+}
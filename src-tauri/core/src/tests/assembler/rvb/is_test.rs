@@ -0,0 +1,67 @@
+use crate::{
+    interface::assembler::{Assembler, AssemblyError},
+    modules::riscv::basic::{assembler::assembler::RiscVAssembler, interface::parser::*},
+    types::middleware_types::{AssemblerConfig, Xlen},
+};
+
+fn assemble(code: &str, xlen: Xlen) -> Result<Vec<u32>, Vec<AssemblyError>> {
+    let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(xlen));
+    let mut assembler = RiscVAssembler::new();
+    assembler.update_config(&AssemblerConfig {
+        xlen,
+        ..Default::default()
+    });
+    Ok(assembler
+        .assemble(parser.parse(&code.to_string()).unwrap())?
+        .instruction
+        .iter()
+        .map(|line| line.code)
+        .collect())
+}
+
+#[test]
+fn test_encode() {
+    let table = [
+        ("andn a0, a1, a2", 0x40c5f533),
+        ("sh1add a0, a1, a2", 0x20c5a533),
+        ("clz a0, a1", 0x60059513),
+        ("sext.b a0, a1", 0x60459513),
+        ("orc.b a0, a1", 0x2875d513),
+        ("rev8 a0, a1", 0x6985d513),
+        ("zext.h a0, a1", 0x0805c533),
+        ("zext.b a0, a1", 0x0ff5f513),
+        ("bseti a0, a1, 5", 0x28559513),
+    ];
+    for (code, expect) in table {
+        assert_eq!(
+            assemble(code, Xlen::Rv32).unwrap(),
+            vec![expect],
+            "{}",
+            code
+        );
+    }
+
+    let table = [
+        ("rev8 a0, a1", 0x6b85d513),
+        ("zext.h a0, a1", 0x0805c53b),
+        ("zext.w a0, a1", 0x0805853b),
+        ("add.uw a0, a1, a2", 0x08c5853b),
+        ("rori a0, a1, 33", 0x6215d513),
+    ];
+    for (code, expect) in table {
+        assert_eq!(
+            assemble(code, Xlen::Rv64).unwrap(),
+            vec![expect],
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn test_xlen() {
+    assert!(assemble("add.uw a0, a1, a2", Xlen::Rv32).is_err());
+    assert!(assemble("clzw a0, a1", Xlen::Rv32).is_err());
+    assert!(assemble("rori a0, a1, 32", Xlen::Rv32).is_err());
+    assert!(assemble("rori a0, a1, 31", Xlen::Rv32).is_ok());
+}
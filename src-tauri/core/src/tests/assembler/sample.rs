@@ -131,6 +131,7 @@ L2:
                 dot_extern_base_address: 0x00001000,
                 text_limit_address: 0x00003ffc,
                 dot_text_base_address: 0x00003000,
+                ..Default::default()
             };
             riscv_assembler.update_config(&new_configuration);
             let assembled_result = riscv_assembler.assemble(ast);
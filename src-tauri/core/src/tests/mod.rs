@@ -0,0 +1,3 @@
+mod assembler;
+mod parser;
+mod simulator;
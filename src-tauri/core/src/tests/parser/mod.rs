@@ -1,2 +1,3 @@
 mod rv32i;
 mod sample;
+mod section_test;
@@ -58,11 +58,13 @@ macro_rules! expect_helper {
     ($op:expr, $($opd:expr),*) => {
         ParserResult::<RISCV> {
             data: vec![],
+            sections: vec![],
             text: vec![ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
                 op: $op.into(),
                 opd: vec![$($opd),*],
             })],
+            labels: vec![],
         }
     };
 }
@@ -74,6 +76,15 @@ macro_rules! test {
     };
 }
 
+// the code defines the label `a` at the first instruction
+macro_rules! test_labeled {
+    ($expect:expr, $code:expr, $parser:expr) => {
+        let mut expect = $expect;
+        expect.labels = vec![("a".to_string(), Text(0))];
+        test!(expect, $code, $parser);
+    };
+}
+
 macro_rules! test_load_mem {
     ($inst:expr, $name:expr, $parser:expr) => {
         let expect = expect_helper!($inst, reg!(), imm!(), reg!());
@@ -84,6 +95,7 @@ macro_rules! test_load_mem {
         test!(expect, concat!($name, " a0, 1"), $parser);
         let expect = ParserResult::<RISCV> {
             data: vec![],
+            sections: vec![],
             text: vec![
                 ParserResultText::Text(ParserInst::<RISCV> {
                     line: 0,
@@ -96,10 +108,12 @@ macro_rules! test_load_mem {
                     opd: vec![reg!(), imm!(-1), reg!()],
                 }),
             ],
+            labels: vec![],
         };
         test!(expect, concat!($name, " a0, 0xffffff"), $parser);
         let expect = ParserResult::<RISCV> {
             data: vec![],
+            sections: vec![],
             text: vec![
                 ParserResultText::Text(ParserInst::<RISCV> {
                     line: 0,
@@ -112,8 +126,9 @@ macro_rules! test_load_mem {
                     opd: vec![reg!(), lbl_delta_low!(), reg!()],
                 }),
             ],
+            labels: vec![],
         };
-        test!(expect, concat!("a:", $name, " a0, a"), $parser);
+        test_labeled!(expect, concat!("a:", $name, " a0, a"), $parser);
     };
 }
 
@@ -127,6 +142,7 @@ macro_rules! test_store_mem {
         test!(expect, concat!($name, " a0, 1"), $parser);
         let expect = ParserResult::<RISCV> {
             data: vec![],
+            sections: vec![],
             text: vec![
                 ParserResultText::Text(ParserInst::<RISCV> {
                     line: 0,
@@ -139,10 +155,12 @@ macro_rules! test_store_mem {
                     opd: vec![reg!(A0), imm!(-1), reg!(A1)],
                 }),
             ],
+            labels: vec![],
         };
         test!(expect, concat!($name, " a0, 0xffffff, a1"), $parser);
         let expect = ParserResult::<RISCV> {
             data: vec![],
+            sections: vec![],
             text: vec![
                 ParserResultText::Text(ParserInst::<RISCV> {
                     line: 0,
@@ -155,8 +173,9 @@ macro_rules! test_store_mem {
                     opd: vec![reg!(A0), lbl_delta_low!(), reg!(A1)],
                 }),
             ],
+            labels: vec![],
         };
-        test!(expect, concat!("a:", $name, " a0, a, a1"), $parser);
+        test_labeled!(expect, concat!("a:", $name, " a0, a, a1"), $parser);
     };
 }
 
@@ -170,7 +189,7 @@ pub fn test() {
     let expect = expect_helper!(Addi, reg!(), reg!(), imm!());
     test!(expect, "addi a0, a0, 1", parser);
     let expect = expect_helper!(Addi, reg!(), reg!(), lbl_low!());
-    test!(expect, "a: addi a0, a0, a", parser);
+    test_labeled!(expect, "a: addi a0, a0, a", parser);
 
     let expect = expect_helper!(And, reg!(), reg!(), reg!());
     test!(expect, "and a0, a0, a0", parser);
@@ -182,22 +201,22 @@ pub fn test() {
     test!(expect, "auipc a0, 1", parser);
 
     let expect = expect_helper!(Beq, reg!(), reg!(), lbl!());
-    test!(expect, "a: beq a0, a0, a", parser);
+    test_labeled!(expect, "a: beq a0, a0, a", parser);
 
     let expect = expect_helper!(Bge, reg!(), reg!(), lbl!());
-    test!(expect, "a: bge a0, a0, a", parser);
+    test_labeled!(expect, "a: bge a0, a0, a", parser);
 
     let expect = expect_helper!(Bgeu, reg!(), reg!(), lbl!());
-    test!(expect, "a: bgeu a0, a0, a", parser);
+    test_labeled!(expect, "a: bgeu a0, a0, a", parser);
 
     let expect = expect_helper!(Blt, reg!(), reg!(), lbl!());
-    test!(expect, "a: blt a0, a0, a", parser);
+    test_labeled!(expect, "a: blt a0, a0, a", parser);
 
     let expect = expect_helper!(Bltu, reg!(), reg!(), lbl!());
-    test!(expect, "a: bltu a0, a0, a", parser);
+    test_labeled!(expect, "a: bltu a0, a0, a", parser);
 
     let expect = expect_helper!(Bne, reg!(), reg!(), lbl!());
-    test!(expect, "a: bne a0, a0, a", parser);
+    test_labeled!(expect, "a: bne a0, a0, a", parser);
 
     // no csr test
 
@@ -214,9 +233,9 @@ pub fn test() {
     // test!(expect, "fence.i", parser);
 
     let expect = expect_helper!(Jal, reg!(Ra), lbl!());
-    test!(expect, "a: jal a", parser);
+    test_labeled!(expect, "a: jal a", parser);
     let expect = expect_helper!(Jal, reg!(), lbl!());
-    test!(expect, "a: jal a0, a", parser);
+    test_labeled!(expect, "a: jal a0, a", parser);
 
     let expect = expect_helper!(Jalr, reg!(), reg!(), imm!());
     test!(expect, "jalr a0, a0, 1", parser);
@@ -235,7 +254,7 @@ pub fn test() {
     let expect = expect_helper!(Lui, reg!(), imm!());
     test!(expect, "lui a0, 1", parser);
     let expect = expect_helper!(Lui, reg!(), lbl_high!());
-    test!(expect, "a: lui a0, a", parser);
+    test_labeled!(expect, "a: lui a0, a", parser);
 
     test_load_mem!(Lw, "lw", parser);
 
@@ -290,40 +309,41 @@ pub fn test() {
     test!(expect, "xori a0, a0, 1", parser);
 
     let expect = expect_helper!(Jal, reg!(Zero), lbl!());
-    test!(expect, "a: b a", parser);
+    test_labeled!(expect, "a: b a", parser);
 
     let expect = expect_helper!(Beq, reg!(), reg!(Zero), lbl!());
-    test!(expect, "a: beqz a0, a", parser);
+    test_labeled!(expect, "a: beqz a0, a", parser);
 
     let expect = expect_helper!(Bge, reg!(), reg!(Zero), lbl!());
-    test!(expect, "a: bgez a0, a", parser);
+    test_labeled!(expect, "a: bgez a0, a", parser);
 
     let expect = expect_helper!(Blt, reg!(A1), reg!(A0), lbl!());
-    test!(expect, "a: bgt a0, a1, a", parser);
+    test_labeled!(expect, "a: bgt a0, a1, a", parser);
 
     let expect = expect_helper!(Bltu, reg!(A1), reg!(A0), lbl!());
-    test!(expect, "a: bgtu a0, a1, a", parser);
+    test_labeled!(expect, "a: bgtu a0, a1, a", parser);
 
     let expect = expect_helper!(Blt, reg!(Zero), reg!(), lbl!());
-    test!(expect, "a: bgtz a0, a", parser);
+    test_labeled!(expect, "a: bgtz a0, a", parser);
 
     let expect = expect_helper!(Bge, reg!(A1), reg!(A0), lbl!());
-    test!(expect, "a: ble a0, a1, a", parser);
+    test_labeled!(expect, "a: ble a0, a1, a", parser);
 
     let expect = expect_helper!(Bgeu, reg!(A1), reg!(A0), lbl!());
-    test!(expect, "a: bleu a0, a1, a", parser);
+    test_labeled!(expect, "a: bleu a0, a1, a", parser);
 
     let expect = expect_helper!(Bge, reg!(Zero), reg!(), lbl!());
-    test!(expect, "a: blez a0, a", parser);
+    test_labeled!(expect, "a: blez a0, a", parser);
 
     let expect = expect_helper!(Blt, reg!(), reg!(Zero), lbl!());
-    test!(expect, "a: bltz a0, a", parser);
+    test_labeled!(expect, "a: bltz a0, a", parser);
 
     let expect = expect_helper!(Bne, reg!(), reg!(Zero), lbl!());
-    test!(expect, "a: bnez a0, a", parser);
+    test_labeled!(expect, "a: bnez a0, a", parser);
 
     let expect = ParserResult::<RISCV> {
         data: vec![],
+        sections: vec![],
         text: vec![
             ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
@@ -336,13 +356,14 @@ pub fn test() {
                 opd: vec![reg!(Ra), reg!(T1), lbl_delta_low!()],
             }),
         ],
+        labels: vec![],
     };
-    test!(expect, "a: call a", parser);
+    test_labeled!(expect, "a: call a", parser);
 
     // no csr test
 
     let expect = expect_helper!(Jal, reg!(Zero), lbl!());
-    test!(expect, "a: j a", parser);
+    test_labeled!(expect, "a: j a", parser);
 
     let expect = expect_helper!(Jalr, reg!(Zero), reg!(), imm!(0));
     test!(expect, "jr a0", parser);
@@ -351,6 +372,7 @@ pub fn test() {
 
     let expect = ParserResult::<RISCV> {
         data: vec![],
+        sections: vec![],
         text: vec![
             ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
@@ -363,13 +385,15 @@ pub fn test() {
                 opd: vec![reg!(), reg!(), lbl_delta_low!()],
             }),
         ],
+        labels: vec![],
     };
-    test!(expect, "a: la a0, a", parser);
+    test_labeled!(expect, "a: la a0, a", parser);
 
     let expect = expect_helper!(Addi, reg!(), reg!(Zero), imm!());
     test!(expect, "li a0, 1", parser);
     let expect = ParserResult::<RISCV> {
         data: vec![],
+        sections: vec![],
         text: vec![
             ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
@@ -382,6 +406,7 @@ pub fn test() {
                 opd: vec![reg!(), reg!(), imm!(-1)],
             }),
         ],
+        labels: vec![],
     };
     test!(expect, "li a0, 0xffffff", parser);
 
@@ -405,6 +430,7 @@ pub fn test() {
 
     let expect = ParserResult::<RISCV> {
         data: vec![],
+        sections: vec![],
         text: vec![
             ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
@@ -417,11 +443,13 @@ pub fn test() {
                 opd: vec![reg!(A0), reg!(A0), imm!(24)],
             }),
         ],
+        labels: vec![],
     };
     test!(expect, "sext.b a0, a1", parser);
 
     let expect = ParserResult::<RISCV> {
         data: vec![],
+        sections: vec![],
         text: vec![
             ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
@@ -434,6 +462,7 @@ pub fn test() {
                 opd: vec![reg!(A0), reg!(A0), imm!(16)],
             }),
         ],
+        labels: vec![],
     };
     test!(expect, "sext.h a0, a1", parser);
 
@@ -454,6 +483,7 @@ pub fn test() {
 
     let expect = ParserResult::<RISCV> {
         data: vec![],
+        sections: vec![],
         text: vec![
             ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
@@ -466,11 +496,13 @@ pub fn test() {
                 opd: vec![reg!(Zero), reg!(T1), lbl_delta_low!()],
             }),
         ],
+        labels: vec![],
     };
-    test!(expect, "a: tail a", parser);
+    test_labeled!(expect, "a: tail a", parser);
 
     let expect = ParserResult::<RISCV> {
         data: vec![],
+        sections: vec![],
         text: vec![
             ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
@@ -483,11 +515,13 @@ pub fn test() {
                 opd: vec![reg!(A0), reg!(A0), imm!(24)],
             }),
         ],
+        labels: vec![],
     };
     test!(expect, "zext.b a0, a1", parser);
 
     let expect = ParserResult::<RISCV> {
         data: vec![],
+        sections: vec![],
         text: vec![
             ParserResultText::Text(ParserInst::<RISCV> {
                 line: 0,
@@ -500,6 +534,7 @@ pub fn test() {
                 opd: vec![reg!(A0), reg!(A0), imm!(16)],
             }),
         ],
+        labels: vec![],
     };
     test!(expect, "zext.h a0, a1", parser);
 }
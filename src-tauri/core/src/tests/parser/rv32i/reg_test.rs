@@ -18,6 +18,7 @@ pub fn test() {
             for k in 0..VALID_REG_NAME.len() {
                 let expect = ParserResult::<RISCV> {
                     data: vec![],
+                    sections: vec![],
                     text: vec![ParserResultText::Text(ParserInst::<RISCV> {
                         line: 0,
                         op: RV32IInstruction::Add.into(),
@@ -33,6 +34,7 @@ pub fn test() {
                             ),
                         ],
                     })],
+                    labels: vec![],
                 };
 
                 let res = parser
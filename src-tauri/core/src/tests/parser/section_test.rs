@@ -0,0 +1,135 @@
+use crate::{
+    interface::assembler::Assembler,
+    modules::riscv::basic::{assembler::assembler::RiscVAssembler, interface::parser::*},
+    types::middleware_types::{AssemblerConfig, SectionConfig},
+};
+
+#[test]
+pub fn test_section() {
+    let mut parser = RISCVParser::new(&vec![RISCVExtension::RV32I]);
+    let res = parser
+        .parse(
+            &"
+        .rodata
+msg:    .asciz \"hi\"
+        .bss
+buf:    .space 8
+        .section .sdata
+val:    .word 1
+        .data
+        .byte 2
+        .section \".rodata\"
+        .byte 3
+        .text
+        la a0, msg
+        la a1, buf
+        la a2, val
+"
+            .to_string(),
+        )
+        .unwrap();
+    assert_eq!(res.data, vec![2]);
+    assert_eq!(
+        res.sections,
+        vec![
+            ParserResultSection {
+                name: ".rodata".to_string(),
+                line: 1,
+                data: vec![b'h', b'i', 0, 3],
+            },
+            ParserResultSection {
+                name: ".bss".to_string(),
+                line: 3,
+                data: vec![0; 8],
+            },
+            ParserResultSection {
+                name: ".sdata".to_string(),
+                line: 5,
+                data: vec![1, 0, 0, 0],
+            },
+        ]
+    );
+    // label operands of the auipc in each `la`
+    let labels = res
+        .text
+        .iter()
+        .filter_map(|inst| match inst {
+            ParserResultText::Text(inst) => match inst.opd[1] {
+                ParserRISCVInstOpd::Imm(ParserRISCVImmediate::Lbl((lbl, _))) => Some(lbl),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec![
+            ParserRISCVLabel::Section(0, 0),
+            ParserRISCVLabel::Section(1, 0),
+            ParserRISCVLabel::Section(2, 0),
+        ]
+    );
+
+    assert!(parser.parse(&".bss\n.word 1\n".to_string()).is_err());
+}
+
+#[test]
+pub fn test_section_layout() {
+    let mut parser = RISCVParser::new(&vec![RISCVExtension::RV32I]);
+    let code = ".rodata\n.word 1\n.section .x\n.word 2\n.text\nnop\n".to_string();
+    let mut assembler = RiscVAssembler::new();
+    let res = assembler.assemble(parser.parse(&code).unwrap()).unwrap();
+    let default = AssemblerConfig::default();
+    assert_eq!(
+        res.sections
+            .iter()
+            .map(|section| section.address as u64)
+            .collect::<Vec<_>>(),
+        vec![
+            default.dot_rodata_base_address,
+            default.dot_rodata_base_address + 4
+        ]
+    );
+
+    // sections may not overlap the text, the extern segment or the stack
+    for (base_address, segment) in [
+        (default.dot_text_base_address, "the text"),
+        (
+            default.dot_extern_base_address + 0x100,
+            "the extern segment",
+        ),
+        (default.stack_pointer_sp, "the stack"),
+    ] {
+        assembler.update_config(&AssemblerConfig {
+            sections: vec![SectionConfig {
+                name: ".x".to_string(),
+                base_address,
+                alignment: 4,
+                read_only: false,
+            }],
+            ..Default::default()
+        });
+        let err = assembler
+            .assemble(parser.parse(&code).unwrap())
+            .err()
+            .unwrap();
+        // at the line of `.section .x`
+        assert_eq!(err[0].line, 2);
+        assert_eq!(err[0].msg, format!("section .x overlaps {}", segment));
+    }
+}
+
+#[test]
+pub fn test_section_dump() {
+    let mut parser = RISCVParser::new(&vec![RISCVExtension::RV32I]);
+    let mut assembler = RiscVAssembler::new();
+    // only .data and .text are dumped, other sections aren't dropped silently
+    let code = ".data\n.word 1\n.rodata\n.word 2\n.bss\n.text\nnop\n".to_string();
+    let err = assembler.dump(parser.parse(&code).unwrap()).err().unwrap();
+    assert_eq!(err.len(), 1);
+    assert_eq!(err[0].line, 2);
+    assert_eq!(err[0].msg, "section .rodata can't be dumped");
+
+    let code = ".data\n.word 1\n.bss\n.text\nnop\n".to_string();
+    assert!(assembler.dump(parser.parse(&code).unwrap()).is_ok());
+}
@@ -0,0 +1,123 @@
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::{
+    interface::{
+        assembler::Assembler,
+        parser::Parser,
+        simulator::{Simulator, SimulatorEventSink},
+    },
+    modules::riscv::basic::{
+        assembler::assembler::RiscVAssembler,
+        interface::parser::{RISCVExtension, RISCVParser},
+    },
+    simulator::simulator::RISCVSimulator,
+    types::middleware_types::{AssemblerConfig, Optional},
+    utility::ptr::Ptr,
+};
+
+pub struct FakeMiddleware {
+    pub input: Option<String>,
+    pub input_res: Option<Result<(), String>>,
+    pub output: Option<String>,
+    pub sim_ptr: Ptr<RISCVSimulator>,
+    pub success: bool,
+    // whether an update arrived since `expect_update`
+    pub cv: (Condvar, Mutex<bool>),
+}
+
+impl FakeMiddleware {
+    /// Forgets the updates sent so far, `wait` returns on the next one.
+    pub fn expect_update(&self) {
+        *self.cv.1.lock().unwrap() = false;
+    }
+
+    /// Waits for an update after `expect_update`, returns whether it
+    /// succeeded.
+    pub fn wait(&self) -> bool {
+        let updated = self.cv.1.lock().unwrap();
+        drop(self.cv.0.wait_while(updated, |updated| !*updated).unwrap());
+        self.success
+    }
+
+    /// Starts the simulator with `f` and waits for the update it ends with,
+    /// returns whether it succeeded.
+    pub fn start(&self, f: impl FnOnce(&mut RISCVSimulator) -> Result<(), String>) -> bool {
+        self.expect_update();
+        f(self.sim_ptr.as_mut()).unwrap();
+        self.wait()
+    }
+}
+
+impl SimulatorEventSink for Ptr<FakeMiddleware> {
+    fn request_input(&self, _filepath: &str) -> Result<(), String> {
+        let self_ptr = *self;
+        std::thread::spawn(move || {
+            let _self = self_ptr.as_mut();
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            _self.input_res = Some(
+                _self
+                    .sim_ptr
+                    .as_mut()
+                    .syscall_input(_self.input.as_ref().unwrap()),
+            );
+        });
+        Ok(())
+    }
+
+    fn output(&self, _filepath: &str, output: &str) -> Result<(), String> {
+        self.as_mut().output = Some(output.to_string());
+        Ok(())
+    }
+
+    fn update(&self, simulator: &dyn Simulator, res: Optional, _paused: bool) {
+        // the input resumes the simulator
+        if simulator.is_waiting_input() {
+            return;
+        }
+        let _self = self.as_mut();
+        let mut updated = _self.cv.1.lock().unwrap();
+        _self.success = res.success;
+        *updated = true;
+        _self.cv.0.notify_one();
+    }
+}
+
+/// A simulator with a `FakeMiddleware` as its event sink, both boxed so the
+/// pointers between them stay valid.
+pub fn fake_sim(filepath: &str) -> (Box<RISCVSimulator>, Box<FakeMiddleware>) {
+    let mut sim = Box::new(RISCVSimulator::new(filepath));
+    let mid = Box::new(FakeMiddleware {
+        input: None,
+        input_res: None,
+        output: None,
+        sim_ptr: Ptr::new(&*sim),
+        success: false,
+        cv: (Condvar::new(), Mutex::new(false)),
+    });
+    sim.set_event_sink(Some(Arc::new(Ptr::new(&*mid))));
+    (sim, mid)
+}
+
+/// Assembles `src` with `conf` and loads it.
+pub fn load_program(sim: &mut RISCVSimulator, src: &str, conf: &AssemblerConfig) {
+    let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(conf.xlen));
+    parser.set_data_endianness(conf.data_endianness);
+    let mut assembler = RiscVAssembler::new();
+    assembler.update_config(conf);
+    let inst = assembler
+        .assemble(parser.parse(&src.to_string()).unwrap())
+        .unwrap();
+    sim.update_config(conf).unwrap();
+    sim.load_inst(inst).unwrap();
+}
+
+/// Runs `src` with `conf` in a new simulator until it stops.
+pub fn run_program(
+    src: &str,
+    conf: &AssemblerConfig,
+) -> (Box<RISCVSimulator>, Box<FakeMiddleware>) {
+    let (mut sim, mid) = fake_sim("");
+    load_program(&mut sim, src, conf);
+    mid.start(RISCVSimulator::run);
+    (sim, mid)
+}
@@ -1,18 +1,16 @@
-use std::sync::{Condvar, Mutex};
-
 use crate::{
     interface::{
         assembler::{AssembleResult, Instruction, InstructionSet, Operand},
-        simulator::{FakeMiddlewareTrait, Simulator},
+        simulator::Simulator,
     },
     modules::riscv::{
-        basic::interface::parser::{ParserInstSet, RISCV},
+        basic::interface::parser::{ParserInstSet, ParserRISCVInstOp, RISCV},
         rv32i::constants::{RV32IInstruction, RV32IRegister},
+        rvb::constants::RVBInstruction,
     },
     simulator::simulator::RISCVSimulator,
-    tests::simulator::helper::FakeMiddleware,
+    tests::simulator::helper::fake_sim,
     types::middleware_types::{AssemblerConfig, MemoryReturnRange},
-    utility::ptr::Ptr,
 };
 
 static CONFIG: Lazy<AssemblerConfig> = Lazy::new(|| Default::default());
@@ -55,7 +53,7 @@ macro_rules! opd {
 /// - `ebreak_placeholder`: how many ebreak inserted after this instruction (to
 ///   stop the simulator and make the jump address a valid instruction address)
 fn test_helper(
-    op: Op,
+    op: impl Into<ParserRISCVInstOp>,
     opd: Vec<Opd>,
     reg: Vec<(Reg, u32)>,
     data: Vec<u8>,
@@ -63,26 +61,12 @@ fn test_helper(
     expect: Expect,
     ebreak_placeholder: usize,
 ) {
-    let sim = RISCVSimulator::new("");
-    let sim_ptr = Ptr::new(&sim);
-    let sim = sim_ptr.as_mut();
-    let mut mid = FakeMiddleware {
-        input,
-        input_res: None,
-        output: None,
-        sim_ptr,
-        cv: (Condvar::new(), Mutex::new(())),
-        success: false,
-    };
-    let mid_ptr = Ptr::new(&mid);
-    let mid = mid_ptr.as_mut();
-    unsafe {
-        sim.set_fake_middleware(Some(std::mem::transmute::<_, &'static mut _>(
-            mid_ptr.as_mut() as &mut dyn FakeMiddlewareTrait,
-        )));
-    }
+    let (mut sim, mut mid) = fake_sim("");
+    mid.input = input;
     let inst = AssembleResult {
+        labels: Default::default(),
         data,
+        sections: vec![],
         instruction: {
             let mut instruction = reg
                 .iter()
@@ -100,7 +84,7 @@ fn test_helper(
             instruction.push(InstructionSet {
                 line_number: 0,
                 instruction: Instruction::<RISCV> {
-                    operation: <RISCV as ParserInstSet>::Operator::RV32I(op),
+                    operation: op.into(),
                     operands: opd,
                 },
                 address: 0,
@@ -126,17 +110,12 @@ fn test_helper(
     sim.load_inst(inst).unwrap();
     let mut expect_reg = sim.get_register().to_vec();
     for &(reg, val) in &reg {
-        expect_reg[reg as usize] = val;
+        expect_reg[reg as usize] = val as u64;
     }
     if let &Some((reg, val)) = &expect.reg_change {
-        expect_reg[reg as usize] = val;
+        expect_reg[reg as usize] = val as u64;
     }
-    let thread_sim = sim_ptr.clone();
-    std::thread::spawn(move || {
-        std::thread::sleep(std::time::Duration::from_millis(10));
-        thread_sim.as_mut().run().unwrap();
-    });
-    drop(mid.cv.0.wait(mid.cv.1.lock().unwrap()));
+    mid.start(RISCVSimulator::run);
     assert_eq!(sim.get_pc_idx(), expect.pc_idx);
     assert_eq!(sim.get_register(), &expect_reg);
     if let Some((idx, val)) = &expect.data_change {
@@ -158,7 +137,12 @@ fn test_helper(
 /// - `opd`: operand list (can be generated through `opd!`)
 /// - `reg`: initial register value
 /// - `change`: changes that the instruction makes
-fn test_helper_only_reg(op: Op, opd: Vec<Opd>, reg: Vec<(Reg, u32)>, change: RegChange) {
+fn test_helper_only_reg(
+    op: impl Into<ParserRISCVInstOp>,
+    opd: Vec<Opd>,
+    reg: Vec<(Reg, u32)>,
+    change: RegChange,
+) {
     test_helper(
         op,
         opd,
@@ -570,3 +554,125 @@ fn test() {
         (S4, 123 ^ 23),
     );
 }
+
+#[test]
+fn test_bitmanip() {
+    use RVBInstruction::*;
+
+    let neg = |val: i32| val as u32;
+    let table: Vec<(RVBInstruction, Vec<Opd>, Vec<(Reg, u32)>, RegChange)> = vec![
+        // Zba
+        (Sh1add, opd![A0, A1, A2], vec![(A1, 3), (A2, 5)], (A0, 11)),
+        (Sh2add, opd![A0, A1, A2], vec![(A1, 3), (A2, 5)], (A0, 17)),
+        (Sh3add, opd![A0, A1, A2], vec![(A1, 3), (A2, 5)], (A0, 29)),
+        // Zbb
+        (
+            Andn,
+            opd![A0, A1, A2],
+            vec![(A1, 0b1110), (A2, 0b0110)],
+            (A0, 0b1000),
+        ),
+        (
+            Orn,
+            opd![A0, A1, A2],
+            vec![(A1, 0b0001), (A2, neg(-3))],
+            (A0, 0b0011),
+        ),
+        (
+            Xnor,
+            opd![A0, A1, A2],
+            vec![(A1, 0b1010), (A2, neg(-1))],
+            (A0, 0b1010),
+        ),
+        (Clz, opd![A0, A1], vec![(A1, 0x0010_0000)], (A0, 11)),
+        (Clz, opd![A0, A1], vec![], (A0, 32)),
+        (Ctz, opd![A0, A1], vec![(A1, 0x0010_0000)], (A0, 20)),
+        (Ctz, opd![A0, A1], vec![], (A0, 32)),
+        (Cpop, opd![A0, A1], vec![(A1, neg(-2))], (A0, 31)),
+        (Max, opd![A0, A1, A2], vec![(A1, neg(-1)), (A2, 1)], (A0, 1)),
+        (
+            Maxu,
+            opd![A0, A1, A2],
+            vec![(A1, neg(-1)), (A2, 1)],
+            (A0, neg(-1)),
+        ),
+        (
+            Min,
+            opd![A0, A1, A2],
+            vec![(A1, neg(-1)), (A2, 1)],
+            (A0, neg(-1)),
+        ),
+        (
+            Minu,
+            opd![A0, A1, A2],
+            vec![(A1, neg(-1)), (A2, 1)],
+            (A0, 1),
+        ),
+        (SextB, opd![A0, A1], vec![(A1, 0x180)], (A0, neg(-128))),
+        (
+            SextH,
+            opd![A0, A1],
+            vec![(A1, 0x1_8000)],
+            (A0, neg(-0x8000)),
+        ),
+        (ZextH, opd![A0, A1], vec![(A1, neg(-1))], (A0, 0xffff)),
+        (
+            Rol,
+            opd![A0, A1, A2],
+            vec![(A1, 0x8000_0001), (A2, 33)],
+            (A0, 3),
+        ),
+        (
+            Ror,
+            opd![A0, A1, A2],
+            vec![(A1, 0x8000_0001), (A2, 1)],
+            (A0, 0xc000_0000),
+        ),
+        (
+            Rori,
+            opd![A0, A1, 4],
+            vec![(A1, 0x1234_5678)],
+            (A0, 0x8123_4567),
+        ),
+        (
+            Rori,
+            opd![A0, A1, 0],
+            vec![(A1, 0x1234_5678)],
+            (A0, 0x1234_5678),
+        ),
+        (
+            OrcB,
+            opd![A0, A1],
+            vec![(A1, 0x0100_2000)],
+            (A0, 0xff00_ff00),
+        ),
+        (
+            Rev8,
+            opd![A0, A1],
+            vec![(A1, 0x1234_5678)],
+            (A0, 0x7856_3412),
+        ),
+        // Zbs
+        (
+            Bclr,
+            opd![A0, A1, A2],
+            vec![(A1, 0xff), (A2, 35)],
+            (A0, 0xf7),
+        ),
+        (Bclri, opd![A0, A1, 0], vec![(A1, 0xff)], (A0, 0xfe)),
+        (Bext, opd![A0, A1, A2], vec![(A1, 0x10), (A2, 4)], (A0, 1)),
+        (Bexti, opd![A0, A1, 3], vec![(A1, 0x10)], (A0, 0)),
+        (Binv, opd![A0, A1, A2], vec![(A1, 0x10), (A2, 4)], (A0, 0)),
+        (Binvi, opd![A0, A1, 31], vec![], (A0, 0x8000_0000)),
+        (
+            Bset,
+            opd![A0, A1, A2],
+            vec![(A1, 0x10), (A2, 0)],
+            (A0, 0x11),
+        ),
+        (Bseti, opd![A0, A1, 8], vec![(A1, 0x10)], (A0, 0x110)),
+    ];
+    for (op, opd, reg, change) in table {
+        test_helper_only_reg(op, opd, reg, change);
+    }
+}
@@ -0,0 +1,1496 @@
+use std::sync::{Arc, Condvar, Mutex};
+
+use super::helper::{fake_sim, load_program, run_program};
+use crate::{
+    interface::{
+        assembler::{AssembleResult, AssembleSection, Instruction, InstructionSet},
+        simulator::{Simulator, SimulatorEventSink},
+    },
+    modules::riscv::basic::interface::parser::{
+        ParserInstSet,
+        RISCVImmediate,
+        RV32CInstruction,
+        RV32IInstruction,
+        RISCV,
+    },
+    simulator::simulator::RISCVSimulator,
+    types::middleware_types::{
+        AbiViolation,
+        AssemblerConfig,
+        BranchPredictorConfig,
+        BranchPredictorKind,
+        CacheConfig,
+        DisplayFormat,
+        ElementWidth,
+        Endianness,
+        ExecutionMode,
+        HartScheduler,
+        InstructionMix,
+        LineProfile,
+        MemoryFaultKind,
+        MemoryReturnRange,
+        MemoryView,
+        Optional,
+        RunLimit,
+        RunLimits,
+        SimulatorDelta,
+        StaticPrediction,
+        TraceFormat,
+        UninitCheck,
+        UninitSource,
+        WatchValue,
+        Xlen,
+    },
+};
+
+#[test]
+fn test() {
+    let (mut sim, mid) = fake_sim("1");
+    let inst = AssembleResult {
+        labels: Default::default(),
+        data: vec![0x13, 0x00, 0x00, 0x00],
+        sections: vec![],
+        instruction: vec![
+            InstructionSet {
+                line_number: 0,
+                instruction: Instruction::<RISCV> {
+                    operation: <RISCV as ParserInstSet>::Operator::RV32I(RV32IInstruction::Add),
+                    operands: vec![0, 0, 0],
+                },
+                address: 0,
+                code: 0,
+                basic: String::new(),
+            };
+            5
+        ],
+    };
+    sim.run().unwrap_err();
+    sim.debug().unwrap_err();
+    sim.step().unwrap_err();
+    sim.undo().unwrap_err();
+    sim.resume().unwrap_err();
+    sim.stop().unwrap_err();
+    sim.load_inst(inst).unwrap();
+    assert!(mid.start(RISCVSimulator::debug));
+    sim.reset().unwrap();
+    mid.start(RISCVSimulator::step);
+    sim.undo().unwrap();
+    mid.start(RISCVSimulator::resume);
+    sim.set_breakpoint(1, None).unwrap();
+    sim.set_breakpoint(999, None).unwrap_err();
+    sim.set_breakpoint(1, Some(64)).unwrap_err();
+    sim.remove_breakpoint(1, None).unwrap();
+    sim.remove_breakpoint(999, None).unwrap_err();
+    assert_eq!(sim.get_filepath(), "1");
+    sim.set_memory_return_range(MemoryReturnRange { start: 0, len: 4 })
+        .unwrap();
+    assert_eq!(
+        sim.get_memory_return_range(),
+        MemoryReturnRange { start: 0, len: 4 }
+    );
+    sim.get_raw_inst();
+    let config: AssemblerConfig = Default::default();
+    sim.set_memory_return_range(MemoryReturnRange {
+        start: config.dot_text_base_address,
+        len: 4,
+    })
+    .unwrap();
+    sim.get_memory();
+    sim.set_memory_return_range(MemoryReturnRange {
+        start: config.dot_text_base_address,
+        len: 8,
+    })
+    .unwrap();
+    sim.get_memory();
+    sim.set_memory_return_range(MemoryReturnRange { start: 1, len: 1 })
+        .unwrap_err();
+    sim.update_config(&config).unwrap();
+}
+
+#[test]
+fn test_read_only_section() {
+    let (mut sim, mut mid) = fake_sim("");
+    let inst_helper = |op: RV32IInstruction, operands: Vec<RISCVImmediate>| InstructionSet {
+        line_number: 0,
+        instruction: Instruction::<RISCV> {
+            operation: <RISCV as ParserInstSet>::Operator::RV32I(op),
+            operands,
+        },
+        address: 0,
+        code: 0,
+        basic: String::new(),
+    };
+    let inst = AssembleResult {
+        labels: Default::default(),
+        data: vec![],
+        sections: vec![AssembleSection {
+            name: ".rodata".to_string(),
+            address: 0x10000000,
+            data: vec![0x78, 0x56, 0x34, 0x12],
+            read_only: true,
+        }],
+        instruction: vec![
+            // lui a1, 0x10000; lw a0, 0(a1); sw a0, 0(a1)
+            inst_helper(RV32IInstruction::Lui, vec![11, 0x10000]),
+            inst_helper(RV32IInstruction::Lw, vec![10, 0, 11]),
+            inst_helper(RV32IInstruction::Sw, vec![10, 0, 11]),
+        ],
+    };
+    sim.load_inst(inst).unwrap();
+    assert!(!mid.start(RISCVSimulator::run));
+    assert_eq!(sim.get_register()[10], 0x12345678);
+    assert_eq!(sim.get_pc_idx(), Some(2));
+}
+
+#[test]
+fn test_compressed_stream() {
+    let (mut sim, mut mid) = fake_sim("");
+    let inst_helper = |op: <RISCV as ParserInstSet>::Operator,
+                       operands: Vec<RISCVImmediate>,
+                       code: u32| InstructionSet {
+        line_number: 0,
+        instruction: Instruction::<RISCV> {
+            operation: op,
+            operands,
+        },
+        address: 0,
+        code,
+        basic: String::new(),
+    };
+    let inst = AssembleResult {
+        labels: Default::default(),
+        data: vec![],
+        sections: vec![],
+        instruction: vec![
+            // c.li a0, 5; jal ra, 6; c.addi a0, 2; c.addi a0, 1
+            inst_helper(RV32CInstruction::CLi.into(), vec![10, 0, 5], 0x4515),
+            inst_helper(RV32IInstruction::Jal.into(), vec![1, 6], 0x006000ef),
+            inst_helper(RV32CInstruction::CAddi.into(), vec![10, 10, 2], 0x0509),
+            inst_helper(RV32CInstruction::CAddi.into(), vec![10, 10, 1], 0x0505),
+        ],
+    };
+    sim.load_inst(inst).unwrap();
+    assert!(mid.start(RISCVSimulator::run));
+    let base = AssemblerConfig::default().dot_text_base_address as u32;
+    assert_eq!(sim.get_register()[10], 6);
+    assert_eq!(sim.get_register()[1], base as u64 + 6);
+    sim.set_memory_return_range(MemoryReturnRange {
+        start: base as u64,
+        len: 8,
+    })
+    .unwrap();
+    assert_eq!(sim.get_memory(), vec![0x00ef4515, 0x05090060]);
+}
+
+#[test]
+fn test_rv64() {
+    let config = AssemblerConfig {
+        xlen: Xlen::Rv64,
+        ..Default::default()
+    };
+    let code = "
+.data
+buf: .word 0 0
+.text
+    li a0, 0x123456789abcdef0
+    srli a1, a0, 32
+    li a2, 0x7fffffff
+    addiw a3, a2, 1
+    addw a4, a2, a2
+    la t0, buf
+    sd a0, 0(t0)
+    ld a5, 0(t0)
+    lw a6, 0(t0)
+    lwu a7, 0(t0)
+    zext.w s0, a3
+    rev8 s1, a0
+    clz s2, a1
+    rolw s3, a2, a2
+";
+    let (sim, mid) = run_program(code, &config);
+    assert!(mid.success);
+    let reg = sim.get_register();
+    assert_eq!(reg[10], 0x123456789abcdef0);
+    assert_eq!(reg[11], 0x12345678);
+    assert_eq!(reg[13], 0xffff_ffff_8000_0000);
+    assert_eq!(reg[14], 0xffff_ffff_ffff_fffe);
+    assert_eq!(reg[15], 0x123456789abcdef0);
+    assert_eq!(reg[16], 0xffff_ffff_9abc_def0);
+    assert_eq!(reg[17], 0x9abc_def0);
+    assert_eq!(reg[8], 0x8000_0000);
+    assert_eq!(reg[9], 0xf0de_bc9a_7856_3412);
+    assert_eq!(reg[18], 35);
+    assert_eq!(reg[19], 0xffff_ffff_bfff_ffff);
+}
+
+#[test]
+fn test_atomic() {
+    let code = "
+.data
+val: .word 5
+.text
+    la t0, val
+    li a5, 7
+    li t1, 3
+    amoadd.w a0, t1, (t0)
+    amoswap.w.aq a1, zero, (t0)
+    li t1, -1
+    amomaxu.w a2, t1, (t0)
+    amomin.w a3, zero, (t0)
+    lr.w a4, (t0)
+    sw zero, 0(t0)
+    sc.w a6, t1, (t0)
+    lr.w.aq a4, (t0)
+    sc.w.rl a5, t1, (t0)
+    sc.w a7, t1, (t0)
+";
+    let (mut sim, mid) = run_program(code, &AssemblerConfig::default());
+    let word = |sim: &mut RISCVSimulator| {
+        sim.set_memory_return_range(MemoryReturnRange {
+            start: AssemblerConfig::default().dot_data_base_address,
+            len: 4,
+        })
+        .unwrap();
+        sim.get_memory()[0]
+    };
+
+    assert!(mid.success);
+    let reg = sim.get_register();
+    assert_eq!(reg[10], 5);
+    assert_eq!(reg[11], 8);
+    assert_eq!(reg[12], 0);
+    assert_eq!(reg[13], 0xffff_ffff);
+    assert_eq!(reg[14], 0);
+    // the store between lr.w and sc.w drops the reservation
+    assert_eq!(reg[16], 1);
+    assert_eq!(reg[15], 0);
+    // a reservation is only good for one sc.w
+    assert_eq!(reg[17], 1);
+    assert_eq!(word(&mut sim), 0xffff_ffff);
+
+    // undo restores memory, registers and the reservation
+    sim.undo().unwrap();
+    sim.undo().unwrap();
+    assert_eq!(sim.get_register()[15], 7);
+    assert_eq!(word(&mut sim), 0);
+    mid.start(RISCVSimulator::step);
+    assert_eq!(sim.get_register()[15], 0);
+    assert_eq!(word(&mut sim), 0xffff_ffff);
+}
+
+#[test]
+fn test_multi_hart() {
+    let code = "
+.data
+counter: .word 0
+.text
+    csrr a0, mhartid
+    la t0, counter
+    li t1, 1
+    amoadd.w a1, t1, (t0)
+    mv a2, sp
+";
+    let mut config = AssemblerConfig {
+        hart_count: 2,
+        ..Default::default()
+    };
+    let (mut sim, mid) = run_program(code, &config);
+    let counter = |sim: &mut RISCVSimulator| {
+        sim.set_memory_return_range(MemoryReturnRange {
+            start: config.dot_data_base_address,
+            len: 4,
+        })
+        .unwrap();
+        sim.get_memory()[0]
+    };
+
+    assert!(mid.success);
+    assert_eq!(sim.get_hart_count(), 2);
+    for hart in 0..2 {
+        let reg = sim.get_hart_register(hart);
+        assert_eq!(reg[10], hart as u64);
+        // hart 0 reaches the amoadd.w first in round robin
+        assert_eq!(reg[11], hart as u64);
+        assert_eq!(
+            reg[12],
+            config.stack_pointer_sp - hart as u64 * config.hart_stack_size
+        );
+        assert_eq!(sim.get_hart_pc_idx(hart), None);
+    }
+    assert_eq!(counter(&mut sim), 2);
+    // stepping a finished hart leaves the simulator stopped
+    sim.step_hart(0).unwrap_err();
+    sim.resume().unwrap_err();
+    assert!(mid.start(RISCVSimulator::run));
+    assert_eq!(counter(&mut sim), 2);
+
+    // stepping a hart runs it alone, undo switches back to it
+    sim.reset().unwrap();
+    mid.start(|sim| sim.step_hart(1));
+    assert_eq!(sim.get_current_hart(), 1);
+    assert_eq!(sim.get_hart_pc_idx(0), Some(0));
+    assert_eq!(sim.get_hart_pc_idx(1), Some(1));
+    assert_eq!(sim.get_hart_register(1)[10], 1);
+    mid.start(RISCVSimulator::step);
+    assert_eq!(sim.get_current_hart(), 0);
+    assert_eq!(sim.get_hart_pc_idx(0), Some(1));
+    sim.undo().unwrap();
+    assert_eq!(sim.get_current_hart(), 0);
+    assert_eq!(sim.get_hart_pc_idx(0), Some(0));
+    sim.undo().unwrap();
+    assert_eq!(sim.get_current_hart(), 1);
+    assert_eq!(sim.get_hart_pc_idx(1), Some(0));
+    sim.step_hart(2).unwrap_err();
+
+    // breakpoints only stop the harts they belong to
+    sim.reset().unwrap();
+    sim.set_breakpoint(4, Some(1)).unwrap();
+    mid.start(RISCVSimulator::debug);
+    assert_eq!(sim.get_current_hart(), 1);
+    assert_eq!(sim.get_hart_pc_idx(1), Some(4));
+    // hart 0 got there first without stopping
+    assert_eq!(sim.get_hart_pc_idx(0), Some(4));
+    assert_eq!(counter(&mut sim), 0);
+    sim.remove_breakpoint(4, Some(1)).unwrap();
+    mid.start(RISCVSimulator::resume);
+    assert_eq!(counter(&mut sim), 2);
+
+    // the random interleaving only depends on the seed
+    config.hart_count = 4;
+    config.hart_scheduler = HartScheduler::Random;
+    config.hart_schedule_seed = 42;
+    sim.update_config(&config).unwrap();
+    let mut orders = Vec::new();
+    for _ in 0..2 {
+        assert!(mid.start(RISCVSimulator::run));
+        assert_eq!(counter(&mut sim), 4);
+        let order: Vec<_> = (0..4).map(|hart| sim.get_hart_register(hart)[11]).collect();
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![0, 1, 2, 3]);
+        orders.push(order);
+    }
+    assert_eq!(orders[0], orders[1]);
+
+    config.hart_count = 65;
+    sim.update_config(&config).unwrap_err();
+}
+
+#[test]
+fn test_pipeline() {
+    let (mut sim, mid) = fake_sim("");
+    let run = |sim: &mut RISCVSimulator, code: &str, config: AssemblerConfig| {
+        load_program(sim, code, &config);
+        assert!(mid.start(RISCVSimulator::run));
+        let data = sim.get_pipeline().unwrap();
+        (data.cycles, data.instructions, data.stalls, data.flushes)
+    };
+    let config = AssemblerConfig {
+        execution_mode: ExecutionMode::Pipeline,
+        ..Default::default()
+    };
+    let no_forwarding = AssemblerConfig {
+        pipeline_forwarding: false,
+        ..config.clone()
+    };
+    let btfn = AssemblerConfig {
+        pipeline_prediction: StaticPrediction::Btfn,
+        ..config.clone()
+    };
+
+    let straight = "
+    li a0, 1
+    li a1, 2
+    li a2, 3
+";
+    assert_eq!(run(&mut sim, straight, config.clone()), (7, 3, 0, 0));
+
+    let load_use = "
+.data
+v: .word 5
+.text
+    la t0, v
+    lw a0, 0(t0)
+    addi a1, a0, 1
+";
+    assert_eq!(run(&mut sim, load_use, config.clone()), (9, 4, 1, 0));
+    assert_eq!(sim.get_register()[11], 6);
+    assert_eq!(run(&mut sim, load_use, no_forwarding), (14, 4, 6, 0));
+
+    let branch = "
+    li t0, 3
+loop:
+    addi t0, t0, -1
+    bnez t0, loop
+";
+    assert_eq!(run(&mut sim, branch, config.clone()), (15, 7, 0, 2));
+    // the last branch mispredicts, but nothing follows it
+    assert_eq!(run(&mut sim, branch, btfn), (11, 7, 0, 1));
+    assert_eq!(sim.get_pipeline().unwrap().cpi, 11.0 / 7.0);
+
+    // undo rewinds to the cycle fetching the last instruction
+    sim.reset().unwrap();
+    for _ in 0..3 {
+        mid.start(RISCVSimulator::step);
+    }
+    let data = sim.get_pipeline().unwrap();
+    assert_eq!(data.stages, vec![Some(2), Some(1), Some(0), None, None]);
+    sim.undo().unwrap();
+    let data = sim.get_pipeline().unwrap();
+    assert_eq!(data.cycles, 2);
+    assert_eq!(data.stages, vec![Some(1), Some(0), None, None, None]);
+    assert_eq!(sim.get_pc_idx(), Some(2));
+
+    let multi_hart = AssemblerConfig {
+        hart_count: 2,
+        ..config
+    };
+    sim.update_config(&multi_hart).unwrap_err();
+}
+
+#[test]
+fn test_cache() {
+    let code = "
+.data
+v: .word 1
+    .word 2
+    .word 3
+    .word 4
+    .word 5
+.text
+    la t0, v
+    lw a0, 0(t0)
+    lw a0, 4(t0)
+    lw a0, 8(t0)
+    lw a0, 12(t0)
+    lw a0, 16(t0)
+    sw a0, 64(t0)
+    lw a0, 0(t0)
+";
+    // direct mapped, 4 sets of 16 bytes
+    let dcache = CacheConfig {
+        enabled: true,
+        size: 64,
+        block_size: 16,
+        ..Default::default()
+    };
+    let config = AssemblerConfig {
+        l1_icache: CacheConfig {
+            enabled: true,
+            ..Default::default()
+        },
+        l1_dcache: dcache.clone(),
+        ..Default::default()
+    };
+    let (mut sim, mid) = run_program(code, &config);
+    assert!(mid.success);
+
+    // the store evicts the first block, which is reloaded afterwards
+    let data = sim.get_dcache().unwrap();
+    assert_eq!(
+        (data.accesses, data.hits, data.misses, data.writebacks),
+        (7, 3, 4, 1)
+    );
+    assert_eq!(data.lines.len(), 4);
+    assert!(data.lines[0].valid && !data.lines[0].dirty);
+    // 9 instructions in 3 blocks of 16 bytes
+    let data = sim.get_icache().unwrap();
+    assert_eq!((data.hits, data.misses), (6, 3));
+
+    let config = AssemblerConfig {
+        l1_dcache: CacheConfig {
+            associativity: 3,
+            ..dcache
+        },
+        ..Default::default()
+    };
+    sim.update_config(&config).unwrap_err();
+    assert!(sim.get_icache().is_some());
+}
+
+#[test]
+fn test_branch_predictor() {
+    let code = "
+    j main
+f:
+    ret
+main:
+    jal ra, f
+    li t0, 3
+loop:
+    addi t0, t0, -1
+    bnez t0, loop
+";
+    let config = AssemblerConfig {
+        branch_predictor: BranchPredictorConfig {
+            enabled: true,
+            kind: BranchPredictorKind::TwoBit,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (sim, mid) = run_program(code, &config);
+    assert!(mid.success);
+
+    // without a BTB `ret` always mispredicts, the loop branch warms up and
+    // mispredicts on exit
+    let data = sim.get_branch_predictor().unwrap();
+    assert_eq!((data.predictions, data.mispredictions), (6, 3));
+    let sites: Vec<_> = data
+        .sites
+        .iter()
+        .map(|site| (site.line, site.predictions, site.mispredictions))
+        .collect();
+    assert_eq!(sites, vec![(1, 1, 0), (3, 1, 1), (5, 1, 0), (9, 3, 2)]);
+}
+
+#[test]
+fn test_profiler() {
+    let (mut sim, mut mid) = fake_sim("");
+    let code = "
+.data
+v: .word 7
+.text
+    la t1, v
+    lw a0, 0(t1)
+    li t0, 3
+loop:
+    addi t0, t0, -1
+    bnez t0, loop
+    sw a0, 0(t1)
+";
+    load_program(&mut sim, code, &AssemblerConfig::default());
+    assert!(sim.get_profile().is_none());
+    let config = AssemblerConfig {
+        profiler: true,
+        ..Default::default()
+    };
+    sim.update_config(&config).unwrap();
+    assert!(mid.start(RISCVSimulator::run));
+
+    let report = sim.get_profile().unwrap();
+    assert_eq!(report.retired, 11);
+    assert_eq!(
+        report.mix,
+        InstructionMix {
+            alu: 6,
+            load: 1,
+            store: 1,
+            branch: 3,
+            jump: 0,
+            ecall: 0,
+        }
+    );
+    let lines: Vec<_> = report.lines.iter().map(|l| (l.line, l.count)).collect();
+    assert_eq!(lines, vec![(8, 3), (9, 3), (4, 2), (5, 1), (6, 1), (10, 1)]);
+    assert_eq!(report.instructions.len(), 7);
+    assert_eq!(report.instructions[4].count, 3);
+
+    // undo takes the store back
+    sim.undo().unwrap();
+    let report = sim.get_profile().unwrap();
+    assert_eq!((report.retired, report.mix.store), (10, 0));
+    assert!(!report.lines.contains(&LineProfile { line: 10, count: 1 }));
+
+    sim.reset().unwrap();
+    assert_eq!(sim.get_profile().unwrap().retired, 0);
+}
+
+#[test]
+fn test_abi_check() {
+    let (mut sim, mut mid) = fake_sim("");
+    let code = "
+.text
+main:
+    addi sp, sp, -12
+    call f
+    addi sp, sp, 12
+    call g
+    mv a1, t0
+    j end
+f:
+    li s0, 5
+    ret
+g:
+    addi a0, t1, 1
+    addi sp, sp, -16
+    ret
+end:
+    nop
+";
+    load_program(&mut sim, code, &AssemblerConfig::default());
+    assert!(sim.get_abi_warnings().is_none());
+    let config = AssemblerConfig {
+        abi_check: true,
+        ..Default::default()
+    };
+    sim.update_config(&config).unwrap();
+    assert!(mid.start(RISCVSimulator::run));
+
+    let warnings = |sim: &RISCVSimulator| {
+        sim.get_abi_warnings()
+            .unwrap()
+            .into_iter()
+            .map(|warning| (warning.line, warning.kind))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        warnings(&sim),
+        vec![
+            (4, AbiViolation::MisalignedStack),
+            (11, AbiViolation::CalleeSaved),
+            (13, AbiViolation::UninitializedTemporary),
+            (15, AbiViolation::StackPointer),
+            (7, AbiViolation::UninitializedTemporary),
+        ]
+    );
+    let messages: Vec<_> = sim
+        .get_abi_warnings()
+        .unwrap()
+        .into_iter()
+        .map(|warning| warning.message)
+        .collect();
+    assert!(messages[1].starts_with("s0 is 0x5 on return from the call at line 5"));
+    assert!(messages[4].starts_with("t0 is read after the call at line 7"));
+
+    // undo takes the read of t0 back, and the return from g with it
+    for _ in 0..3 {
+        sim.undo().unwrap();
+    }
+    assert_eq!(warnings(&sim).len(), 4);
+    sim.undo().unwrap();
+    assert_eq!(warnings(&sim).len(), 3);
+
+    sim.reset().unwrap();
+    assert!(warnings(&sim).is_empty());
+
+    // sp itself is checked, a stack starting off by 4 is aligned only at
+    // the first call
+    sim.update_config(&AssemblerConfig {
+        stack_pointer_sp: 0x7fffeffc,
+        ..config
+    })
+    .unwrap();
+    mid.start(RISCVSimulator::run);
+    assert_eq!(
+        warnings(&sim)[..2],
+        [
+            (11, AbiViolation::CalleeSaved),
+            (6, AbiViolation::MisalignedStack),
+        ]
+    );
+}
+
+#[test]
+fn test_uninit_check() {
+    let (mut sim, mut mid) = fake_sim("");
+    let code = "
+.data
+v: .word 1
+.text
+    la t1, v
+    lw a0, 0(t1)
+    addi sp, sp, -16
+    lw a1, 4(sp)
+    add a2, a3, a0
+    sw a0, 8(sp)
+    lw a4, 8(sp)
+    add a2, a3, a3
+";
+    load_program(&mut sim, code, &AssemblerConfig::default());
+    assert!(sim.get_uninit_reads().is_none());
+    let mut config = AssemblerConfig {
+        uninit_check: UninitCheck::Warn,
+        ..Default::default()
+    };
+    sim.update_config(&config).unwrap();
+    assert!(mid.start(RISCVSimulator::run));
+    let reads = |sim: &RISCVSimulator| {
+        sim.get_uninit_reads()
+            .unwrap()
+            .into_iter()
+            .map(|read| (read.line, read.source))
+            .collect::<Vec<_>>()
+    };
+    // each is reported once, written memory is initialised
+    assert_eq!(
+        reads(&sim),
+        vec![
+            (7, UninitSource::Memory(0x7fffefe4)),
+            (8, UninitSource::Register("a3".to_string())),
+        ]
+    );
+    let read = &sim.get_uninit_reads().unwrap()[1];
+    assert_eq!(
+        (read.pc, read.instruction.as_str()),
+        (0x400014, "add x12,x13,x10")
+    );
+
+    config.uninit_check = UninitCheck::Pause;
+    sim.update_config(&config).unwrap();
+    // the reading instruction completes before the pause
+    mid.start(RISCVSimulator::run);
+    assert!(sim.is_paused());
+    assert_eq!(sim.get_pc_idx(), Some(5));
+    mid.start(RISCVSimulator::resume);
+    assert_eq!(sim.get_pc_idx(), Some(6));
+    assert_eq!(reads(&sim).len(), 2);
+
+    // undo forgets the read of a3
+    sim.undo().unwrap();
+    assert_eq!(reads(&sim).len(), 1);
+    mid.start(RISCVSimulator::resume);
+    assert_eq!(sim.get_pc_idx(), Some(6));
+    mid.start(RISCVSimulator::resume);
+    assert!(!sim.is_paused());
+    assert_eq!(reads(&sim).len(), 2);
+}
+
+#[test]
+fn test_memory_fault() {
+    let (mut sim, mut mid) = fake_sim("");
+    let mut run = |code: &str, config: &AssemblerConfig| {
+        load_program(&mut sim, code, config);
+        assert!(sim.get_memory_fault().is_none());
+        assert!(!mid.start(RISCVSimulator::run));
+        sim.get_memory_fault().unwrap()
+    };
+
+    let fault = run(
+        "
+.text
+    li t0, 0x100
+    lw a0, 0(t0)
+",
+        &AssemblerConfig::default(),
+    );
+    assert_eq!(fault.kind, MemoryFaultKind::OutOfRange);
+    assert_eq!((fault.address, fault.width, fault.write), (0x100, 4, false));
+    assert_eq!(
+        (
+            fault.segment.as_str(),
+            fault.segment_start,
+            fault.segment_end
+        ),
+        (".text", 0x400000, 0x400008)
+    );
+    assert_eq!((fault.pc, fault.line), (0x400004, 3));
+    assert!(!fault.stack_overflow);
+    assert_eq!(
+        fault.message,
+        "Invalid memory access: 4-byte load at 0x00000100, 0x3fff00 bytes below .text \
+         (0x00400000-0x00400008) by `lw x10,0(x5)` on line 4"
+    );
+
+    // the stack grew past its limit and out of the data segment
+    let config = AssemblerConfig {
+        data_segment_limit_address: 0x1001ffff,
+        ..Default::default()
+    };
+    let fault = run(
+        "
+.text
+    li sp, 0x10030000
+    sh a0, 0(sp)
+",
+        &config,
+    );
+    assert_eq!(
+        (fault.address, fault.width, fault.write),
+        (0x10030000, 2, true)
+    );
+    assert_eq!(fault.segment, "stack");
+    assert!(fault.stack_overflow);
+    assert!(fault.message.ends_with("the stack overflowed"));
+
+    let fault = run(
+        "
+.rodata
+k: .word 1
+.text
+    la t1, k
+    sw zero, 0(t1)
+",
+        &AssemblerConfig::default(),
+    );
+    assert_eq!(fault.kind, MemoryFaultKind::ReadOnly);
+    assert_eq!(fault.segment, ".rodata");
+    assert!(fault
+        .message
+        .starts_with("Write to read-only section .rodata"));
+
+    // undo takes the faulting instruction back
+    sim.undo().unwrap();
+    assert!(sim.get_memory_fault().is_none());
+}
+
+#[test]
+fn test_self_modifying_code() {
+    let (mut sim, mid) = fake_sim("");
+    let run = |sim: &mut RISCVSimulator, code: &str, config: &AssemblerConfig| {
+        load_program(sim, code, config);
+        mid.start(RISCVSimulator::run)
+    };
+    // patches `addi a1, zero, 1` into `addi a1, zero, 3` and calls
+    // `addi a0, zero, 2; ret` in the data segment
+    let code = "
+.data
+f: .word 0x00200513 0x00008067
+.text
+    la t0, patch
+    li t1, 0x00300593
+    sw t1, 0(t0)
+    fence.i
+patch:
+    addi a1, zero, 1
+    la t2, f
+    jalr ra, 0(t2)
+    addi a2, zero, 4
+";
+    let mut config = AssemblerConfig {
+        self_modifying_code: true,
+        profiler: true,
+        ..Default::default()
+    };
+    assert!(run(&mut sim, code, &config));
+    assert_eq!(sim.get_register()[10..13], [2, 3, 4]);
+    // the two instructions in the data segment retire too
+    let report = sim.get_profile().unwrap();
+    let text: u64 = report.lines.iter().map(|line| line.count).sum();
+    assert_eq!((report.retired, text), (13, 11));
+    // the text is in memory, patched
+    sim.set_memory_return_range(MemoryReturnRange {
+        start: 0x400000,
+        len: 0x20,
+    })
+    .unwrap();
+    assert_eq!(sim.get_memory()[6], 0x00300593);
+    assert_eq!(sim.get_pc_idx(), None);
+
+    // the store alone makes the executed `addi a1, a1, 1` stale, the second
+    // pass runs `addi a1, a1, 10`
+    assert!(run(
+        &mut sim,
+        "
+.text
+    li t3, 2
+patch:
+    addi a1, a1, 1
+    la t0, patch
+    li t1, 0x00a58593
+    sw t1, 0(t0)
+    addi t3, t3, -1
+    bnez t3, patch
+",
+        &config
+    ));
+    assert_eq!(sim.get_register()[11], 11);
+
+    // data which isn't an instruction
+    assert!(!run(
+        &mut sim,
+        "
+.data
+f: .word 0
+.text
+    la t0, f
+    jr t0
+",
+        &config
+    ));
+
+    config.execution_mode = ExecutionMode::Pipeline;
+    assert!(sim.update_config(&config).is_err());
+    assert!(!run(&mut sim, code, &AssemblerConfig::default()));
+    assert_eq!(sim.get_memory_fault().unwrap().segment, ".text");
+}
+
+#[test]
+fn test_data_endianness() {
+    let config = AssemblerConfig {
+        data_endianness: Endianness::Big,
+        ..Default::default()
+    };
+    let code = "
+.data
+w: .word 0x12345678
+h: .half 0x1234 0
+.text
+    la t0, w
+    lw a0, 0(t0)
+    lw a1, 4(t0)
+    li t1, 0xabcd
+    sh t1, 6(t0)
+    lw a2, 4(t0)
+";
+    let (mut sim, mid) = run_program(code, &config);
+    assert!(mid.success);
+    assert_eq!(
+        sim.get_register()[10..13],
+        [0x12345678, 0x12340000, 0x1234abcd]
+    );
+
+    // memory words follow the data byte order
+    sim.set_memory_return_range(MemoryReturnRange {
+        start: 0x10010000,
+        len: 8,
+    })
+    .unwrap();
+    assert_eq!(sim.get_memory(), vec![0x12345678, 0x1234abcd]);
+    // instructions stay little-endian
+    let lw = &sim.get_raw_inst().as_ref().unwrap().instruction[2];
+    let (addr, code) = (lw.address, lw.code);
+    sim.set_memory_return_range(MemoryReturnRange {
+        start: addr as u64,
+        len: 4,
+    })
+    .unwrap();
+    assert_eq!(sim.get_memory(), vec![code]);
+
+    // views read the same bytes in either order
+    let view = |name: &str, endianness| MemoryView {
+        name: name.to_string(),
+        start: 0x10010000,
+        len: 8,
+        width: ElementWidth::Half,
+        format: DisplayFormat::Hex,
+        endianness,
+    };
+    sim.set_memory_view(view("be", Endianness::Big)).unwrap();
+    sim.set_memory_view(view("le", Endianness::Little)).unwrap();
+    let views = sim.get_memory_views();
+    assert_eq!(views[0].data, vec![0x1234, 0x5678, 0x1234, 0xabcd]);
+    assert_eq!(views[0].endianness, Endianness::Big);
+    assert_eq!(views[1].data, vec![0x3412, 0x7856, 0x3412, 0xcdab]);
+}
+
+#[test]
+fn test_trace() {
+    let code = "
+.data
+v: .word 0
+.text
+    la t0, v
+    li a0, 42
+    sw a0, 0(t0)
+    lw a1, 0(t0)
+    li a7, 1
+    ecall
+";
+    let config = AssemblerConfig {
+        trace: true,
+        ..Default::default()
+    };
+    let (mut sim, mid) = run_program(code, &config);
+    assert!(mid.success);
+
+    let spike = sim.export_trace(TraceFormat::Spike).unwrap();
+    let lines: Vec<&str> = spike.lines().collect();
+    assert_eq!(lines.len(), 7);
+    assert_eq!(
+        lines[3],
+        "core   0: 3 0x0040000c (0x00a2a023) mem 0x10010000 0x0000002a"
+    );
+    assert_eq!(
+        lines[4],
+        "core   0: 3 0x00400010 (0x0002a583) x11 0x0000002a mem 0x10010000"
+    );
+
+    let jsonl = sim.export_trace(TraceFormat::Jsonl).unwrap();
+    let ecall: serde_json::Value = serde_json::from_str(jsonl.lines().last().unwrap()).unwrap();
+    assert_eq!(ecall["disasm"], "ecall");
+    assert_eq!(ecall["output"], "42");
+
+    // undo drops the ecall
+    sim.undo().unwrap();
+    assert_eq!(
+        sim.export_trace(TraceFormat::Spike)
+            .unwrap()
+            .lines()
+            .count(),
+        6
+    );
+
+    sim.reset().unwrap();
+    assert_eq!(sim.export_trace(TraceFormat::Jsonl).unwrap(), "");
+    sim.update_config(&AssemblerConfig::default()).unwrap();
+    sim.export_trace(TraceFormat::Jsonl).unwrap_err();
+}
+
+#[test]
+fn test_run_limits() {
+    let (mut sim, mid) = fake_sim("");
+    let code = "
+.text
+    li t0, 0
+loop:
+    addi t0, t0, 1
+    j loop
+";
+    let run = |sim: &mut RISCVSimulator, limits: RunLimits| {
+        let config = AssemblerConfig {
+            run_limits: limits,
+            ..Default::default()
+        };
+        load_program(sim, code, &config);
+        mid.start(RISCVSimulator::run)
+    };
+
+    assert!(!run(
+        &mut sim,
+        RunLimits {
+            max_instructions: 101,
+            time_limit_ms: 0,
+        }
+    ));
+    assert_eq!(sim.get_exceeded_limit(), Some(RunLimit::Instructions));
+    assert_eq!(sim.get_register()[5], 50);
+    assert_eq!(sim.get_pc_idx(), Some(1));
+
+    assert!(!run(
+        &mut sim,
+        RunLimits {
+            max_instructions: 0,
+            time_limit_ms: 50,
+        }
+    ));
+    assert_eq!(sim.get_exceeded_limit(), Some(RunLimit::Time));
+
+    // the sleeps of an animated run don't count, 10 steps take 100 ms
+    sim.set_run_rate(100);
+    assert!(!run(
+        &mut sim,
+        RunLimits {
+            max_instructions: 10,
+            time_limit_ms: 50,
+        }
+    ));
+    assert_eq!(sim.get_exceeded_limit(), Some(RunLimit::Instructions));
+    sim.set_run_rate(0);
+
+    // a reset clears it
+    sim.reset().unwrap();
+    assert_eq!(sim.get_exceeded_limit(), None);
+}
+
+/// Records the deltas of an animated run.
+#[derive(Default)]
+struct ProgressSink {
+    deltas: Mutex<Vec<SimulatorDelta>>,
+    done: (Condvar, Mutex<bool>),
+}
+
+impl SimulatorEventSink for ProgressSink {
+    fn update(&self, _simulator: &dyn Simulator, _res: Optional, _paused: bool) {
+        *self.done.1.lock().unwrap() = true;
+        self.done.0.notify_one();
+    }
+
+    fn output(&self, _filepath: &str, _output: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn request_input(&self, _filepath: &str) -> Result<(), String> {
+        Err("No input".to_string())
+    }
+
+    fn delta(&self, delta: SimulatorDelta) {
+        self.deltas.lock().unwrap().push(delta);
+    }
+}
+
+#[test]
+fn test_run_rate() {
+    let mut sim = RISCVSimulator::new("");
+    let sink = Arc::new(ProgressSink::default());
+    sim.set_event_sink(Some(sink.clone()));
+    let code = "
+.text
+    li t0, 10
+loop:
+    addi t0, t0, -1
+    bnez t0, loop
+";
+    load_program(&mut sim, code, &AssemblerConfig::default());
+    sim.set_run_rate(100);
+    // loading notifies too
+    *sink.done.1.lock().unwrap() = false;
+    let start = std::time::Instant::now();
+    sim.run().unwrap();
+    let done = sink.done.1.lock().unwrap();
+    drop(sink.done.0.wait_while(done, |done| !*done).unwrap());
+    // 21 instructions at 100 per second
+    assert!(start.elapsed() >= std::time::Duration::from_millis(200));
+    assert_eq!(sim.get_register()[5], 0);
+
+    let deltas = sink.deltas.lock().unwrap();
+    assert!(deltas.len() >= 2);
+    // only changed registers are sent
+    for delta in deltas.iter().skip(1) {
+        assert!(delta.harts[0].registers.iter().all(|reg| reg.number == "5"));
+    }
+}
+
+#[test]
+fn test_delta() {
+    let (mut sim, mut mid) = fake_sim("");
+    let code = "
+.data
+v: .word 0
+.text
+    la t1, v
+    li t0, 9
+    sw t0, 0(t1)
+";
+    load_program(&mut sim, code, &AssemblerConfig::default());
+    let take = |sim: &RISCVSimulator| {
+        sim.take_delta(
+            Optional {
+                success: true,
+                message: String::new(),
+            },
+            true,
+        )
+    };
+    let regs = |delta: &SimulatorDelta| {
+        delta.harts[0]
+            .registers
+            .iter()
+            .map(|reg| (reg.number.clone(), reg.value))
+            .collect::<Vec<_>>()
+    };
+
+    // nothing is known after loading
+    let delta = take(&sim);
+    assert!(delta.resync);
+    assert!(delta.harts.is_empty());
+    let seq = delta.seq;
+
+    // `la` writes t1 twice
+    for _ in 0..3 {
+        mid.start(RISCVSimulator::step);
+    }
+    let delta = take(&sim);
+    assert!(!delta.resync);
+    assert_eq!(delta.seq, seq + 1);
+    assert_eq!(
+        regs(&delta),
+        vec![("5".to_string(), 9), ("6".to_string(), 0x10010000)]
+    );
+    assert!(delta.memory.is_empty());
+    assert_eq!(delta.harts[0].current_text, 3);
+
+    mid.start(RISCVSimulator::step);
+    let delta = take(&sim);
+    assert!(regs(&delta).is_empty());
+    assert_eq!(delta.memory.len(), 1);
+    assert_eq!(
+        (delta.memory[0].start, &delta.memory[0].data),
+        (0x10010000, &vec![9])
+    );
+
+    // undo writes the old word back
+    sim.undo().unwrap();
+    let delta = take(&sim);
+    assert_eq!(
+        (delta.memory[0].start, &delta.memory[0].data),
+        (0x10010000, &vec![0])
+    );
+
+    // a full update starts the sequence over
+    let seq = sim.resync();
+    assert_eq!(take(&sim).seq, seq + 1);
+    sim.set_memory_return_range(MemoryReturnRange {
+        start: 0x10010000,
+        len: 0x200,
+    })
+    .unwrap();
+    assert!(take(&sim).resync);
+}
+
+#[test]
+fn test_memory_views() {
+    let (mut sim, mut mid) = fake_sim("");
+    let code = "
+.data
+bytes: .byte 1 0xfe 3 4
+halves: .half 0xffff 2
+floats: .float 1.5
+.text
+    la t1, bytes
+    li t0, 7
+    sw t0, 0x100(t1)
+";
+    load_program(&mut sim, code, &AssemblerConfig::default());
+    let view = |name: &str, start, len, width, format| MemoryView {
+        name: name.to_string(),
+        start,
+        len,
+        width,
+        format,
+        endianness: Endianness::Little,
+    };
+
+    sim.set_memory_view(view(
+        "bytes",
+        0x10010000,
+        4,
+        ElementWidth::Byte,
+        DisplayFormat::Signed,
+    ))
+    .unwrap();
+    sim.set_memory_view(view(
+        "halves",
+        0x10010004,
+        4,
+        ElementWidth::Half,
+        DisplayFormat::Hex,
+    ))
+    .unwrap();
+    sim.set_memory_view(view(
+        "floats",
+        0x10010008,
+        4,
+        ElementWidth::Word,
+        DisplayFormat::Float,
+    ))
+    .unwrap();
+    let data = sim
+        .get_memory_views()
+        .into_iter()
+        .map(|view| (view.name, view.data))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        data,
+        vec![
+            ("bytes".to_string(), vec![1, 0xfe, 3, 4]),
+            ("halves".to_string(), vec![0xffff, 2]),
+            ("floats".to_string(), vec![1.5f32.to_bits()]),
+        ]
+    );
+
+    // misaligned, out of range or mismatched views are rejected
+    sim.set_memory_view(view("", 0, 4, ElementWidth::Byte, DisplayFormat::Hex))
+        .unwrap_err();
+    sim.set_memory_view(view("x", 1, 2, ElementWidth::Half, DisplayFormat::Hex))
+        .unwrap_err();
+    sim.set_memory_view(view(
+        "x",
+        0xfffffffc,
+        8,
+        ElementWidth::Word,
+        DisplayFormat::Hex,
+    ))
+    .unwrap_err();
+    sim.set_memory_view(view("x", 0, 4, ElementWidth::Half, DisplayFormat::Float))
+        .unwrap_err();
+
+    // a view with the same name replaces the old one
+    sim.set_memory_view(view(
+        "bytes",
+        0x10010000,
+        2,
+        ElementWidth::Byte,
+        DisplayFormat::Ascii,
+    ))
+    .unwrap();
+    assert_eq!(sim.get_memory_views().len(), 3);
+    assert_eq!(sim.get_memory_views()[0].data, vec![1, 0xfe]);
+    sim.remove_memory_view("halves").unwrap();
+    sim.remove_memory_view("halves").unwrap_err();
+    assert_eq!(sim.get_memory_views().len(), 2);
+
+    // writes to a view outside the memory return range are in the delta
+    sim.set_memory_return_range(MemoryReturnRange {
+        start: 0x10010000,
+        len: 4,
+    })
+    .unwrap();
+    sim.set_memory_view(view(
+        "heap",
+        0x10010100,
+        4,
+        ElementWidth::Word,
+        DisplayFormat::Unsigned,
+    ))
+    .unwrap();
+    sim.resync();
+    for _ in 0..4 {
+        mid.start(RISCVSimulator::step);
+    }
+    let delta = sim.take_delta(
+        Optional {
+            success: true,
+            message: String::new(),
+        },
+        false,
+    );
+    assert_eq!(delta.memory.len(), 1);
+    assert_eq!(
+        (delta.memory[0].start, &delta.memory[0].data),
+        (0x10010100, &vec![7])
+    );
+    assert_eq!(sim.get_memory_views()[2].data, vec![7]);
+}
+
+#[test]
+fn test_debugger_edit() {
+    let (mut sim, mut mid) = fake_sim("");
+    let code = "
+.data
+v: .word 5
+.text
+    li t0, 1
+    li t1, 2
+    li t2, 3
+";
+    load_program(&mut sim, code, &AssemblerConfig::default());
+    let text = AssemblerConfig::default().dot_text_base_address as u32;
+
+    // values are truncated to XLEN bits
+    sim.set_register(5, 42).unwrap();
+    sim.set_register(6, u64::MAX).unwrap();
+    assert_eq!(sim.get_register()[5..7], [42, 0xffffffff]);
+    sim.set_register(0, 1).unwrap_err();
+    sim.set_register(32, 1).unwrap_err();
+
+    // skip `li t1, 2`
+    mid.start(RISCVSimulator::step);
+    sim.set_pc(text + 8).unwrap();
+    mid.start(RISCVSimulator::step);
+    assert_eq!(sim.get_register()[5..8], [1, 0xffffffff, 3]);
+    sim.set_pc(text + 2).unwrap_err();
+    sim.set_pc(text + 12).unwrap_err();
+
+    sim.set_memory(0x10010000, &[9, 0]).unwrap();
+    assert_eq!(sim.get_memory()[0], 9);
+    sim.set_memory(text, &[0; 4]).unwrap_err();
+    sim.set_memory(0x10010000, &[0; 9]).unwrap_err();
+    sim.set_memory(0x10010000, &[]).unwrap_err();
+
+    // every edit is undone like an instruction
+    sim.undo().unwrap();
+    assert_eq!(sim.get_memory()[0], 5);
+    sim.undo().unwrap();
+    sim.undo().unwrap();
+    assert_eq!(sim.get_pc_idx(), Some(1));
+    sim.undo().unwrap();
+    sim.undo().unwrap();
+    sim.undo().unwrap();
+    assert_eq!(sim.get_register()[5..7], [0, 0]);
+    sim.undo().unwrap_err();
+}
+
+#[test]
+fn test_snapshot() {
+    let (mut sim, mut mid) = fake_sim("");
+    let code = "
+.data
+v: .word 5
+.text
+    li t0, 7
+    la t1, v
+    sw t0, 0(t1)
+    li a7, 5
+    ecall
+    addi t2, a0, 1
+";
+    load_program(&mut sim, code, &AssemblerConfig::default());
+
+    mid.start(RISCVSimulator::step);
+    sim.set_breakpoint(3, None).unwrap();
+    let snapshot = sim.save_snapshot().unwrap();
+    for _ in 0..3 {
+        mid.start(RISCVSimulator::step);
+    }
+    assert_eq!(sim.get_memory()[0], 7);
+    sim.restore_snapshot(&snapshot).unwrap();
+    assert_eq!(sim.get_memory()[0], 5);
+    assert_eq!(sim.get_register()[5], 7);
+    assert_eq!(sim.get_pc_idx(), Some(1));
+    assert!(sim.is_paused());
+    assert_eq!(sim.save_snapshot().unwrap(), snapshot);
+    // the history starts over
+    sim.undo().unwrap_err();
+
+    // a pending input is requested again
+    for _ in 0..4 {
+        mid.start(RISCVSimulator::step);
+    }
+    let waiting = sim
+        .save_snapshot()
+        .unwrap()
+        .replace("\"input\": null", "\"input\": \"INT\"");
+    mid.input = Some("41".to_string());
+    mid.expect_update();
+    sim.restore_snapshot(&waiting).unwrap();
+    assert!(sim.is_waiting_input());
+    mid.wait();
+    assert_eq!(sim.get_register()[7], 42);
+
+    let newer = snapshot.replace("\"version\": 1", "\"version\": 99");
+    assert!(sim.restore_snapshot(&newer).unwrap_err().contains("newer"));
+    sim.restore_snapshot("{}").unwrap_err();
+    load_program(
+        &mut sim,
+        &code.replace("li t0, 7", "li t0, 8"),
+        &AssemblerConfig::default(),
+    );
+    assert!(sim
+        .restore_snapshot(&snapshot)
+        .unwrap_err()
+        .contains("different program"));
+}
+
+#[test]
+fn test_watches() {
+    let (mut sim, mut mid) = fake_sim("");
+    let code = "
+.data
+counter: .word 3
+msg: .string \"hi\"
+.text
+    la a0, msg
+    li t0, 0x3fc00000
+    addi sp, sp, -16
+    sw t0, 8(sp)
+";
+    sim.set_watches(vec!["counter".to_string(), "1 +".to_string()]);
+    let values = |sim: &RISCVSimulator| {
+        sim.get_watches()
+            .into_iter()
+            .map(|watch| watch.value)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        values(&sim),
+        vec![
+            WatchValue::Error("No program loaded".to_string()),
+            WatchValue::Error("Unexpected end".to_string()),
+        ]
+    );
+
+    load_program(&mut sim, code, &AssemblerConfig::default());
+    assert!(mid.start(RISCVSimulator::run));
+    sim.set_watches(
+        [
+            "*(&counter) + 1",
+            "mem32[sp + 8]",
+            "float(mem32[sp + 8])",
+            "str[a0]",
+            "msg - counter",
+        ]
+        .map(str::to_string)
+        .to_vec(),
+    );
+    assert_eq!(
+        values(&sim),
+        vec![
+            WatchValue::Int(4),
+            WatchValue::Int(0x3fc00000),
+            WatchValue::Float(1.5),
+            WatchValue::Str("hi".to_string()),
+            WatchValue::Int(4),
+        ]
+    );
+}
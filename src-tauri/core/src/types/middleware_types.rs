@@ -0,0 +1,791 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{interface::parser::ParserResult, modules::riscv::basic::interface::parser::RISCV};
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct Optional {
+    pub success: bool,
+    pub message: String,
+}
+
+/// both start and len are aligned by 4
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+pub struct MemoryReturnRange {
+    pub start: u64,
+    pub len: u64,
+}
+
+impl Default for MemoryReturnRange {
+    fn default() -> Self {
+        Self {
+            start: 0x10010000,
+            len: 0x100,
+        }
+    }
+}
+
+/// Size of the elements of a memory view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ElementWidth {
+    Byte,
+    Half,
+    Word,
+}
+
+impl ElementWidth {
+    pub fn bytes(self) -> u64 {
+        match self {
+            ElementWidth::Byte => 1,
+            ElementWidth::Half => 2,
+            ElementWidth::Word => 4,
+        }
+    }
+}
+
+/// Byte order of data in memory, instructions are always little-endian.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+/// How the front end shows the elements of a memory view, `Float` needs
+/// `Word` elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DisplayFormat {
+    Signed,
+    Unsigned,
+    Hex,
+    Ascii,
+    Float,
+}
+
+/// A named memory window of a tab, both start and len are aligned by
+/// `width`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct MemoryView {
+    pub name: String,
+    pub start: u64,
+    pub len: u64,
+    pub width: ElementWidth,
+    pub format: DisplayFormat,
+    /// byte order the elements are read in, whatever the data endianness
+    #[serde(default)]
+    pub endianness: Endianness,
+}
+
+/// Contents of a memory view, `data` holds its elements zero-extended.
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+pub struct MemoryViewData {
+    pub name: String,
+    pub start: u64,
+    pub width: ElementWidth,
+    pub format: DisplayFormat,
+    pub endianness: Endianness,
+    pub data: Vec<Data>,
+}
+
+#[derive(Clone, Serialize)]
+pub enum AssembleResult {
+    Success(AssembleSuccess),
+    Error(Vec<AssembleError>),
+}
+
+#[derive(Clone, Serialize)]
+pub enum DumpResult {
+    Success(()),
+    Error(Vec<AssembleError>),
+}
+
+#[derive(Clone, Serialize)]
+pub struct AssembleSuccess {
+    pub text: Vec<AssembleText>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct AssembleText {
+    pub line: u64,
+    pub address: u32,
+    pub code: u32,
+    pub basic: String,
+}
+
+pub type Data = u32;
+
+#[derive(Clone, Serialize)]
+pub struct AssembleError {
+    pub line: u64,
+    pub column: u64,
+    pub msg: String,
+}
+
+#[derive(Default)]
+pub struct AssembleCache {
+    pub code: String,
+    pub parser_cache: Option<ParserResult<RISCV>>,
+    pub parser_result: Option<Vec<AssembleError>>,
+    pub assembler_result: Option<AssembleResult>,
+}
+
+/// `has_current_text`, `current_text` and `registers` belong to the hart
+/// `current_hart`, `harts` holds the state of every hart.
+#[derive(Clone, Serialize)]
+pub struct SimulatorData {
+    pub filepath: String,
+    pub success: bool,
+    pub paused: bool,
+    pub has_current_text: bool,
+    pub current_text: u64,
+    pub registers: Vec<Register>,
+    pub data: Vec<Data>,
+    /// every memory view in the order they were added
+    pub views: Vec<MemoryViewData>,
+    pub message: String,
+    pub current_hart: u64,
+    pub harts: Vec<HartData>,
+    /// only in pipeline mode
+    pub pipeline: Option<PipelineData>,
+    /// only if enabled
+    pub icache: Option<CacheData>,
+    pub dcache: Option<CacheData>,
+    pub branch_predictor: Option<BranchPredictorData>,
+    /// set if the run was stopped at a limit
+    pub limit: Option<RunLimit>,
+    /// set if the run was stopped at a memory fault
+    pub fault: Option<MemoryFault>,
+    /// the next `SimulatorDelta` follows it
+    pub seq: u64,
+    pub watches: Vec<WatchData>,
+}
+
+/// Changes since the update or delta with `seq - 1`. The front end has to
+/// request a full update after a gap or if `resync` is set, `harts` and
+/// `memory` are empty then.
+#[derive(Clone, Serialize)]
+pub struct SimulatorDelta {
+    pub filepath: String,
+    pub seq: u64,
+    pub resync: bool,
+    pub success: bool,
+    pub paused: bool,
+    pub message: String,
+    pub current_hart: u64,
+    /// every hart with only the registers written since the last emit
+    pub harts: Vec<HartData>,
+    /// written words in the memory return range and the memory views
+    pub memory: Vec<MemoryDelta>,
+    pub watches: Vec<WatchData>,
+}
+
+/// Words of memory starting at `start`, the front end updates every window
+/// showing them.
+#[derive(Clone, Serialize)]
+pub struct MemoryDelta {
+    pub start: u64,
+    pub data: Vec<Data>,
+}
+
+/// A watch expression evaluated on the current hart.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WatchData {
+    pub expression: String,
+    pub value: WatchValue,
+}
+
+/// `ERROR` holds why the expression couldn't be parsed or evaluated.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "UPPERCASE")]
+pub enum WatchValue {
+    Int(u64),
+    Float(f64),
+    Str(String),
+    Error(String),
+}
+
+#[derive(Clone, Serialize)]
+pub struct HartData {
+    pub id: u64,
+    pub has_current_text: bool,
+    pub current_text: u64,
+    pub registers: Vec<Register>,
+}
+
+/// `stages` holds the instruction index in IF, ID, EX, MEM and WB, `None` is
+/// a bubble.
+#[derive(Clone, Serialize)]
+pub struct PipelineData {
+    pub stages: Vec<Option<u64>>,
+    pub cycles: u64,
+    pub instructions: u64,
+    pub stalls: u64,
+    pub flushes: u64,
+    pub cpi: f64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct CacheData {
+    pub accesses: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub writebacks: u64,
+    pub hit_rate: f64,
+    pub last_access: Option<CacheAccess>,
+    pub lines: Vec<CacheLine>,
+}
+
+/// An access touching two blocks is reported as two accesses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct CacheAccess {
+    pub address: u32,
+    pub write: bool,
+    pub hit: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct CacheLine {
+    pub set: u64,
+    pub way: u64,
+    pub valid: bool,
+    pub dirty: bool,
+    pub tag: u32,
+}
+
+#[derive(Clone, Serialize)]
+pub struct BranchPredictorData {
+    pub predictions: u64,
+    pub mispredictions: u64,
+    pub accuracy: f64,
+    pub btb_hits: u64,
+    pub btb_misses: u64,
+    /// sorted by address
+    pub sites: Vec<BranchSiteData>,
+}
+
+/// A branch or jump of the program, `line` is the source line it was
+/// assembled from.
+#[derive(Clone, Serialize)]
+pub struct BranchSiteData {
+    pub line: u64,
+    pub address: u32,
+    pub predictions: u64,
+    pub mispredictions: u64,
+    pub accuracy: f64,
+}
+
+/// A calling convention violation, `line` is the instruction that made it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AbiWarning {
+    pub hart: u64,
+    pub line: u64,
+    pub kind: AbiViolation,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AbiViolation {
+    /// `s0`-`s11` differ on return
+    CalleeSaved,
+    /// `sp` differs on return
+    StackPointer,
+    /// `sp` isn't 16-byte aligned at a call
+    MisalignedStack,
+    /// a temporary is read after a call or return before being written
+    UninitializedTemporary,
+}
+
+/// A read of data never written, each register and byte is reported once.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UninitRead {
+    pub hart: u64,
+    /// address and disassembly of the reading instruction
+    pub pc: u32,
+    pub instruction: String,
+    pub line: u64,
+    pub source: UninitSource,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "UPPERCASE")]
+pub enum UninitSource {
+    /// ABI name of the register
+    Register(String),
+    /// first uninitialised byte read
+    Memory(u32),
+}
+
+/// Returned by `profile_report`, counts only cover retired instructions.
+#[derive(Clone, Serialize)]
+pub struct ProfileReport {
+    pub retired: u64,
+    pub mix: InstructionMix,
+    /// executed lines, hottest first
+    pub lines: Vec<LineProfile>,
+    /// every instruction of the program, by address
+    pub instructions: Vec<InstructionProfile>,
+}
+
+/// Atomics count as stores except `lr`, CSR and fence instructions as ALU.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct InstructionMix {
+    pub alu: u64,
+    pub load: u64,
+    pub store: u64,
+    pub branch: u64,
+    pub jump: u64,
+    pub ecall: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct LineProfile {
+    pub line: u64,
+    pub count: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct InstructionProfile {
+    pub address: u32,
+    pub line: u64,
+    pub count: u64,
+}
+
+/// A retired instruction, `size` is 2 for compressed instructions. `input`
+/// is the text entered for an input ecall.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TraceEntry {
+    pub hart: u64,
+    pub pc: u32,
+    pub code: u32,
+    pub size: u8,
+    pub disasm: String,
+    pub reg_writes: Vec<TraceRegWrite>,
+    pub mem_reads: Vec<TraceMemAccess>,
+    pub mem_writes: Vec<TraceMemAccess>,
+    pub output: String,
+    pub input: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct TraceRegWrite {
+    pub reg: u8,
+    pub value: u64,
+}
+
+/// `value` is read little endian.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct TraceMemAccess {
+    pub address: u32,
+    pub size: u8,
+    pub value: u64,
+}
+
+/// `Jsonl` writes a `TraceEntry` per line, `Spike` the commit log of
+/// `spike --log-commits`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TraceFormat {
+    Jsonl,
+    Spike,
+}
+
+/// Snapshot format written by this version. State added later, such as
+/// CSRs, gets `#[serde(default)]`, incompatible changes bump the version.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Machine state of a paused or stopped simulator. It only restores into
+/// the program it was taken from, `text` holds its encodings to check that.
+/// Caches, the pipeline, the profile, the trace and the undo history start
+/// over.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub xlen: Xlen,
+    pub text: Vec<u32>,
+    pub harts: Vec<HartSnapshot>,
+    pub current_hart: u64,
+    /// instructions left in the quantum of `current_hart`
+    pub quantum_left: u64,
+    pub scheduler_seed: u64,
+    /// every page holding a non-zero byte
+    pub memory: Vec<PageSnapshot>,
+    pub breakpoints: Vec<BreakpointSnapshot>,
+    /// the input ecall at the pc of `current_hart` waits for input
+    pub input: Option<InputKind>,
+}
+
+/// `pc` is the end of the text once the hart has finished.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HartSnapshot {
+    pub registers: Vec<u64>,
+    pub pc: u32,
+    pub reservation: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageSnapshot {
+    pub address: u32,
+    /// hexadecimal
+    pub data: String,
+}
+
+/// `harts` has bit `n` set if hart `n` breaks at `pc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakpointSnapshot {
+    pub pc: u32,
+    pub harts: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum InputKind {
+    Int,
+    String,
+    Char,
+}
+
+#[derive(Clone, Serialize)]
+pub struct Register {
+    pub name: String,
+    pub number: String,
+    pub value: u64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct SyscallOutput {
+    pub filepath: String,
+    pub data: String,
+}
+
+#[derive(Clone, Serialize)]
+pub struct SyscallRequest {
+    pub filepath: String,
+}
+
+/// Placement of a named section, `base_address` is rounded up to `alignment`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SectionConfig {
+    pub name: String,
+    pub base_address: u64,
+    pub alignment: u64,
+    pub read_only: bool,
+}
+
+/// Register width of the target, `RV64` enables the RV64I instructions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Xlen {
+    #[default]
+    Rv32,
+    Rv64,
+}
+
+/// What the simulator does on a read of a register or memory byte that was
+/// never written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum UninitCheck {
+    #[default]
+    Off,
+    /// collect it for `uninit_reads`
+    Warn,
+    /// collect it and pause after the reading instruction, the message of
+    /// the update is the one of the read
+    Pause,
+}
+
+/// How the simulator interleaves the harts, both are deterministic.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HartScheduler {
+    /// every hart runs `hart_quantum` instructions in turn
+    #[default]
+    RoundRobin,
+    /// every `hart_quantum` instructions a hart is drawn with
+    /// `hart_schedule_seed`
+    Random,
+}
+
+/// `Pipeline` steps cycle by cycle through a classic IF/ID/EX/MEM/WB
+/// pipeline, `Functional` steps instruction by instruction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ExecutionMode {
+    #[default]
+    Functional,
+    Pipeline,
+}
+
+/// Prediction made by the pipeline at fetch, control transfers resolve in EX.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StaticPrediction {
+    #[default]
+    NotTaken,
+    /// backward branches and `jal` taken, forward branches not taken
+    Btfn,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CacheReplacement {
+    #[default]
+    Lru,
+    Fifo,
+    Random,
+}
+
+/// `WriteBack` allocates a line on a write miss, `WriteThrough` doesn't.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CacheWritePolicy {
+    #[default]
+    WriteBack,
+    WriteThrough,
+}
+
+/// `size` and `block_size` are in bytes and powers of 2, `size` holds at
+/// least one set of `associativity` blocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub size: u64,
+    pub associativity: u64,
+    pub block_size: u64,
+    pub replacement: CacheReplacement,
+    pub write_policy: CacheWritePolicy,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            size: 1024,
+            associativity: 1,
+            block_size: 16,
+            replacement: CacheReplacement::Lru,
+            write_policy: CacheWritePolicy::WriteBack,
+        }
+    }
+}
+
+/// Direction predictor for conditional branches, jumps are always taken.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BranchPredictorKind {
+    StaticTaken,
+    #[default]
+    StaticNotTaken,
+    OneBit,
+    /// saturating counters, starting weakly not taken
+    TwoBit,
+    /// 2-bit counters indexed by the pc xor the global history
+    Gshare,
+}
+
+/// The counter table has `1 << table_bits` entries and gshare keeps
+/// `history_bits <= table_bits` outcomes. `btb_entries` is a power of 2, 0
+/// means taken targets are known at fetch except for `jalr`, which then always
+/// mispredicts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BranchPredictorConfig {
+    pub enabled: bool,
+    pub kind: BranchPredictorKind,
+    pub table_bits: u64,
+    pub history_bits: u64,
+    pub btb_entries: u64,
+}
+
+impl Default for BranchPredictorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: BranchPredictorKind::StaticNotTaken,
+            table_bits: 8,
+            history_bits: 8,
+            btb_entries: 0,
+        }
+    }
+}
+
+/// Limits of a run from `run` or `debug`, 0 means unlimited. The time only
+/// counts while the simulator runs, not while it is paused or sleeping
+/// between the steps of an animated run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunLimits {
+    /// retired instructions, undo takes them back
+    pub max_instructions: u64,
+    pub time_limit_ms: u64,
+}
+
+/// A load or store the simulator rejected, the run stops at it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MemoryFault {
+    pub kind: MemoryFaultKind,
+    /// addresses above 32 bits only come from RV64
+    pub address: u64,
+    pub width: u32,
+    pub write: bool,
+    /// the segment or section closest to the access, `[start, end)`
+    pub segment: String,
+    pub segment_start: u64,
+    pub segment_end: u64,
+    /// address and disassembly of the faulting instruction
+    pub pc: u32,
+    pub instruction: String,
+    pub line: u64,
+    /// `sp` is below `stack_limit_address`
+    pub stack_overflow: bool,
+    pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MemoryFaultKind {
+    /// outside the data segment, the stack and the writable sections
+    OutOfRange,
+    /// a store to a read-only section
+    ReadOnly,
+    /// an atomic access that isn't word aligned
+    Misaligned,
+}
+
+/// The limit a run was stopped at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RunLimit {
+    Instructions,
+    Time,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssemblerConfig {
+    pub memory_map_limit_address: u64,
+    pub kernel_space_high_address: u64,
+    pub mmio_base_address: u64,
+    pub kernel_space_base_address: u64,
+    pub user_space_high_address: u64,
+    pub data_segment_limit_address: u64,
+    pub stack_base_address: u64,
+    pub stack_pointer_sp: u64,
+    pub stack_limit_address: u64,
+    pub heap_base_address: u64,
+    pub dot_data_base_address: u64,
+    pub global_pointer_gp: u64,
+    pub data_segment_base_address: u64,
+    pub dot_extern_base_address: u64,
+    pub text_limit_address: u64,
+    pub dot_text_base_address: u64,
+    pub dot_rodata_base_address: u64,
+    pub dot_rodata_alignment: u64,
+    pub dot_bss_base_address: u64,
+    pub dot_bss_alignment: u64,
+    /// sections not listed here are placed right after the last placed
+    /// section, aligned by 4
+    pub sections: Vec<SectionConfig>,
+    /// encode eligible base instructions with their 16-bit C extension form
+    pub compress_instructions: bool,
+    pub xlen: Xlen,
+    /// number of harts sharing the memory, at most 64
+    pub hart_count: u64,
+    pub hart_scheduler: HartScheduler,
+    pub hart_quantum: u64,
+    pub hart_schedule_seed: u64,
+    /// hart `n` starts with `sp = stack_pointer_sp - n * hart_stack_size`
+    pub hart_stack_size: u64,
+    /// the pipeline only supports a single hart
+    pub execution_mode: ExecutionMode,
+    pub pipeline_forwarding: bool,
+    pub pipeline_prediction: StaticPrediction,
+    /// the caches are shared by all harts and only affect statistics
+    pub l1_icache: CacheConfig,
+    pub l1_dcache: CacheConfig,
+    /// shared by all harts and only affects statistics
+    pub branch_predictor: BranchPredictorConfig,
+    /// count executed instructions for `profile_report`
+    pub profiler: bool,
+    /// record every retired instruction for `export_trace`, the trace is not
+    /// bounded
+    pub trace: bool,
+    /// check calls and returns against the calling convention for
+    /// `abi_warnings`
+    pub abi_check: bool,
+    /// shadow every register and memory byte to catch reads of data never
+    /// written, see `uninit_reads`
+    pub uninit_check: UninitCheck,
+    /// byte order of `.half`, `.word`, `.dword`, `.float` and `.double` data
+    /// and of loads and stores, `get_memory` words follow it
+    pub data_endianness: Endianness,
+    /// keep the text in memory and decode instructions when fetched, so stores
+    /// may rewrite the code and jumps may enter data; the pipeline doesn't
+    /// support it
+    pub self_modifying_code: bool,
+    /// stop runaway programs
+    pub run_limits: RunLimits,
+}
+
+impl Default for AssemblerConfig {
+    fn default() -> Self {
+        Self {
+            memory_map_limit_address: 0xffffffff,
+            kernel_space_high_address: 0xffffffff,
+            mmio_base_address: 0xffff0000,
+            kernel_space_base_address: 0x80000000,
+            user_space_high_address: 0x7fffffff,
+            data_segment_limit_address: 0x7fffffff,
+            stack_base_address: 0x7ffffffc,
+            stack_pointer_sp: 0x7fffeff0,
+            stack_limit_address: 0x10040000,
+            heap_base_address: 0x10040000,
+            dot_data_base_address: 0x10010000,
+            global_pointer_gp: 0x10008000,
+            data_segment_base_address: 0x10000000,
+            dot_extern_base_address: 0x10000000,
+            text_limit_address: 0x0ffffffc,
+            dot_text_base_address: 0x00400000,
+            dot_rodata_base_address: 0x10020000,
+            dot_rodata_alignment: 0x10,
+            dot_bss_base_address: 0x10030000,
+            dot_bss_alignment: 0x10,
+            sections: Vec::new(),
+            compress_instructions: false,
+            xlen: Xlen::Rv32,
+            hart_count: 1,
+            hart_scheduler: HartScheduler::RoundRobin,
+            hart_quantum: 1,
+            hart_schedule_seed: 0,
+            hart_stack_size: 0x10000,
+            execution_mode: ExecutionMode::Functional,
+            pipeline_forwarding: true,
+            pipeline_prediction: StaticPrediction::NotTaken,
+            l1_icache: CacheConfig::default(),
+            l1_dcache: CacheConfig::default(),
+            branch_predictor: BranchPredictorConfig::default(),
+            profiler: false,
+            trace: false,
+            abi_check: false,
+            uninit_check: UninitCheck::Off,
+            data_endianness: Endianness::Little,
+            self_modifying_code: false,
+            run_limits: RunLimits::default(),
+        }
+    }
+}
+
+/// Use for event `front_update_content`
+#[derive(Clone, Serialize)]
+pub struct UpdateContent {
+    pub file_name: String,
+    pub op: i32,
+    pub start: (u64, u64),
+    pub end: (u64, u64),
+    pub content: String,
+}
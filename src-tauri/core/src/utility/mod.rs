@@ -0,0 +1,5 @@
+pub mod any;
+pub mod enum_map;
+pub mod ptr;
+
+mod debug;
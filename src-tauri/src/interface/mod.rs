@@ -1,5 +1,3 @@
-pub mod assembler;
-pub mod parser;
+pub use moras_core::interface::{assembler, parser, simulator};
 pub mod remote;
-pub mod simulator;
 pub mod storage;
@@ -0,0 +1,43 @@
+#![cfg_attr(
+    debug_assertions,
+    allow(dead_code),
+    allow(unused_variables),
+    allow(unused_mut),
+    allow(unused_assignments),
+    allow(unreachable_code),
+    allow(unused_macros)
+)]
+#![feature(linked_list_cursors)]
+
+use std::sync::{Arc, Mutex};
+
+pub use moras_core::{dprintln, simulator};
+use once_cell::sync::Lazy;
+use tauri::AppHandle;
+use types::rpc_types;
+
+use crate::remote::Modification;
+
+/// front_end api is under [`middleware.rs`]
+///
+/// [`middleware.rs`]: crate::modules::riscv::middleware
+pub mod interface;
+
+pub mod io;
+pub mod menu;
+pub mod modules;
+pub mod remote;
+pub mod storage;
+pub mod types;
+pub mod utility;
+
+#[cfg(test)]
+mod tests;
+
+pub static APP_HANDLE: Lazy<Arc<Mutex<Option<AppHandle>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(None)));
+static CURSOR_LIST: Lazy<Arc<Mutex<rpc_types::CursorList>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Default::default())));
+
+static HISTORY: Lazy<Arc<Mutex<Vec<Modification>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
@@ -1,48 +1,14 @@
-#![cfg_attr(
-    debug_assertions,
-    allow(dead_code),
-    allow(unused_variables),
-    allow(unused_mut),
-    allow(unused_assignments),
-    allow(unreachable_code),
-    allow(unused_macros)
-)]
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-#![feature(linked_list_cursors)]
 
-use std::sync::{Arc, Mutex};
+use moras_lib::{
+    menu,
+    modules::riscv::middleware::frontend_api,
+    types::{middleware_types, rpc_types},
+    APP_HANDLE,
+};
+use tauri::Manager;
 
-use modules::riscv::middleware::frontend_api;
-use once_cell::sync::Lazy;
-use tauri::{AppHandle, Manager};
-use types::{middleware_types, rpc_types};
-
-use crate::remote::Modification;
-
-/// front_end api is under [`middleware.rs`]
-///
-/// [`middleware.rs`]: crate::modules::riscv::middleware
-mod interface;
-
-mod io;
-mod menu;
-mod modules;
-mod remote;
-mod simulator;
-mod storage;
-mod types;
-mod utility;
-
-#[cfg(test)]
-mod tests;
-
-static APP_HANDLE: Lazy<Arc<Mutex<Option<AppHandle>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
-static CURSOR_LIST: Lazy<Arc<Mutex<rpc_types::CursorList>>> =
-    Lazy::new(|| Arc::new(Mutex::new(Default::default())));
-
-static HISTORY: Lazy<Arc<Mutex<Vec<Modification>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(Vec::new())));
 fn main() {
     tauri::Builder::default()
         .menu(menu::init_menu())
@@ -71,6 +37,9 @@ fn main() {
             frontend_api::read_tab,
             frontend_api::write_tab,
             frontend_api::set_return_data_range,
+            frontend_api::set_memory_view,
+            frontend_api::remove_memory_view,
+            frontend_api::set_watches,
             frontend_api::assembly,
             frontend_api::dump,
             frontend_api::run,
@@ -78,7 +47,19 @@ fn main() {
             frontend_api::stop,
             frontend_api::resume,
             frontend_api::step,
+            frontend_api::step_hart,
+            frontend_api::set_run_rate,
+            frontend_api::resync_simulator,
+            frontend_api::profile_report,
+            frontend_api::abi_warnings,
+            frontend_api::uninit_reads,
+            frontend_api::export_trace,
+            frontend_api::save_snapshot,
+            frontend_api::restore_snapshot,
             frontend_api::undo,
+            frontend_api::set_register,
+            frontend_api::set_memory,
+            frontend_api::set_pc,
             frontend_api::reset,
             frontend_api::set_breakpoint,
             frontend_api::remove_breakpoint,
@@ -16,15 +16,17 @@ use crate::{
     dprintln,
     interface::storage::FileShareStatus::{Client, Server},
     io::file_io,
-    modules::riscv::basic::interface::{
-        assembler::RiscVAssembler,
-        parser::{RISCVExtension, RISCVParser},
+    modules::riscv::{
+        basic::interface::{
+            assembler::RiscVAssembler,
+            parser::{RISCVExtension, RISCVParser},
+        },
+        middleware::backend_api,
     },
-    simulator::simulator::RISCVSimulator,
     storage::rope_store,
     types::{
         menu_types,
-        middleware_types::{Tab, TabMap},
+        middleware_types::{Tab, TabMap, Xlen},
         rpc_types::RpcState,
         ResultVoid,
     },
@@ -246,9 +248,9 @@ fn new_tab(event: &WindowMenuEvent, file_path: &Path) -> ResultVoid {
     let tab_map = event.window().state::<TabMap>();
     let tab = Tab {
         text: Box::new(content),
-        parser: Box::new(RISCVParser::new(&vec![RISCVExtension::RV32I])),
+        parser: Box::new(RISCVParser::new(&RISCVExtension::for_xlen(Xlen::Rv32))),
         assembler: Box::new(RiscVAssembler::new()),
-        simulator: Box::new(RISCVSimulator::new(file_path.to_str().unwrap())),
+        simulator: backend_api::new_simulator(file_path.to_str().unwrap()),
         assembly_cache: Default::default(),
     };
     tab_map
@@ -5,6 +5,7 @@ pub mod frontend_api {
 
     use tauri::{async_runtime::block_on, State, Window};
 
+    use super::backend_api;
     use crate::{
         dprintln,
         interface::{
@@ -21,7 +22,6 @@ pub mod frontend_api {
             parser::{RISCVExtension, RISCVParser, RISCV},
         },
         remote::{Modification, OpRange},
-        simulator::simulator::RISCVSimulator,
         storage::rope_store,
         types::{
             menu_types::OpenShareFile,
@@ -52,9 +52,9 @@ pub mod frontend_api {
             Ok(content) => {
                 let tab = Tab {
                     text: Box::new(content),
-                    parser: Box::new(RISCVParser::new(&vec![RISCVExtension::RV32I])),
+                    parser: Box::new(RISCVParser::new(&RISCVExtension::for_xlen(Xlen::Rv32))),
                     assembler: Box::new(RiscVAssembler::new()),
-                    simulator: Box::new(RISCVSimulator::new(filepath)),
+                    simulator: backend_api::new_simulator(filepath),
                     assembly_cache: Default::default(),
                 };
                 tab_map
@@ -357,6 +357,87 @@ pub mod frontend_api {
         }
     }
 
+    /// Adds a named memory view to the current tab, or replaces the view with
+    /// the same name. Every view is sent in `front_simulator_update`.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `view`: [MemoryView](crate::types::middleware_types::MemoryView)
+    ///   - `name`: string
+    ///   - `start`: u64, aligned to the width
+    ///   - `len`: u64, aligned to the width
+    ///   - `width`: `BYTE`, `HALF` or `WORD`
+    ///   - `format`: `SIGNED`, `UNSIGNED`, `HEX`, `ASCII` or `FLOAT`, `FLOAT`
+    ///     needs `WORD`
+    ///   - `endianness`: `LITTLE` (default) or `BIG`, the byte order the
+    ///     elements are read in
+    ///
+    /// Returns `Optional` indicating the success or failure of the operation.
+    #[tauri::command]
+    pub fn set_memory_view(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        view: MemoryView,
+    ) -> Optional {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let mut lock = tab_map.tabs.lock().unwrap();
+        let tab = lock.get_mut(&name).unwrap();
+        match tab.simulator.set_memory_view(view) {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e.to_string(),
+            },
+        }
+    }
+
+    /// Removes a named memory view of the current tab.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `name`: Name of the view.
+    ///
+    /// Returns `Optional` indicating the success or failure of the operation.
+    #[tauri::command]
+    pub fn remove_memory_view(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        name: String,
+    ) -> Optional {
+        let tab_name = cur_tab_name.name.lock().unwrap().clone();
+        let mut lock = tab_map.tabs.lock().unwrap();
+        let tab = lock.get_mut(&tab_name).unwrap();
+        match tab.simulator.remove_memory_view(&name) {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e.to_string(),
+            },
+        }
+    }
+
+    /// Replaces the watch expressions of the current tab, they are evaluated
+    /// in every `front_simulator_update` and `front_simulator_delta`.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `expressions`: The expressions, e.g. `mem32[sp + 8]`, `*(&counter)`,
+    ///   `str[a0]` or `float(a1)`, F registers aren't simulated.
+    #[tauri::command]
+    pub fn set_watches(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        expressions: Vec<String>,
+    ) {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let mut lock = tab_map.tabs.lock().unwrap();
+        let tab = lock.get_mut(&name).unwrap();
+        tab.simulator.set_watches(expressions);
+    }
+
     /// Assembles the code in the currently active tab.
     /// - `cur_tab_name`: State containing the current tab name.
     /// - `tab_map`: State containing the map of all tabs.
@@ -582,6 +663,157 @@ pub mod frontend_api {
         }
     }
 
+    /// Steps one hart through the code in the currently active tab.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `hart`: Index of the hart to step.
+    ///
+    /// Returns `Optional` indicating whether the step was successful.
+    #[tauri::command]
+    pub fn step_hart(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        hart: u64,
+    ) -> Optional {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let mut lock = tab_map.tabs.lock().unwrap();
+        let tab = lock.get_mut(&name).unwrap();
+        match tab.simulator.step_hart(hart as usize) {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e.to_string(),
+            },
+        }
+    }
+
+    /// Sets the speed of runs in the currently active tab, also during a run.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `rate`: Instructions per second, 0 runs at full speed.
+    ///
+    /// Animated runs emit `front_simulator_delta` events, see
+    /// [`simulator_delta`](super::backend_api::simulator_delta).
+    #[tauri::command]
+    pub fn set_run_rate(cur_tab_name: State<CurTabName>, tab_map: State<TabMap>, rate: u64) {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let lock = tab_map.tabs.lock().unwrap();
+        lock.get(&name).unwrap().simulator.set_run_rate(rate);
+    }
+
+    /// Gets the full state of the currently active tab's simulator, for when
+    /// a `front_simulator_delta` event was missed.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    ///
+    /// Returns `SimulatorData` as in `front_simulator_update`, the next delta
+    /// follows its `seq`.
+    #[tauri::command]
+    pub fn resync_simulator(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+    ) -> SimulatorData {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let lock = tab_map.tabs.lock().unwrap();
+        let simulator = lock.get(&name).unwrap().simulator.as_ref();
+        backend_api::simulator_data(
+            simulator,
+            Optional {
+                success: true,
+                message: "resync".to_string(),
+            },
+            simulator.is_paused(),
+        )
+    }
+
+    /// Gets the execution profile of the currently active tab's simulator.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    ///
+    /// Returns `ProfileReport` with the execution counts since the last reset,
+    /// or `None` if the profiler is disabled in the assembler settings.
+    #[tauri::command]
+    pub fn profile_report(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+    ) -> Option<ProfileReport> {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let lock = tab_map.tabs.lock().unwrap();
+        lock.get(&name).unwrap().simulator.get_profile()
+    }
+
+    /// Gets the calling convention violations found by the currently active
+    /// tab's simulator.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    ///
+    /// Returns the `AbiWarning`s since the last reset, each with its source
+    /// line, or `None` if `abi_check` is disabled in the assembler settings.
+    #[tauri::command]
+    pub fn abi_warnings(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+    ) -> Option<Vec<AbiWarning>> {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let lock = tab_map.tabs.lock().unwrap();
+        lock.get(&name).unwrap().simulator.get_abi_warnings()
+    }
+
+    /// Gets the reads of uninitialised data found by the currently active
+    /// tab's simulator.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    ///
+    /// Returns the `UninitRead`s since the last reset, each with the address,
+    /// instruction and source line, or `None` if `uninit_check` is `OFF` in
+    /// the assembler settings.
+    #[tauri::command]
+    pub fn uninit_reads(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+    ) -> Option<Vec<UninitRead>> {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let lock = tab_map.tabs.lock().unwrap();
+        lock.get(&name).unwrap().simulator.get_uninit_reads()
+    }
+
+    /// Exports the execution trace of the currently active tab's simulator.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `format`: `JSONL` or `SPIKE`.
+    /// - `filepath`: File to write the trace to.
+    ///
+    /// Returns `Optional` indicating whether the trace was written.
+    #[tauri::command]
+    pub fn export_trace(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        format: TraceFormat,
+        filepath: &str,
+    ) -> Optional {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let lock = tab_map.tabs.lock().unwrap();
+        let res = lock
+            .get(&name)
+            .unwrap()
+            .simulator
+            .export_trace(format)
+            .and_then(|trace| file_io::write_file_str(filepath, &trace).map_err(|e| e.to_string()));
+        match res {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e,
+            },
+        }
+    }
+
     /// Resets the state of the currently active tab's simulator.
     /// - `cur_tab_name`: State containing the current tab name.
     /// - `tab_map`: State containing the map of all tabs.
@@ -604,6 +836,74 @@ pub mod frontend_api {
         }
     }
 
+    /// Saves the machine state of the currently active tab's simulator while
+    /// it is paused or stopped.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `filepath`: File to write the
+    ///   [Snapshot](crate::types::middleware_types::Snapshot) to, as JSON.
+    ///
+    /// Returns `Optional` indicating whether the snapshot was written.
+    #[tauri::command]
+    pub fn save_snapshot(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        filepath: &str,
+    ) -> Optional {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let lock = tab_map.tabs.lock().unwrap();
+        let res = lock
+            .get(&name)
+            .unwrap()
+            .simulator
+            .save_snapshot()
+            .and_then(|snapshot| {
+                file_io::write_file_str(filepath, &snapshot).map_err(|e| e.to_string())
+            });
+        match res {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e,
+            },
+        }
+    }
+
+    /// Restores a snapshot into the currently active tab's simulator, which
+    /// has to be paused or stopped with the program of the snapshot
+    /// assembled. The simulator is paused afterwards.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `filepath`: File written by `save_snapshot`.
+    ///
+    /// Returns `Optional` indicating whether the snapshot was restored.
+    #[tauri::command]
+    pub fn restore_snapshot(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        filepath: &str,
+    ) -> Optional {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let mut lock = tab_map.tabs.lock().unwrap();
+        let tab = lock.get_mut(&name).unwrap();
+        let res = file_io::read_file_str(filepath)
+            .map_err(|e| e.to_string())
+            .and_then(|snapshot| tab.simulator.restore_snapshot(&snapshot));
+        match res {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e,
+            },
+        }
+    }
+
     /// Undoes the last instruction for current activate tab's simulator.
     /// - `cur_tab_name`: State containing the current tab name.
     /// - `tab_map`: State containing the map of all tabs.
@@ -626,10 +926,101 @@ pub mod frontend_api {
         }
     }
 
+    /// Sets a register of the current hart while the simulator of the current
+    /// tab is paused or stopped, undo reverts it.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `idx`: Register number, `x0` can't be written.
+    /// - `value`: New value, truncated to XLEN bits.
+    ///
+    /// Returns `Optional` indicating whether the register was set.
+    #[tauri::command]
+    pub fn set_register(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        idx: usize,
+        value: u64,
+    ) -> Optional {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let mut lock = tab_map.tabs.lock().unwrap();
+        let tab = lock.get_mut(&name).unwrap();
+        match tab.simulator.set_register(idx, value) {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e.to_string(),
+            },
+        }
+    }
+
+    /// Writes memory while the simulator of the current tab is paused or
+    /// stopped, undo reverts it.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `addr`: Address of the first byte.
+    /// - `data`: At most 8 bytes, the same segments as for stores are writable.
+    ///
+    /// Returns `Optional` indicating whether the memory was written.
+    #[tauri::command]
+    pub fn set_memory(
+        cur_tab_name: State<CurTabName>,
+        tab_map: State<TabMap>,
+        addr: u64,
+        data: Vec<u8>,
+    ) -> Optional {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let mut lock = tab_map.tabs.lock().unwrap();
+        let tab = lock.get_mut(&name).unwrap();
+        match u32::try_from(addr)
+            .map_err(|_| "Invalid address".to_string())
+            .and_then(|addr| tab.simulator.set_memory(addr, &data))
+        {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e.to_string(),
+            },
+        }
+    }
+
+    /// Moves the pc of the current hart while the simulator of the current tab
+    /// is paused or stopped, undo reverts it.
+    /// - `cur_tab_name`: State containing the current tab name.
+    /// - `tab_map`: State containing the map of all tabs.
+    /// - `addr`: Address of an instruction in the text.
+    ///
+    /// Returns `Optional` indicating whether the pc was set.
+    #[tauri::command]
+    pub fn set_pc(cur_tab_name: State<CurTabName>, tab_map: State<TabMap>, addr: u64) -> Optional {
+        let name = cur_tab_name.name.lock().unwrap().clone();
+        let mut lock = tab_map.tabs.lock().unwrap();
+        let tab = lock.get_mut(&name).unwrap();
+        match u32::try_from(addr)
+            .map_err(|_| "Invalid address".to_string())
+            .and_then(|addr| tab.simulator.set_pc(addr))
+        {
+            Ok(_) => Optional {
+                success: true,
+                message: String::new(),
+            },
+            Err(e) => Optional {
+                success: false,
+                message: e.to_string(),
+            },
+        }
+    }
+
     /// Sets a breakpoint at a specified line in the code of the current tab.
     /// - `cur_tab_name`: State containing the current tab name.
     /// - `tab_map`: State containing the map of all tabs.
     /// - `line`: Line number at which to set the breakpoint.
+    /// - `hart`: Hart the breakpoint applies to, every hart if omitted.
     ///
     /// Returns `Optional` indicating whether the breakpoint was successfully
     /// set.
@@ -638,11 +1029,15 @@ pub mod frontend_api {
         cur_tab_name: State<CurTabName>,
         tab_map: State<TabMap>,
         line: u64,
+        hart: Option<u64>,
     ) -> Optional {
         let name = cur_tab_name.name.lock().unwrap().clone();
         let mut lock = tab_map.tabs.lock().unwrap();
         let tab = lock.get_mut(&name).unwrap();
-        match tab.simulator.set_breakpoint(line as usize) {
+        match tab
+            .simulator
+            .set_breakpoint(line as usize, hart.map(|hart| hart as usize))
+        {
             Ok(_) => Optional {
                 success: true,
                 message: String::new(),
@@ -658,6 +1053,7 @@ pub mod frontend_api {
     /// - `cur_tab_name`: State containing the current tab name.
     /// - `tab_map`: State containing the map of all tabs.
     /// - `line`: Line number at which to remove the breakpoint.
+    /// - `hart`: Hart the breakpoint applies to, every hart if omitted.
     ///
     /// Returns `Optional` indicating whether the breakpoint was successfully
     /// removed.
@@ -666,11 +1062,15 @@ pub mod frontend_api {
         cur_tab_name: State<CurTabName>,
         tab_map: State<TabMap>,
         line: u64,
+        hart: Option<u64>,
     ) -> Optional {
         let name = cur_tab_name.name.lock().unwrap().clone();
         let mut lock = tab_map.tabs.lock().unwrap();
         let tab = lock.get_mut(&name).unwrap();
-        match tab.simulator.remove_breakpoint(line as usize) {
+        match tab
+            .simulator
+            .remove_breakpoint(line as usize, hart.map(|hart| hart as usize))
+        {
             Ok(_) => Optional {
                 success: true,
                 message: String::new(),
@@ -732,6 +1132,11 @@ pub mod frontend_api {
             };
         }
         tab.assembler.update_config(&settings);
+        // the register width decides which operators the tab accepts
+        let mut parser = RISCVParser::new(&RISCVExtension::for_xlen(settings.xlen));
+        parser.set_data_endianness(settings.data_endianness);
+        tab.parser = Box::new(parser);
+        tab.assembly_cache = Default::default();
         Optional {
             success: true,
             message: String::new(),
@@ -851,9 +1256,9 @@ pub mod frontend_api {
                 };
                 let client_tab = Tab {
                     text: Box::new(client_text),
-                    parser: Box::new(RISCVParser::new(&vec![RISCVExtension::RV32I])),
+                    parser: Box::new(RISCVParser::new(&RISCVExtension::for_xlen(Xlen::Rv32))),
                     assembler: Box::new(RiscVAssembler::new()),
-                    simulator: Box::new(RISCVSimulator::new(&val.0)),
+                    simulator: backend_api::new_simulator(&val.0),
                     assembly_cache: Default::default(),
                 };
                 tab_map
@@ -944,22 +1349,60 @@ pub mod frontend_api {
 /// to emit event to the frontend, and the frontend needs to handle the event by
 /// `listen`.
 pub mod backend_api {
+    use std::sync::Arc;
+
     use strum::VariantArray;
     use tauri::Manager;
 
     use crate::{
-        interface::simulator::Simulator,
+        dprintln,
+        interface::simulator::{Simulator, SimulatorEventSink},
         modules::riscv::basic::interface::parser::RV32IRegister,
+        simulator::simulator::RISCVSimulator,
         types::middleware_types::{
+            HartData,
             Optional,
             Register,
             SimulatorData,
+            SimulatorDelta,
             SyscallOutput,
             SyscallRequest,
         },
         APP_HANDLE,
     };
 
+    /// Forwards the simulator events to the frontend.
+    pub struct TauriEventSink;
+
+    impl SimulatorEventSink for TauriEventSink {
+        fn update(&self, simulator: &dyn Simulator, res: Optional, paused: bool) {
+            if let Err(e) = simulator_update(simulator, res, paused) {
+                dprintln!("{}", e);
+            }
+        }
+
+        fn output(&self, filepath: &str, output: &str) -> Result<(), String> {
+            syscall_output_print(filepath, output)
+        }
+
+        fn request_input(&self, filepath: &str) -> Result<(), String> {
+            syscall_input_request(filepath)
+        }
+
+        fn delta(&self, delta: SimulatorDelta) {
+            if let Err(e) = simulator_delta(delta) {
+                dprintln!("{}", e);
+            }
+        }
+    }
+
+    /// Simulator of the tab `filepath` reporting to the frontend.
+    pub fn new_simulator(filepath: &str) -> Box<dyn Simulator> {
+        let mut simulator = RISCVSimulator::new(filepath);
+        simulator.set_event_sink(Some(Arc::new(TauriEventSink)));
+        Box::new(simulator)
+    }
+
     /// Emits a simulator update event to the frontend.
     /// - `simulator`: Simulator instance to update its state.
     /// - `simulator_res`: Result of the simulator operation.
@@ -979,34 +1422,35 @@ pub mod backend_api {
     /// - `current_text`: u64
     /// - `registers`: Vec<[Register](crate::types::middleware_types::Register)>
     /// - `data`: Vec<u32>
+    /// - `views`:
+    ///   Vec<[MemoryViewData](crate::types::middleware_types::MemoryViewData)>
     /// - `message`: string
+    /// - `current_hart`: u64
+    /// - `harts`: Vec<[HartData](crate::types::middleware_types::HartData)>
+    /// - `pipeline`:
+    ///   Option<[PipelineData](crate::types::middleware_types::PipelineData)>
+    /// - `icache`, `dcache`:
+    ///   Option<[CacheData](crate::types::middleware_types::CacheData)>
+    /// - `branch_predictor`:
+    ///   Option<[BranchPredictorData](crate::types::middleware_types::BranchPredictorData)>
+    /// - `limit`: Option<[RunLimit](crate::types::middleware_types::RunLimit)>,
+    ///   set if the run stopped at a limit of the config
+    /// - `fault`: Option<[MemoryFault](crate::types::middleware_types::MemoryFault)>,
+    ///   set if the run stopped at a rejected load or store
+    /// - `seq`: u64, the next `front_simulator_delta` follows it
+    /// - `watches`: Vec<[WatchData](crate::types::middleware_types::WatchData)>
+    ///
+    /// `has_current_text`, `current_text` and `registers` are those of the
+    /// current hart.
     pub fn simulator_update(
-        simulator: &mut dyn Simulator,
+        simulator: &dyn Simulator,
         simulator_res: Optional,
         paused: bool,
     ) -> Result<(), String> {
         if let Some(app_handle) = APP_HANDLE.lock().unwrap().as_ref() {
             if let Ok(_) = app_handle.emit_all(
                 "front_simulator_update",
-                SimulatorData {
-                    filepath: simulator.get_filepath().to_string(),
-                    success: simulator_res.success,
-                    paused,
-                    has_current_text: simulator.get_pc_idx().is_some(),
-                    current_text: simulator.get_pc_idx().unwrap_or(0) as u64,
-                    registers: simulator
-                        .get_register()
-                        .iter()
-                        .enumerate()
-                        .map(|(i, &val)| Register {
-                            name: RV32IRegister::VARIANTS[i].to_string(),
-                            number: i.to_string(),
-                            value: val as u64,
-                        })
-                        .collect(),
-                    data: simulator.get_memory(),
-                    message: simulator_res.message,
-                },
+                simulator_data(simulator, simulator_res, paused),
             ) {
                 Ok(())
             } else {
@@ -1017,6 +1461,93 @@ pub mod backend_api {
         }
     }
 
+    /// Full state of `simulator`, the change tracking starts over from it.
+    pub fn simulator_data(
+        simulator: &dyn Simulator,
+        simulator_res: Optional,
+        paused: bool,
+    ) -> SimulatorData {
+        SimulatorData {
+            filepath: simulator.get_filepath().to_string(),
+            success: simulator_res.success,
+            paused,
+            has_current_text: simulator.get_pc_idx().is_some(),
+            current_text: simulator.get_pc_idx().unwrap_or(0) as u64,
+            registers: registers(simulator.get_register()),
+            data: simulator.get_memory(),
+            views: simulator.get_memory_views(),
+            message: simulator_res.message,
+            current_hart: simulator.get_current_hart() as u64,
+            harts: (0..simulator.get_hart_count())
+                .map(|hart| HartData {
+                    id: hart as u64,
+                    has_current_text: simulator.get_hart_pc_idx(hart).is_some(),
+                    current_text: simulator.get_hart_pc_idx(hart).unwrap_or(0) as u64,
+                    registers: registers(simulator.get_hart_register(hart)),
+                })
+                .collect(),
+            pipeline: simulator.get_pipeline(),
+            icache: simulator.get_icache(),
+            dcache: simulator.get_dcache(),
+            branch_predictor: simulator.get_branch_predictor(),
+            limit: simulator.get_exceeded_limit(),
+            fault: simulator.get_memory_fault(),
+            seq: simulator.resync(),
+            watches: simulator.get_watches(),
+        }
+    }
+
+    /// Emits the changes of an animated run to the frontend.
+    /// - `delta`: Registers and memory written since the last update or delta.
+    ///
+    /// Returns `Result` indicating the success or failure of the event
+    /// emission.
+    ///
+    /// This function will emit a `front_simulator_delta` event to the
+    /// frontend at most every 50 ms while a run is slowed down by
+    /// `set_run_rate`, pauses and stops still emit `front_simulator_update`.
+    /// The frontend calls `resync_simulator` if `seq` doesn't follow the
+    /// previous event or `resync` is set.
+    ///
+    /// [SimulatorDelta](crate::types::middleware_types::SimulatorDelta):
+    /// - `filepath`: string
+    /// - `seq`: u64
+    /// - `resync`: bool
+    /// - `success`: bool
+    /// - `paused`: bool
+    /// - `message`: string
+    /// - `current_hart`: u64
+    /// - `harts`: Vec<[HartData](crate::types::middleware_types::HartData)>,
+    ///   only with the written registers
+    /// - `memory`: Vec<[MemoryDelta](crate::types::middleware_types::MemoryDelta)>,
+    ///   the written words of the memory return range
+    /// - `watches`:
+    ///   Vec<[WatchData](crate::types::middleware_types::WatchData)>, all of
+    ///   them
+    pub fn simulator_delta(delta: SimulatorDelta) -> Result<(), String> {
+        if let Some(app_handle) = APP_HANDLE.lock().unwrap().as_ref() {
+            if let Ok(_) = app_handle.emit_all("front_simulator_delta", delta) {
+                Ok(())
+            } else {
+                Err("Failed to emit simulator delta event!".to_string())
+            }
+        } else {
+            Err("AppHandle is not initialized!".to_string())
+        }
+    }
+
+    fn registers(values: &[u64]) -> Vec<Register> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &val)| Register {
+                name: RV32IRegister::VARIANTS[i].to_string(),
+                number: i.to_string(),
+                value: val,
+            })
+            .collect()
+    }
+
     /// Emits a print syscall output event to the frontend.
     /// - `pathname`: Identifier for the tab to which the output should be sent.
     /// - `output`: Output to be printed.
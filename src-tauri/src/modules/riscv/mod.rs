@@ -1,7 +1,5 @@
-pub mod basic;
+pub use moras_core::modules::riscv::{basic, rv32a, rv32c, rv32f, rv32i, rv64i, rvb};
 /// You Should ignore any Window, State<> type in the function signature, as
 /// they are used for Tauri's state management. You only need to focus on the
 /// function signature, other type parameters and the return type.
 pub mod middleware;
-pub mod rv32f;
-pub mod rv32i;
@@ -1,6 +0,0 @@
-#[allow(arithmetic_overflow)]
-mod instruction;
-mod memory;
-pub mod simulator;
-#[cfg(test)]
-mod test;
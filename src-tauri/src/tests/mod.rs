@@ -1,4 +1 @@
-mod assembler;
-mod parser;
 mod remote;
-mod simulator;
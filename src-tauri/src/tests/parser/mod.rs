@@ -1,2 +0,0 @@
-mod rv32i;
-mod sample;
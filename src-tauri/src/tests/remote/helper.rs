@@ -5,12 +5,14 @@ use tauri::async_runtime::block_on;
 use super::{CURSOR_LIST, TAB_MAP};
 use crate::{
     interface::remote::RpcClient,
-    modules::riscv::basic::interface::{
-        assembler::RiscVAssembler,
-        parser::{RISCVExtension, RISCVParser},
+    modules::riscv::{
+        basic::interface::{
+            assembler::RiscVAssembler,
+            parser::{RISCVExtension, RISCVParser},
+        },
+        middleware::backend_api,
     },
     remote::{client::RpcClientImpl, server::RpcServerImpl, utils::get_free_port},
-    simulator::simulator::RISCVSimulator,
     storage::rope_store,
     tests::remote::{MAX_PORT_RETRY, TEST_FILE_NAME, TEST_PASSWD},
     types::middleware_types::{Tab, TabMap},
@@ -40,7 +42,7 @@ pub(super) fn init_test_server(content: &str) -> Result<RpcServerImpl, Box<dyn E
             text: Box::new(content),
             parser: Box::new(RISCVParser::new(&vec![RISCVExtension::RV32I])),
             assembler: Box::new(RiscVAssembler::new()),
-            simulator: Box::new(RISCVSimulator::new(TEST_FILE_NAME)),
+            simulator: backend_api::new_simulator(TEST_FILE_NAME),
             assembly_cache: Default::default(),
         };
         static_tab.insert(TEST_FILE_NAME.to_string(), tab);
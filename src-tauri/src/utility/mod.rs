@@ -1,7 +1,3 @@
-pub mod any;
-pub mod enum_map;
-pub mod ptr;
+pub use moras_core::utility::{any, enum_map, ptr};
 pub mod state_helper;
 pub mod text_helper;
-
-mod debug;
@@ -22,6 +22,7 @@ import { invoke } from '@tauri-apps/api/tauri';
 
 export default function AssemblerSettingsPage() {
   const [choice, setChoice] = useState('default');
+  const [xlen, setXlen] = useState('RV32');
 
   const value_table = {
     default: {
@@ -32,7 +33,7 @@ export default function AssemblerSettingsPage() {
       user_space_high_address: 0x7fffffff,
       data_segment_limit_address: 0x7fffffff,
       stack_base_address: 0x7ffffffc,
-      stack_pointer_sp: 0x7fffeffc,
+      stack_pointer_sp: 0x7fffeff0,
       stack_limit_address: 0x10040000,
       heap_base_address: 0x10040000,
       dot_data_base_address: 0x10010000,
@@ -41,6 +42,10 @@ export default function AssemblerSettingsPage() {
       dot_extern_base_address: 0x10000000,
       text_limit_address: 0x0ffffffc,
       dot_text_base_address: 0x00400000,
+      dot_rodata_base_address: 0x10020000,
+      dot_rodata_alignment: 0x10,
+      dot_bss_base_address: 0x10030000,
+      dot_bss_alignment: 0x10,
     },
     compact_data_0: {
       memory_map_limit_address: 0x00007fff,
@@ -50,7 +55,7 @@ export default function AssemblerSettingsPage() {
       user_space_high_address: 0x00003fff,
       data_segment_limit_address: 0x00002fff,
       stack_base_address: 0x00002ffc,
-      stack_pointer_sp: 0x00002ffc,
+      stack_pointer_sp: 0x00002ff0,
       stack_limit_address: 0x00002000,
       heap_base_address: 0x00002000,
       dot_data_base_address: 0x00000000,
@@ -59,6 +64,10 @@ export default function AssemblerSettingsPage() {
       dot_extern_base_address: 0x00001000,
       text_limit_address: 0x00003ffc,
       dot_text_base_address: 0x00003000,
+      dot_rodata_base_address: 0x00000800,
+      dot_rodata_alignment: 0x10,
+      dot_bss_base_address: 0x00000c00,
+      dot_bss_alignment: 0x10,
     },
     compact_text_0: {
       memory_map_limit_address: 0x00007fff,
@@ -68,7 +77,7 @@ export default function AssemblerSettingsPage() {
       user_space_high_address: 0x00003fff,
       data_segment_limit_address: 0x00003fff,
       stack_base_address: 0x00003ffc,
-      stack_pointer_sp: 0x00003ffc,
+      stack_pointer_sp: 0x00003ff0,
       stack_limit_address: 0x00003000,
       heap_base_address: 0x00003000,
       dot_data_base_address: 0x00002000,
@@ -77,6 +86,10 @@ export default function AssemblerSettingsPage() {
       dot_extern_base_address: 0x00001000,
       text_limit_address: 0x00000ffc,
       dot_text_base_address: 0x00000000,
+      dot_rodata_base_address: 0x00002800,
+      dot_rodata_alignment: 0x10,
+      dot_bss_base_address: 0x00002c00,
+      dot_bss_alignment: 0x10,
     },
   };
 
@@ -129,9 +142,10 @@ export default function AssemblerSettingsPage() {
 
   const handleAssemblerSettingsChange = async () => {
     // send the new settings to the backend
-    console.log('New settings: ', value_table[choice]);
+    const settings = { ...value_table[choice], xlen: xlen };
+    console.log('New settings: ', settings);
     let result = await invoke('update_assembler_settings', {
-      settings: value_table[choice],
+      settings: settings,
     });
     if (result.success) {
       alert('Assembler settings changed successfully!');
@@ -155,6 +169,15 @@ export default function AssemblerSettingsPage() {
           <Radio value='compact_data_0'>Compact, Data at Address 0</Radio>
           <Radio value='compact_text_0'>Compact, Text at Address 0</Radio>
         </RadioGroup>
+        <RadioGroup
+          label='XLEN'
+          orientation='horizontal'
+          defaultValue='RV32'
+          onValueChange={value => setXlen(value)}
+        >
+          <Radio value='RV32'>RV32</Radio>
+          <Radio value='RV64'>RV64</Radio>
+        </RadioGroup>
       </CardHeader>
       <Divider />
       <CardBody>{generateTable(choice)}</CardBody>